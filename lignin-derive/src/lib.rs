@@ -0,0 +1,238 @@
+//! Derives [`lignin`](https://docs.rs/lignin)'s `Vdom` trait on custom types,
+//! so that they take part in transitive thread-safety inference like `lignin`'s own VDOM types.
+//!
+//! See `lignin::auto_safety` for how this inference works.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{
+//!   auto_safety::{Align as _, AutoSafe, VdomSeal},
+//!   Node, ThreadBound, ThreadSafe, ThreadSafety,
+//! };
+//! use lignin_derive::Vdom;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Vdom)]
+//! #[repr(C)]
+//! pub struct Card<'a, S: ThreadSafety> {
+//!   pub title: Node<'a, S>,
+//!   pub footer: Option<&'a Node<'a, S>>,
+//! }
+//!
+//! // SAFETY: `#[repr(C)]` with fields that are `Align` across `ThreadSafety`s.
+//! unsafe impl<'a, S: ThreadSafety> VdomSeal for Card<'a, S> {}
+//!
+//! fn card<'a>(title: Node<'a, ThreadSafe>) -> impl AutoSafe<Card<'a, ThreadBound>> {
+//!   Card { title, footer: None }
+//! }
+//!
+//! let bound: Card<ThreadBound> = card("Title".into()).align();
+//! ```
+//!
+//! # Safety
+//!
+//! The derived `unsafe` `Align` implementation reinterprets values and references between variants of the type that differ only in `ThreadSafety`.
+//! Its soundness rests on the `unsafe` supertrait `lignin::auto_safety::VdomSeal`, which the derive doesn't implement.
+//! Implement it manually, which asserts that all these variants share their layout.
+//!
+//! This is the case for example if the type is `#[repr(C)]` or `#[repr(transparent)]`,
+//! since the field types that mention the `ThreadSafety` parameter are required to have matching layouts themselves.
+//!
+//! Without that implementation, the derived code doesn't compile:
+//!
+//! ```compile_fail
+//! use lignin::{Node, ThreadSafety};
+//! use lignin_derive::Vdom;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Vdom)]
+//! pub struct Unsealed<'a, S: ThreadSafety>(Node<'a, S>);
+//! ```
+//!
+//! # Requirements
+//!
+//! The deriving type must have exactly one type parameter bounded by `ThreadSafety`, which becomes its `Vdom::ThreadSafety`.
+//!
+//! Each field type that mentions this parameter must itself implement `Align` across `ThreadSafety`s,
+//! which is the case for `lignin`'s VDOM types, [`Option`]s, references, arrays and slices of them, and other derived types.
+//! [`PhantomData`](`core::marker::PhantomData`) fields are exempt from this.
+//!
+//! The `Vdom` trait additionally requires [`Debug`], [`Clone`], [`Copy`], [`PartialEq`], [`Eq`], [`PartialOrd`], [`Ord`] and [`Hash`].
+//!
+//! # Limitations
+//!
+//! Rust's orphan rules prevent implementing `lignin::auto_safety::Deanonymize` on derived types,
+//! so calling `.deanonymize()` on an opaque `impl AutoSafe<Custom<ThreadBound>>` always resolves to the [`ThreadBound`](https://docs.rs/lignin/0.1/lignin/struct.ThreadBound.html) variant.
+//! Access the fields of the returned value directly or declare [`ThreadSafe`](https://docs.rs/lignin/0.1/lignin/struct.ThreadSafe.html) explicitly where that's needed.
+
+#![doc(html_root_url = "https://docs.rs/lignin-derive/0.1.0")]
+#![warn(clippy::pedantic, missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::{
+	parse_macro_input, parse_quote,
+	spanned::Spanned,
+	visit::Visit,
+	visit_mut::{self, VisitMut},
+	Data, DeriveInput, Error, GenericParam, Ident, Type, TypeParamBound, WherePredicate,
+};
+
+/// Implements `lignin::Vdom` and `lignin::auto_safety::Align` (and with it `AutoSafe`) on a custom type.
+///
+/// `lignin::auto_safety::VdomSeal` must be implemented manually.
+/// See the crate documentation for safety, requirements and limitations.
+#[proc_macro_derive(Vdom)]
+pub fn derive_vdom(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	derive(&input)
+		.unwrap_or_else(Error::into_compile_error)
+		.into()
+}
+
+fn derive(input: &DeriveInput) -> Result<TokenStream2, Error> {
+	let name = &input.ident;
+	let s = thread_safety_parameter(input)?;
+	let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+	let s1 = Ident::new("__LigninS1", Span::call_site());
+	let s2 = Ident::new("__LigninS2", Span::call_site());
+
+	let mut align_generics = input.generics.clone();
+	align_generics.params = align_generics
+		.params
+		.into_iter()
+		.filter(|param| !matches!(param, GenericParam::Type(param) if param.ident == *s))
+		.collect();
+	align_generics.params.push(parse_quote!(#s1));
+	align_generics.params.push(parse_quote!(#s2));
+	let align_where = align_generics.make_where_clause();
+	align_where.predicates = align_where
+		.predicates
+		.iter()
+		.cloned()
+		.map(|mut predicate| {
+			Substitute { s, with: &s1 }.visit_where_predicate_mut(&mut predicate);
+			predicate
+		})
+		.collect();
+	align_where
+		.predicates
+		.push(parse_quote!(#s1: ::lignin::ThreadSafety + ::core::convert::Into<#s2>));
+	align_where
+		.predicates
+		.push(parse_quote!(#s2: ::lignin::ThreadSafety));
+	for ty in field_types(&input.data) {
+		if !Mentions::check(ty, s) || is_phantom_data(ty) {
+			continue;
+		}
+		let (from, to) = (substitute(ty, s, &s1), substitute(ty, s, &s2));
+		let predicate: WherePredicate = parse_quote!(#from: ::lignin::auto_safety::Align<#to>);
+		align_where.predicates.push(predicate);
+	}
+	let (align_impl_generics, _, align_where_clause) = align_generics.split_for_impl();
+	let from_type = substitute(&parse_quote!(#name #type_generics), s, &s1);
+	let to_type = substitute(&parse_quote!(#name #type_generics), s, &s2);
+
+	// `VdomSeal` isn't implemented here: Only the deriving crate can vouch that all instances of the type share their layout.
+	Ok(quote! {
+		impl #impl_generics ::lignin::Vdom for #name #type_generics #where_clause {
+			type ThreadSafety = #s;
+		}
+
+		// SAFETY: `Vdom` requires `VdomSeal`, which asserts that this only changes layout-neutral `ThreadSafety` arguments.
+		unsafe impl #align_impl_generics ::lignin::auto_safety::Align<#to_type> for #from_type #align_where_clause {}
+	})
+}
+
+/// Finds the single type parameter bounded by `ThreadSafety`, either inline or in the `where` clause.
+fn thread_safety_parameter(input: &DeriveInput) -> Result<&Ident, Error> {
+	let is_thread_safety = |bound: &TypeParamBound| match bound {
+		TypeParamBound::Trait(bound) => bound
+			.path
+			.segments
+			.last()
+			.map_or(false, |segment| segment.ident == "ThreadSafety"),
+		TypeParamBound::Lifetime(_) => false,
+	};
+	let where_bounded = |ident: &Ident| {
+		input.generics.where_clause.as_ref().map_or(false, |where_clause| {
+			where_clause.predicates.iter().any(|predicate| match predicate {
+				WherePredicate::Type(predicate) => {
+					matches!(&predicate.bounded_ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident(ident))
+						&& predicate.bounds.iter().any(is_thread_safety)
+				}
+				_ => false,
+			})
+		})
+	};
+
+	let mut candidates = input
+		.generics
+		.type_params()
+		.filter(|param| param.bounds.iter().any(is_thread_safety) || where_bounded(&param.ident))
+		.map(|param| &param.ident);
+	match (candidates.next(), candidates.next()) {
+		(Some(s), None) => Ok(s),
+		(None, _) => Err(Error::new(
+			input.generics.span(),
+			"`#[derive(Vdom)]` requires a type parameter bounded by `ThreadSafety`",
+		)),
+		(Some(_), Some(second)) => Err(Error::new(
+			second.span(),
+			"`#[derive(Vdom)]` supports only one type parameter bounded by `ThreadSafety`",
+		)),
+	}
+}
+
+fn field_types(data: &Data) -> Vec<&Type> {
+	match data {
+		Data::Struct(data) => data.fields.iter().map(|field| &field.ty).collect(),
+		Data::Enum(data) => data
+			.variants
+			.iter()
+			.flat_map(|variant| variant.fields.iter().map(|field| &field.ty))
+			.collect(),
+		Data::Union(data) => data.fields.named.iter().map(|field| &field.ty).collect(),
+	}
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+	matches!(ty, Type::Path(path) if path.path.segments.last().map_or(false, |segment| segment.ident == "PhantomData"))
+}
+
+fn substitute(ty: &Type, s: &Ident, with: &Ident) -> TokenStream2 {
+	let mut ty = ty.clone();
+	Substitute { s, with }.visit_type_mut(&mut ty);
+	ty.into_token_stream()
+}
+
+struct Mentions<'a> {
+	s: &'a Ident,
+	found: bool,
+}
+impl<'a> Mentions<'a> {
+	fn check(ty: &Type, s: &'a Ident) -> bool {
+		let mut mentions = Self { s, found: false };
+		mentions.visit_type(ty);
+		mentions.found
+	}
+}
+impl<'a, 'ast> Visit<'ast> for Mentions<'a> {
+	fn visit_ident(&mut self, ident: &'ast Ident) {
+		self.found |= ident == self.s;
+	}
+}
+
+struct Substitute<'a> {
+	s: &'a Ident,
+	with: &'a Ident,
+}
+impl<'a> VisitMut for Substitute<'a> {
+	fn visit_ident_mut(&mut self, ident: &mut Ident) {
+		if ident == self.s {
+			*ident = self.with.clone();
+		}
+		visit_mut::visit_ident_mut(self, ident);
+	}
+}
@@ -0,0 +1,104 @@
+use lignin::{
+	auto_safety::{Align as _, AutoSafe, VdomSeal},
+	Node, ThreadBound, ThreadSafe, ThreadSafety,
+};
+use lignin_derive::Vdom;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Vdom)]
+#[repr(C)]
+struct Card<'a, S: ThreadSafety> {
+	title: Node<'a, S>,
+	footer: Option<&'a Node<'a, S>>,
+	items: &'a [Node<'a, S>],
+	name: &'a str,
+}
+// SAFETY: `#[repr(C)]` with fields that are `Align` across `ThreadSafety`s.
+unsafe impl<'a, S: ThreadSafety> VdomSeal for Card<'a, S> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Vdom)]
+#[repr(C)]
+enum Slot<'a, S>
+where
+	S: ThreadSafety,
+{
+	Empty(PhantomData<S>),
+	Card(Card<'a, S>),
+	Pair([Node<'a, S>; 2]),
+}
+// SAFETY: `#[repr(C)]` with fields that are `Align` across `ThreadSafety`s.
+unsafe impl<'a, S> VdomSeal for Slot<'a, S> where S: ThreadSafety {}
+
+fn card<'a, S: ThreadSafety>(title: Node<'a, S>) -> Card<'a, S> {
+	Card {
+		title,
+		footer: None,
+		items: &[],
+		name: "card",
+	}
+}
+
+fn inferred_safe<'a>() -> impl AutoSafe<Card<'a, ThreadBound>> {
+	card::<ThreadSafe>("safe".into())
+}
+
+fn inferred_bound<'a>() -> impl AutoSafe<Card<'a, ThreadBound>> {
+	card::<ThreadBound>("bound".into())
+}
+
+fn inferred_slot<'a>() -> impl AutoSafe<Slot<'a, ThreadBound>> {
+	Slot::Card(inferred_safe().deanonymize())
+}
+
+fn assert_safe<T: Send + Sync>(_: &T) {}
+
+#[test]
+fn inference() {
+	assert_safe(&inferred_safe());
+
+	let safe: Card<ThreadBound> = inferred_safe().deanonymize();
+	assert_eq!(safe, card("safe".into()));
+	let bound: Card<ThreadBound> = inferred_bound().deanonymize();
+	assert_eq!(bound, card("bound".into()));
+
+	let slot: Slot<ThreadBound> = inferred_slot().deanonymize();
+	assert_eq!(slot, Slot::Card(card("safe".into())));
+}
+
+#[test]
+fn align() {
+	let footer = Node::Multi(&[]);
+	let safe = Card::<ThreadSafe> {
+		footer: Some(&footer),
+		..card("title".into())
+	};
+	let bound: Card<ThreadBound> = safe.align();
+	assert!(matches!(bound.title, Node::Text { text: "title", .. }));
+	assert_eq!(bound.footer, Some(&Node::Multi(&[])));
+
+	let slot = Slot::<ThreadSafe>::Pair(["a".into(), "b".into()]);
+	let bound: &Slot<ThreadBound> = slot.align_ref();
+	assert_eq!(bound, &Slot::Pair(["a".into(), "b".into()]));
+}
+
+#[test]
+fn containers_of_derived() {
+	let cards = [card::<ThreadSafe>("a".into()), card("b".into())];
+	let bound: Option<&[Card<ThreadBound>]> = Some(&cards[..]).align();
+	assert_eq!(bound.map(<[_]>::len), Some(2));
+}
+
+#[test]
+fn thread_safety() {
+	fn thread_safety<V: lignin::Vdom>(_: &V) -> &'static str {
+		std::any::type_name::<V::ThreadSafety>()
+	}
+	assert_eq!(
+		thread_safety(&card::<ThreadSafe>(Node::Multi(&[]))),
+		std::any::type_name::<ThreadSafe>()
+	);
+	assert_eq!(
+		thread_safety(&Slot::<ThreadBound>::Empty(PhantomData)),
+		std::any::type_name::<ThreadBound>()
+	);
+}
@@ -0,0 +1,185 @@
+//! Allocator abstraction for building VDOM graphs.
+//!
+//! `lignin` itself doesn't allocate, but helpers like [`memo`](`crate::memo::memo`) need to place [`Node`](`crate::Node`)s somewhere.
+//! [`Arena`] abstracts over the bump allocators typically used for this.
+//!
+//! With the `"bumpalo"` feature, [`Arena`] is implemented for [`bumpalo::Bump`](https://docs.rs/bumpalo/3/bumpalo/struct.Bump.html).
+//!
+//! [`Node::copy_into`] moves a VDOM graph into a different [`Arena`], for example to keep it past the end of a frame.
+
+use crate::{
+	Attribute, AttributeValue, Element, ElementCreationOptions, EventBinding, Node, Portal,
+	Property, PropertyValue, ReorderableFragment, ShadowRoot, StyleDeclaration, ThreadSafety,
+	TrustedHtml,
+};
+
+/// A (typically bump) allocator that VDOM values can be placed in.
+///
+/// VDOM values are [`Copy`], so allocations are never dropped individually.
+pub trait Arena {
+	/// Moves `value` into the arena and returns a reference to it.
+	fn alloc<T: Copy>(&self, value: T) -> &T;
+
+	/// Copies `slice` into the arena and returns a reference to the copy.
+	fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T];
+
+	/// Copies `string` into the arena and returns a reference to the copy.
+	fn alloc_str(&self, string: &str) -> &str;
+
+	/// Allocates a slice of length `len` in the arena, with each element initialised to `f(index)`.
+	fn alloc_slice_fill_with<T: Copy>(&self, len: usize, f: impl FnMut(usize) -> T) -> &[T];
+}
+
+#[cfg(feature = "bumpalo")]
+impl Arena for bumpalo::Bump {
+	fn alloc<T: Copy>(&self, value: T) -> &T {
+		bumpalo::Bump::alloc(self, value)
+	}
+
+	fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T] {
+		bumpalo::Bump::alloc_slice_copy(self, slice)
+	}
+
+	fn alloc_str(&self, string: &str) -> &str {
+		bumpalo::Bump::alloc_str(self, string)
+	}
+
+	fn alloc_slice_fill_with<T: Copy>(&self, len: usize, f: impl FnMut(usize) -> T) -> &[T] {
+		bumpalo::Bump::alloc_slice_fill_with(self, len, f)
+	}
+}
+
+impl<'a, S: ThreadSafety> Node<'a, S> {
+	/// Deeply copies this [`Node`] into `arena`, so that the copy is independent of this [`Node`]'s storage.
+	///
+	/// Strings and [`CallbackRef`](`crate::CallbackRef`)s are copied as-is, so the copy compares equal to the original.
+	///
+	/// # Panics
+	///
+	/// Iff a [`Node::RemnantSite`] is encountered, as those aren't implemented yet.
+	#[must_use]
+	pub fn copy_into<'b, A: Arena + ?Sized>(&self, arena: &'b A) -> Node<'b, S> {
+		match *self {
+			Node::Comment {
+				comment,
+				dom_binding,
+			} => Node::Comment {
+				comment: arena.alloc_str(comment),
+				dom_binding,
+			},
+			Node::HtmlElement {
+				element,
+				dom_binding,
+			} => Node::HtmlElement {
+				element: arena.alloc(copy_element(element, arena)),
+				dom_binding,
+			},
+			Node::MathMlElement {
+				element,
+				dom_binding,
+			} => Node::MathMlElement {
+				element: arena.alloc(copy_element(element, arena)),
+				dom_binding,
+			},
+			Node::SvgElement {
+				element,
+				dom_binding,
+			} => Node::SvgElement {
+				element: arena.alloc(copy_element(element, arena)),
+				dom_binding,
+			},
+			Node::Memoized { state_key, content } => Node::Memoized {
+				state_key,
+				content: arena.alloc(content.copy_into(arena)),
+			},
+			Node::Multi(nodes) => {
+				Node::Multi(arena.alloc_slice_fill_with(nodes.len(), |i| nodes[i].copy_into(arena)))
+			}
+			Node::Keyed(pairs) => {
+				Node::Keyed(
+					arena.alloc_slice_fill_with(pairs.len(), |i| ReorderableFragment {
+						dom_key: pairs[i].dom_key,
+						content: pairs[i].content.copy_into(arena),
+					}),
+				)
+			}
+			Node::Text { text, dom_binding } => Node::Text {
+				text: arena.alloc_str(text),
+				dom_binding,
+			},
+			Node::RawHtml(html) => {
+				Node::RawHtml(TrustedHtml::assume_trusted(arena.alloc_str(html.as_str())))
+			}
+			Node::Portal(portal) => Node::Portal(arena.alloc(Portal {
+				target: arena.alloc_str(portal.target),
+				content: portal.content.copy_into(arena),
+				dom_binding: portal.dom_binding,
+			})),
+			Node::HeadContribution {
+				element,
+				dom_binding,
+			} => Node::HeadContribution {
+				element: arena.alloc(copy_element(element, arena)),
+				dom_binding,
+			},
+			Node::RemnantSite(_) => todo!("Copying `Node::RemnantSite`"),
+		}
+	}
+}
+
+fn copy_element<'b, S: ThreadSafety, A: Arena + ?Sized>(
+	element: &Element<'_, S>,
+	arena: &'b A,
+) -> Element<'b, S> {
+	let str = |string: &str| -> &'b str { arena.alloc_str(string) };
+	let attributes = element.attributes;
+	let properties = element.properties;
+	let classes = element.classes;
+	let styles = element.styles;
+	let event_bindings = element.event_bindings;
+	Element {
+		name: str(element.name),
+		creation_options: ElementCreationOptions::new()
+			.with_is(element.creation_options.is().map(str)),
+		attributes: arena.alloc_slice_fill_with(attributes.len(), |i| Attribute {
+			name: str(attributes[i].name),
+			value: match attributes[i].value {
+				AttributeValue::String(value) => AttributeValue::String(str(value)),
+				AttributeValue::Bool(value) => AttributeValue::Bool(value),
+				AttributeValue::Integer(value) => AttributeValue::Integer(value),
+				AttributeValue::Float(value) => AttributeValue::Float(value),
+				AttributeValue::Tokens(tokens) => AttributeValue::Tokens(
+					arena.alloc_slice_fill_with(tokens.len(), |i| str(tokens[i])),
+				),
+			},
+		}),
+		properties: arena.alloc_slice_fill_with(properties.len(), |i| Property {
+			name: str(properties[i].name),
+			value: match properties[i].value {
+				PropertyValue::Bool(value) => PropertyValue::Bool(value),
+				PropertyValue::Integer(value) => PropertyValue::Integer(value),
+				PropertyValue::Number(value) => PropertyValue::Number(value),
+				PropertyValue::String(value) => PropertyValue::String(str(value)),
+			},
+		}),
+		classes: arena.alloc_slice_fill_with(classes.len(), |i| str(classes[i])),
+		styles: arena.alloc_slice_fill_with(styles.len(), |i| StyleDeclaration {
+			property: str(styles[i].property),
+			value: str(styles[i].value),
+			important: styles[i].important,
+		}),
+		content: element.content.copy_into(arena),
+		shadow_root: element.shadow_root.map(|shadow_root| {
+			arena.alloc(ShadowRoot {
+				options: shadow_root.options,
+				content: shadow_root.content.copy_into(arena),
+				dom_binding: shadow_root.dom_binding,
+			})
+		}),
+		event_bindings: arena.alloc_slice_fill_with(event_bindings.len(), |i| EventBinding {
+			name: str(event_bindings[i].name),
+			callback: event_bindings[i].callback,
+			options: event_bindings[i].options,
+		}),
+	}
+}
@@ -556,6 +556,42 @@
 //!
 //! > This is implemented directly on the individual [`Vdom`] type variants, so no additional trait imports are necessary to use it.
 //!
+//! # Containers and Custom Types
+//!
+//! [`Option`]s, references, arrays and slice references of [`Vdom`] types are [`Vdom`] types with the same [`ThreadSafety`] themselves,
+//! so components can return for example an optional [`Node`] with inferred [`ThreadSafety`]:
+//!
+//! ```rust
+//! # use lignin::{
+//! #   auto_safety::{Align as _, AutoSafe, Deanonymize as _},
+//! #   Node, ThreadBound, ThreadSafe,
+//! # };
+//! #
+//! # fn safe<'a>() -> Node::<'a, ThreadSafe> { Node::Multi(&[]) }
+//! # fn bound<'a>() -> Node::<'a, ThreadBound> { Node::Multi(&[]) }
+//! #
+//! # fn assert_safe<'a>(value: Option<Node<'a, ThreadSafe>>) { }
+//! # fn assert_bound<'a>(value: Option<Node<'a, ThreadBound>>) { }
+//! #
+//! fn maybe_safe<'a>(show: bool) -> impl AutoSafe<Option<Node::<'a, ThreadBound>>> {
+//!   show.then(safe)
+//! }
+//!
+//! fn maybe_bound<'a>(show: bool) -> impl AutoSafe<Option<Node::<'a, ThreadBound>>> {
+//!   show.then(bound)
+//! }
+//! #
+//! # assert_safe(maybe_safe(true).deanonymize());
+//! # assert_bound(maybe_bound(false).deanonymize());
+//! ```
+//!
+//! [`Deanonymize`] resolves only through one such layer, so for example `Option<&Node<…>>` can be [`Align`]ed but not deanonymized to its [`ThreadSafe`] variant.
+//!
+//! Custom types that contain VDOM can implement [`Vdom`] and [`Align`] (and with it [`AutoSafe`]) through `#[derive(Vdom)]` from the `lignin-derive` crate,
+//! after implementing [`VdomSeal`] on them manually.
+//! [`Align`] and [`Deanonymize`] are `unsafe` to implement by hand, as their methods reinterpret values between the implementing and target types.
+//! Rust's orphan rules prevent [`Deanonymize`] implementations for them, so their opaque instances always deanonymize towards the [`ThreadBound`] variant.
+//!
 //! # Limiting [`AutoSafe`] Exposure
 //!
 //! Thread-safety inference is powerful, but also dangerous: A change deep in a library could cause a public function return type to shift, breaking compatibility with downstream crates.
@@ -603,11 +639,23 @@
 use core::ptr::addr_of;
 
 use crate::{
-	callback_registry::CallbackSignature, Attribute, CallbackRef, Element, ElementCreationOptions,
-	EventBinding, EventBindingOptions, Node, ReorderableFragment, ThreadBound, ThreadSafe,
-	ThreadSafety, Vdom,
+	callback_registry::CallbackSignature, Attribute, AttributeValue, CallbackRef, Document,
+	Element, ElementCreationOptions, EventBinding, EventBindingOptions, Node, Portal, Property,
+	PropertyValue, ReorderableFragment, ShadowRoot, ShadowRootOptions, StyleDeclaration,
+	ThreadBound, ThreadSafe, ThreadSafety, TrustedHtml, Vdom,
 };
 
+/// Seals [`Vdom`], and with it [`AutoSafe`], against safe implementations.
+///
+/// This is implemented on all [`Vdom`] types in this crate.  
+/// Custom types must implement it manually, and can then implement [`Vdom`] and [`Align`] through `#[derive(Vdom)]` from the `lignin-derive` crate.
+///
+/// # Safety
+///
+/// Implementing this trait asserts that all variants of `Self` that differ only in [`ThreadSafety`] type arguments share their layout,
+/// which the [`Align`] implementations derived by `lignin-derive` rely on.
+pub unsafe trait VdomSeal {}
+
 /// Deanonymize towards the general ([`ThreadBound`]) case. Used as `-> impl AutoSafe<…>`.
 ///
 /// See module documentation for usage.
@@ -625,8 +673,8 @@ where
 	fn deanonymize(&self) -> BoundVariant {
 		unsafe {
 			// SAFETY:
-			// Under normal circumstances, this trait or method would have to be `unsafe`.
-			// However, we're ensuring only sound implementations exist by sealing it and carefully implementing it only across layout-compatible types.
+			// `Self: Align<BoundVariant>`, so this is guaranteed by `Align`'s implementation contract.
+			// `AutoSafe` itself can't be implemented elsewhere, as the blanket implementation below covers all candidates.
 			*(self as *const Self).cast()
 		}
 	}
@@ -635,7 +683,14 @@ where
 /// Deanonymize towards the special ([`ThreadSafe`]) case. **This trait must be in scope for correct inference!**
 ///
 /// See module documentation for usage.
-pub trait Deanonymize<SafeVariant>
+///
+/// # Safety
+///
+/// [`Deanonymize::deanonymize`] reinterprets values without checks.
+///
+/// Each implementation, regardless of which crate it's in, **must** be on a type `Self` that has the same layout as `SafeVariant`,
+/// and that differs from it only in [`ThreadSafety`] type arguments.
+pub unsafe trait Deanonymize<SafeVariant>
 where
 	Self: Vdom + Send + Sync,
 	SafeVariant: Vdom<ThreadSafety = ThreadSafe>,
@@ -648,9 +703,7 @@ where
 	#[inline(always)] // No-op.
 	fn deanonymize(self) -> SafeVariant {
 		unsafe {
-			// SAFETY:
-			// Under normal circumstances, this trait or method would have to be `unsafe`.
-			// However, we're ensuring only sound implementations exist by sealing it and carefully implementing it only across layout-compatible types.
+			// SAFETY: Guaranteed by the implementation contract of this `unsafe` trait.
 			*addr_of!(self).cast()
 		}
 	}
@@ -668,13 +721,22 @@ where
 /// This trait acts as [`Into`] on and between variants of the same [`Vdom`] type, but without raising `useless_conversion` warnings.
 ///
 /// See module documentation for when to use this trait and when it's unnecessary.
-pub trait Align<T: Vdom>: Vdom {
+///
+/// # Safety
+///
+/// [`Align::align`] and [`Align::align_ref`] reinterpret values and references without checks.
+///
+/// Each implementation, regardless of which crate it's in, **must** be on a type `Self` that has the same layout as `T`,
+/// and that differs from it only in [`ThreadSafety`] type arguments that are [`Into`]-convertible to `T`'s.
+///
+/// This also applies to implementations that target a custom type from a foreign one, like `Align<Custom<ThreadSafe>> for Node<ThreadBound>`.
+pub unsafe trait Align<T: Vdom>: Vdom {
 	/// Contextually thread-binds an instance, or not. Use only without qualification.
 	#[allow(clippy::inline_always)]
 	#[inline(always)] // No-op.
 	fn align(self) -> T {
 		unsafe {
-			// SAFETY: This trait is sealed and only implemented on and across compatible types.
+			// SAFETY: Guaranteed by the implementation contract of this `unsafe` trait.
 			*addr_of!(self).cast()
 		}
 	}
@@ -684,7 +746,7 @@ pub trait Align<T: Vdom>: Vdom {
 	#[inline(always)] // No-op.
 	fn align_ref(&self) -> &T {
 		unsafe {
-			// SAFETY: This trait is sealed and only implemented on and across compatible types.
+			// SAFETY: Guaranteed by the implementation contract of this `unsafe` trait.
 			&*(self as *const Self).cast()
 		}
 	}
@@ -776,6 +838,42 @@ impl<'a> Attribute<'a> {
 	}
 }
 
+impl<'a> Property<'a> {
+	deanonymize_on_named!();
+	prefer_thread_safe_safe! {
+		///
+		/// > Calling this method on [`Property`] produces a deprecation warning since the type is always [`ThreadSafe`].
+		by value:
+		#[deprecated = "Call of `.prefer_thread_safe()` on `Property`."]
+		by ref:
+		#[deprecated = "Call of `.prefer_thread_safe_ref()` on `Property`."]
+	}
+}
+
+impl<'a> StyleDeclaration<'a> {
+	deanonymize_on_named!();
+	prefer_thread_safe_safe! {
+		///
+		/// > Calling this method on [`StyleDeclaration`] produces a deprecation warning since the type is always [`ThreadSafe`].
+		by value:
+		#[deprecated = "Call of `.prefer_thread_safe()` on `StyleDeclaration`."]
+		by ref:
+		#[deprecated = "Call of `.prefer_thread_safe_ref()` on `StyleDeclaration`."]
+	}
+}
+
+impl<'a> TrustedHtml<'a> {
+	deanonymize_on_named!();
+	prefer_thread_safe_safe! {
+		///
+		/// > Calling this method on [`TrustedHtml`] produces a deprecation warning since the type is always [`ThreadSafe`].
+		by value:
+		#[deprecated = "Call of `.prefer_thread_safe()` on `TrustedHtml`."]
+		by ref:
+		#[deprecated = "Call of `.prefer_thread_safe_ref()` on `TrustedHtml`."]
+	}
+}
+
 impl<'a> ElementCreationOptions<'a> {
 	deanonymize_on_named!();
 	prefer_thread_safe_safe! {
@@ -800,6 +898,18 @@ impl EventBindingOptions {
 	}
 }
 
+impl ShadowRootOptions {
+	deanonymize_on_named!();
+	prefer_thread_safe_safe! {
+		///
+		/// > Calling this method on [`ShadowRootOptions`] produces a deprecation warning since the type is always [`ThreadSafe`].
+		by value:
+		#[deprecated = "Call of `.prefer_thread_safe()` on `ShadowRootOptions`."]
+		by ref:
+		#[deprecated = "Call of `.prefer_thread_safe_ref()` on `ShadowRootOptions`."]
+	}
+}
+
 macro_rules! impl_auto_safety {
 	($($Name:ident),*$(,)?) => {$(
 		impl<'a, S: ThreadSafety> $Name<'a, S> {
@@ -811,12 +921,14 @@ macro_rules! impl_auto_safety {
 		impl<'a> $Name<'a, ThreadBound> {
 			prefer_thread_safe_bound!();
 		}
-		impl<'a, V> Deanonymize<$Name<'a, ThreadSafe>> for V where
+		// SAFETY: `AutoSafe` implies `Align`, which guarantees layout compatibility.
+		unsafe impl<'a, V> Deanonymize<$Name<'a, ThreadSafe>> for V where
 			V: Send + Sync + AutoSafe<$Name<'a, ThreadBound>>,
 		{}
 
 		/// Not derived from the [`Into`] constraints on `$Name` directly since those are too broad.
-		impl<'a, S1, S2> Align<$Name<'a, S2>> for $Name<'a, S1>
+		// SAFETY: Only `ThreadSafety` changes, which doesn't affect layout (see `tests/layout.rs`).
+		unsafe impl<'a, S1, S2> Align<$Name<'a, S2>> for $Name<'a, S1>
 		where
 			S1: ThreadSafety + Into<S2>,
 			S2: ThreadSafety,
@@ -824,7 +936,15 @@ macro_rules! impl_auto_safety {
 	)*};
 }
 
-impl_auto_safety!(Element, EventBinding, Node, ReorderableFragment);
+impl_auto_safety!(
+	Document,
+	Element,
+	EventBinding,
+	Node,
+	Portal,
+	ReorderableFragment,
+	ShadowRoot
+);
 
 impl<S: ThreadSafety, C> CallbackRef<S, C>
 where
@@ -844,13 +964,15 @@ where
 {
 	prefer_thread_safe_bound!();
 }
-impl<C, O> Deanonymize<CallbackRef<ThreadSafe, C>> for O
+// SAFETY: `AutoSafe` implies `Align`, which guarantees layout compatibility.
+unsafe impl<C, O> Deanonymize<CallbackRef<ThreadSafe, C>> for O
 where
 	C: CallbackSignature,
 	O: Send + Sync + AutoSafe<CallbackRef<ThreadBound, C>>,
 {
 }
-impl<S1, S2, C> Align<CallbackRef<S2, C>> for CallbackRef<S1, C>
+// SAFETY: Only `ThreadSafety` changes, which doesn't affect layout (see `tests/layout.rs`).
+unsafe impl<S1, S2, C> Align<CallbackRef<S2, C>> for CallbackRef<S1, C>
 where
 	C: CallbackSignature,
 	S1: ThreadSafety + Into<S2>,
@@ -858,6 +980,70 @@ where
 {
 }
 
+// SAFETY: Only `ThreadSafety` type arguments vary, which doesn't affect layout (see `tests/layout.rs`).
+unsafe impl<'a> VdomSeal for Attribute<'a> {}
+unsafe impl<'a> VdomSeal for AttributeValue<'a> {}
+unsafe impl<'a> VdomSeal for Property<'a> {}
+unsafe impl<'a> VdomSeal for PropertyValue<'a> {}
+unsafe impl<'a> VdomSeal for StyleDeclaration<'a> {}
+unsafe impl<'a> VdomSeal for TrustedHtml<'a> {}
+unsafe impl<'a> VdomSeal for ElementCreationOptions<'a> {}
+unsafe impl VdomSeal for EventBindingOptions {}
+unsafe impl VdomSeal for ShadowRootOptions {}
+unsafe impl<S: ThreadSafety, C: CallbackSignature> VdomSeal for CallbackRef<S, C> {}
+unsafe impl<'a, S: ThreadSafety> VdomSeal for Document<'a, S> {}
+unsafe impl<'a, S: ThreadSafety> VdomSeal for Element<'a, S> {}
+unsafe impl<'a, S: ThreadSafety> VdomSeal for EventBinding<'a, S> {}
+unsafe impl<'a, S: ThreadSafety> VdomSeal for Node<'a, S> {}
+unsafe impl<'a, S: ThreadSafety> VdomSeal for Portal<'a, S> {}
+unsafe impl<'a, S: ThreadSafety> VdomSeal for ReorderableFragment<'a, S> {}
+unsafe impl<'a, S: ThreadSafety> VdomSeal for ShadowRoot<'a, S> {}
+
+// SAFETY:
+// These containers have the same layout whenever their items do.
+// This is also asserted for `Option<Node>` in `tests/layout.rs`, as its niche could in theory depend on `ThreadSafety`.
+unsafe impl<T: Vdom> VdomSeal for Option<T> {}
+unsafe impl<'r, T: Vdom> VdomSeal for &'r T {}
+unsafe impl<T: Vdom, const N: usize> VdomSeal for [T; N] {}
+unsafe impl<'r, T: Vdom> VdomSeal for &'r [T] {}
+
+// SAFETY: These containers have the same layout whenever their items do, see above.
+unsafe impl<T: Align<U>, U: Vdom> Align<Option<U>> for Option<T> {}
+unsafe impl<'r, T: Align<U>, U: Vdom> Align<&'r U> for &'r T {}
+unsafe impl<T: Align<U>, U: Vdom, const N: usize> Align<[U; N]> for [T; N] {}
+unsafe impl<'r, T: Align<U>, U: Vdom> Align<&'r [U]> for &'r [T] {}
+
+/// [`Deanonymize`] can't be implemented generically over containers without breaking inference,
+/// so this is done separately for each [`ThreadSafe`] variant that may be nested directly.
+macro_rules! deanonymize_containers {
+	($([$($generics:tt)*] $Safe:ty, $Bound:ty;)*) => {$(
+		// SAFETY: `AutoSafe` implies `Align`, which guarantees layout compatibility.
+		unsafe impl<$($generics)* V> Deanonymize<Option<$Safe>> for V where
+			V: Send + Sync + AutoSafe<Option<$Bound>>,
+		{}
+		unsafe impl<'r, $($generics)* V> Deanonymize<&'r $Safe> for V where
+			V: Send + Sync + AutoSafe<&'r $Bound>,
+		{}
+		unsafe impl<$($generics)* V, const N: usize> Deanonymize<[$Safe; N]> for V where
+			V: Send + Sync + AutoSafe<[$Bound; N]>,
+		{}
+		unsafe impl<'r, $($generics)* V> Deanonymize<&'r [$Safe]> for V where
+			V: Send + Sync + AutoSafe<&'r [$Bound]>,
+		{}
+	)*};
+}
+
+deanonymize_containers! {
+	['a,] Document<'a, ThreadSafe>, Document<'a, ThreadBound>;
+	['a,] Element<'a, ThreadSafe>, Element<'a, ThreadBound>;
+	['a,] EventBinding<'a, ThreadSafe>, EventBinding<'a, ThreadBound>;
+	['a,] Node<'a, ThreadSafe>, Node<'a, ThreadBound>;
+	['a,] Portal<'a, ThreadSafe>, Portal<'a, ThreadBound>;
+	['a,] ReorderableFragment<'a, ThreadSafe>, ReorderableFragment<'a, ThreadBound>;
+	['a,] ShadowRoot<'a, ThreadSafe>, ShadowRoot<'a, ThreadBound>;
+	[C: CallbackSignature,] CallbackRef<ThreadSafe, C>, CallbackRef<ThreadBound, C>;
+}
+
 /// Mainly for use by frameworks. Canonically located at `auto_safe::AutoSafe_alias`.  
 /// Creates a custom-visibility alias for [`auto_safety::AutoSafe`](`AutoSafe`).
 ///
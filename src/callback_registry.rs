@@ -4,15 +4,21 @@
 //! As such, you *probably* don't need to access this module, but if you do then it's available.
 #![allow(clippy::inline_always)] // Most functions here are either extremely simple or proxies to the inner module.
 
-use crate::{sealed::Sealed, web, DomRef, ThreadBound, ThreadSafe, ThreadSafety};
+use crate::{sealed::Sealed, web, DomRef, Element, Node, ThreadBound, ThreadSafe, ThreadSafety};
 use core::{
-	fmt::Debug,
+	fmt::{self, Debug, Display},
+	future::Future,
 	marker::{PhantomData, PhantomPinned},
 	mem,
 	num::NonZeroU32,
 	pin::Pin,
+	ptr::addr_of,
+	task::{Context, Poll},
 };
 
+#[cfg(feature = "callbacks")]
+extern crate std;
+
 /// Indicates whether the `"callbacks"` feature is enabled.
 pub const ENABLED: bool = cfg!(feature = "callback");
 
@@ -64,14 +70,22 @@ mod callbacks_on {
 
 	use crate::DomRef;
 
-	use super::{CallbackRegistration, CallbackSignature};
+	use super::{
+		CallbackError, CallbackErrorKind, CallbackRegistration, CallbackSignature, ErrorPolicy,
+		LocalFuture,
+	};
 	use core::{
+		any::type_name,
 		cell::Cell,
 		convert::TryInto,
+		fmt::{self, Debug},
+		future::Future,
 		marker::{PhantomData, PhantomPinned},
 		mem,
 		num::NonZeroU32,
 		pin::Pin,
+		sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+		task::{Context, Poll, Waker},
 	};
 	use lazy_static::lazy_static;
 	use mem::size_of_val;
@@ -79,48 +93,60 @@ mod callbacks_on {
 		boxed::Box,
 		collections::{HashMap, VecDeque},
 		panic::{catch_unwind, AssertUnwindSafe},
-		result::Result::{Err, Ok},
-		sync::RwLock,
+		result::Result::{self, Err, Ok},
+		sync::{Arc, Mutex, RwLock},
 	};
 
+	/// Erased `fn(LocalFuture)`, or `0` if not set.
+	static SPAWN_LOCAL: AtomicUsize = AtomicUsize::new(0);
+
 	lazy_static! {
-		static ref REGISTRY: RwLock<Registry> = RwLock::default();
+		static ref REGISTRY: RwLock<Registry> = RwLock::new(Registry {
+			key_count: 0,
+			entries: HashMap::new(),
+			error_policy: ErrorPolicy::Propagate,
+		});
+
+		/// Whether each live registration is recorded as [`Sync`], shared with [`CallbackRegistration::thread_safe_recorded`].
+		///
+		/// This is separate from [`REGISTRY`] so that it can be read while a handler holds that lock.
+		/// The flags are only ever set through the registrations, which keeps [`CallbackRegistration::to_ref`] lock-free.
+		static ref THREAD_SAFE_FLAGS: Mutex<HashMap<NonZeroU32, Arc<AtomicBool>>> = Mutex::new(HashMap::new());
+
+		/// Cancellation state of live async registrations, see [`CallbackRegistration::new_async`].
+		///
+		/// This is separate from [`REGISTRY`] so that it can be accessed while a handler holds that lock.
+		static ref ASYNC_STATES: Mutex<HashMap<NonZeroU32, Arc<AsyncState>>> = Mutex::new(HashMap::new());
 	}
 
-	#[derive(Default)]
 	struct Registry {
 		key_count: u32,
 		entries: HashMap<NonZeroU32, Entry>,
+		error_policy: ErrorPolicy,
 	}
 
 	struct Entry {
 		receiver_address: usize,
 		invoke_typed_address: usize,
 		handler_address: usize,
+		error_policy: Option<ErrorPolicy>,
 	}
 
-	#[must_use]
-	pub fn register<R, T>(
+	type HandlerResult<O = ()> = Result<O, Box<dyn Debug>>;
+
+	fn insert<R, C>(
 		receiver: Pin<&'_ R>,
-		handler: fn(*const R, T),
-	) -> CallbackRegistration<R, fn(T)>
+		invoke_typed_address: usize,
+		handler_address: usize,
+	) -> CallbackRegistration<R, C>
 	where
-		fn(T): CallbackSignature,
+		C: CallbackSignature,
 	{
 		let mut registry = REGISTRY.write().unwrap();
 		if registry.key_count == u32::MAX {
 			drop(registry);
 			panic!("[lignin] Callback registry keys exhausted")
 		} else {
-			fn invoke_typed<R, T>(receiver_address: usize, handler_address: usize, parameter: T) {
-				let receiver = receiver_address as *const R;
-				let handler = unsafe {
-					// SAFETY: The pointer to invoke_typed is taken with matching monomorphization just below.
-					mem::transmute::<usize, fn(*const R, T)>(handler_address)
-				};
-				handler(receiver, parameter)
-			}
-
 			registry.key_count += 1;
 			let key = NonZeroU32::new(registry.key_count).unwrap();
 			assert!(registry
@@ -129,19 +155,218 @@ mod callbacks_on {
 					key,
 					Entry {
 						receiver_address: receiver.get_ref() as *const R as usize,
-						invoke_typed_address: invoke_typed::<R, T> as usize,
-						handler_address: handler as usize,
+						invoke_typed_address,
+						handler_address,
+						error_policy: None,
 					},
 				)
 				.is_none());
+			let thread_safe_recorded = Arc::new(AtomicBool::new(false));
+			assert!(THREAD_SAFE_FLAGS
+				.lock()
+				.unwrap()
+				.insert(key, Arc::clone(&thread_safe_recorded))
+				.is_none());
 			CallbackRegistration {
 				key,
+				thread_safe_recorded,
 				phantom: PhantomData,
 				_pinned: PhantomPinned,
 			}
 		}
 	}
 
+	#[must_use]
+	pub fn register<R, T, O>(
+		receiver: Pin<&'_ R>,
+		handler: fn(*const R, T) -> O,
+	) -> CallbackRegistration<R, fn(T) -> O>
+	where
+		fn(T) -> O: CallbackSignature,
+	{
+		#[allow(clippy::unnecessary_wraps)] // Type-erased signature.
+		fn invoke_typed<R, T, O>(
+			_key: NonZeroU32,
+			receiver_address: usize,
+			handler_address: usize,
+			parameter: T,
+		) -> HandlerResult<O> {
+			let receiver = receiver_address as *const R;
+			let handler = unsafe {
+				// SAFETY: The pointer to invoke_typed is taken with matching monomorphization just below.
+				mem::transmute::<usize, fn(*const R, T) -> O>(handler_address)
+			};
+			Ok(handler(receiver, parameter))
+		}
+
+		insert(
+			receiver,
+			invoke_typed::<R, T, O> as fn(NonZeroU32, usize, usize, T) -> HandlerResult<O> as usize,
+			handler as usize,
+		)
+	}
+
+	#[must_use]
+	pub fn register_fallible<R, T, O, E>(
+		receiver: Pin<&'_ R>,
+		handler: fn(*const R, T) -> Result<O, E>,
+	) -> CallbackRegistration<R, fn(T) -> O>
+	where
+		fn(T) -> O: CallbackSignature,
+		E: 'static + Debug,
+	{
+		fn invoke_typed<R, T, O, E: 'static + Debug>(
+			_key: NonZeroU32,
+			receiver_address: usize,
+			handler_address: usize,
+			parameter: T,
+		) -> HandlerResult<O> {
+			let receiver = receiver_address as *const R;
+			let handler = unsafe {
+				// SAFETY: The pointer to invoke_typed is taken with matching monomorphization just below.
+				mem::transmute::<usize, fn(*const R, T) -> Result<O, E>>(handler_address)
+			};
+			handler(receiver, parameter).map_err(|error| Box::new(error) as Box<dyn Debug>)
+		}
+
+		insert(
+			receiver,
+			invoke_typed::<R, T, O, E> as fn(NonZeroU32, usize, usize, T) -> HandlerResult<O>
+				as usize,
+			handler as usize,
+		)
+	}
+
+	#[must_use]
+	pub fn register_async<R, T, F>(
+		receiver: Pin<&'_ R>,
+		handler: fn(*const R, T) -> F,
+	) -> CallbackRegistration<R, fn(T)>
+	where
+		fn(T): CallbackSignature,
+		F: 'static + Future<Output = ()>,
+	{
+		#[allow(clippy::unnecessary_wraps)] // Type-erased signature.
+		fn invoke_typed<R, T, F: 'static + Future<Output = ()>>(
+			key: NonZeroU32,
+			receiver_address: usize,
+			handler_address: usize,
+			parameter: T,
+		) -> HandlerResult {
+			let spawn_local = match SPAWN_LOCAL.load(Ordering::Acquire) {
+				0 => return Err(Box::new("No `spawn_local` hook set. Use `callback_registry::set_spawn_local` before invoking async handlers.")),
+				spawn_local => unsafe {
+					// SAFETY: Only ever stored from a matching function pointer in `set_spawn_local`.
+					mem::transmute::<usize, fn(LocalFuture)>(spawn_local)
+				},
+			};
+			let state = match ASYNC_STATES.lock().unwrap().get(&key) {
+				Some(state) => state.clone(),
+				None => return Ok(()), // Cancelled concurrently.
+			};
+
+			let receiver = receiver_address as *const R;
+			let handler = unsafe {
+				// SAFETY: The pointer to invoke_typed is taken with matching monomorphization just below.
+				mem::transmute::<usize, fn(*const R, T) -> F>(handler_address)
+			};
+			let future = Cancellable {
+				id: state.next_id.fetch_add(1, Ordering::Relaxed),
+				state,
+				future: Some(Box::pin(handler(receiver, parameter))),
+			};
+
+			// Spawning is deferred so that the executor never polls while the registry is locked.
+			when_unlocked_locally(move || spawn_local(LocalFuture(Box::pin(future))));
+			Ok(())
+		}
+
+		let registration = insert(
+			receiver,
+			invoke_typed::<R, T, F> as fn(NonZeroU32, usize, usize, T) -> HandlerResult as usize,
+			handler as usize,
+		);
+		ASYNC_STATES.lock().unwrap().insert(
+			registration.key,
+			Arc::new(AsyncState {
+				live: RwLock::new(true),
+				next_id: AtomicUsize::new(0),
+				wakers: Mutex::new(HashMap::new()),
+			}),
+		);
+		registration
+	}
+
+	/// Shared between an async [`CallbackRegistration`] and the [`Future`]s spawned from it.
+	struct AsyncState {
+		/// Read-locked while polling and write-locked to cancel, so that `receiver` stays valid during each poll.
+		live: RwLock<bool>,
+		next_id: AtomicUsize,
+		/// Wakers of pending [`Cancellable`]s by id, so that cancelled ones are dropped promptly.
+		wakers: Mutex<HashMap<usize, Waker>>,
+	}
+
+	/// Cancels all [`Cancellable`]s spawned for `key`, blocking while any of them is being polled.
+	fn cancel_async(key: NonZeroU32) {
+		let state = ASYNC_STATES.lock().unwrap().remove(&key);
+		if let Some(state) = state {
+			*state.live.write().unwrap() = false;
+			let wakers = mem::take(&mut *state.wakers.lock().unwrap());
+			for waker in wakers.into_values() {
+				waker.wake()
+			}
+		}
+	}
+
+	/// Wraps an async handler's [`Future`], dropping it instead of polling it once the [`CallbackRegistration`] is gone.
+	struct Cancellable<F> {
+		id: usize,
+		state: Arc<AsyncState>,
+		future: Option<Pin<Box<F>>>,
+	}
+
+	impl<F: Future<Output = ()>> Future for Cancellable<F> {
+		type Output = ();
+
+		fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+			if is_locked_locally() {
+				let waker = cx.waker().clone();
+				when_unlocked_locally(move || waker.wake());
+				return Poll::Pending;
+			}
+
+			let this = &mut *self;
+			let poll = match &mut this.future {
+				Some(future) => {
+					// Dropping the `CallbackRegistration` blocks until this guard is released.
+					let live = this.state.live.read().unwrap();
+					if *live {
+						this.state
+							.wakers
+							.lock()
+							.unwrap()
+							.insert(this.id, cx.waker().clone());
+						future.as_mut().poll(cx)
+					} else {
+						Poll::Ready(())
+					}
+				}
+				None => Poll::Ready(()),
+			};
+			if poll.is_ready() {
+				this.future = None;
+				this.state.wakers.lock().unwrap().remove(&this.id);
+			}
+			poll
+		}
+	}
+
+	impl<F> Drop for Cancellable<F> {
+		fn drop(&mut self) {
+			self.state.wakers.lock().unwrap().remove(&self.id);
+		}
+	}
+
 	#[must_use]
 	pub fn register_by_ref<R, T>(
 		receiver: Pin<&'_ R>,
@@ -150,43 +375,61 @@ mod callbacks_on {
 	where
 		fn(DomRef<&'_ T>): CallbackSignature,
 	{
-		let mut registry = REGISTRY.write().unwrap();
-		if registry.key_count == u32::MAX {
-			drop(registry);
-			panic!("[lignin] Callback registry keys exhausted")
-		} else {
-			fn invoke_typed<R, T>(
-				receiver_address: usize,
-				handler_address: usize,
-				parameter: DomRef<&'_ T>,
-			) {
-				let receiver = receiver_address as *const R;
-				let handler = unsafe {
-					// SAFETY: The pointer to invoke_typed is taken with matching monomorphization just below.
-					mem::transmute::<usize, fn(*const R, DomRef<&'_ T>)>(handler_address)
-				};
-				handler(receiver, parameter)
-			}
+		#[allow(clippy::unnecessary_wraps)] // Type-erased signature.
+		fn invoke_typed<R, T>(
+			_key: NonZeroU32,
+			receiver_address: usize,
+			handler_address: usize,
+			parameter: DomRef<&'_ T>,
+		) -> HandlerResult {
+			let receiver = receiver_address as *const R;
+			let handler = unsafe {
+				// SAFETY: The pointer to invoke_typed is taken with matching monomorphization just below.
+				mem::transmute::<usize, fn(*const R, DomRef<&'_ T>)>(handler_address)
+			};
+			handler(receiver, parameter);
+			Ok(())
+		}
 
-			registry.key_count += 1;
-			let key = NonZeroU32::new(registry.key_count).unwrap();
-			assert!(registry
-				.entries
-				.insert(
-					key,
-					Entry {
-						receiver_address: receiver.get_ref() as *const R as usize,
-						invoke_typed_address: invoke_typed::<R, T> as usize,
-						handler_address: handler as usize,
-					},
+		insert(
+			receiver,
+			invoke_typed::<R, T> as fn(NonZeroU32, usize, usize, DomRef<&'_ T>) -> HandlerResult
+				as usize,
+			handler as usize,
+		)
+	}
+
+	#[must_use]
+	pub fn register_by_ref_fallible<R, T, E>(
+		receiver: Pin<&'_ R>,
+		handler: fn(*const R, DomRef<&'_ T>) -> Result<(), E>,
+	) -> CallbackRegistration<R, fn(DomRef<&'_ T>)>
+	where
+		fn(DomRef<&'_ T>): CallbackSignature,
+		E: 'static + Debug,
+	{
+		fn invoke_typed<R, T, E: 'static + Debug>(
+			_key: NonZeroU32,
+			receiver_address: usize,
+			handler_address: usize,
+			parameter: DomRef<&'_ T>,
+		) -> HandlerResult {
+			let receiver = receiver_address as *const R;
+			let handler = unsafe {
+				// SAFETY: The pointer to invoke_typed is taken with matching monomorphization just below.
+				mem::transmute::<usize, fn(*const R, DomRef<&'_ T>) -> Result<(), E>>(
+					handler_address,
 				)
-				.is_none());
-			CallbackRegistration {
-				key,
-				phantom: PhantomData,
-				_pinned: PhantomPinned,
-			}
+			};
+			handler(receiver, parameter).map_err(|error| Box::new(error) as Box<dyn Debug>)
 		}
+
+		insert(
+			receiver,
+			invoke_typed::<R, T, E> as fn(NonZeroU32, usize, usize, DomRef<&'_ T>) -> HandlerResult
+				as usize,
+			handler as usize,
+		)
 	}
 
 	pub fn deregister<R, C>(registration: &CallbackRegistration<R, C>)
@@ -199,38 +442,77 @@ mod callbacks_on {
 			.entries
 			.remove(&registration.key)
 			.expect("`CallbackRegistration` double-drop");
+		cancel_async(registration.key);
+		THREAD_SAFE_FLAGS.lock().unwrap().remove(&registration.key);
 	}
 
-	pub fn invoke<T>(key: NonZeroU32, parameter: T)
+	pub fn record_thread_safe<R, C>(registration: &CallbackRegistration<R, C>)
 	where
-		fn(T): CallbackSignature,
+		R: Sync,
+		C: CallbackSignature,
+	{
+		registration
+			.thread_safe_recorded
+			.store(true, Ordering::Release);
+	}
+
+	#[must_use]
+	pub fn is_thread_safe(key: NonZeroU32) -> bool {
+		THREAD_SAFE_FLAGS
+			.lock()
+			.unwrap()
+			.get(&key)
+			.map_or(false, |recorded| recorded.load(Ordering::Acquire))
+	}
+
+	pub fn set_registration_error_policy<R, C>(
+		registration: &CallbackRegistration<R, C>,
+		error_policy: Option<ErrorPolicy>,
+	) where
+		C: CallbackSignature,
+	{
+		REGISTRY
+			.write()
+			.unwrap()
+			.entries
+			.get_mut(&registration.key)
+			.expect("`CallbackRegistration` already dropped")
+			.error_policy = error_policy;
+	}
+
+	pub fn invoke<T, O: Default>(key: NonZeroU32, parameter: T) -> O
+	where
+		fn(T) -> O: CallbackSignature,
 	{
 		CONTINUATION_QUEUE.with(|continuation_queue| {
 			let none = continuation_queue.replace(Some(VecDeque::new()));
 			debug_assert!(none.is_none());
 
-			// UNWIND SAFETY: The only part we examine is the continuation queue,
-			// and we don't run consumer code while holding a reference to it.
-			match catch_unwind(AssertUnwindSafe(|| {
-				let registry = REGISTRY.read().unwrap();
-				if let Some(entry) = registry.entries.get(&key) {
-					let invoke_typed = unsafe {
-						// SAFETY: Same type as above.
-						mem::transmute::<usize, fn(usize, usize, T)>(entry.invoke_typed_address)
-					};
-					invoke_typed(entry.receiver_address, entry.handler_address, parameter)
+			let (error_policy, result) = invoke_guarded::<T, O>(key, parameter);
+			match result {
+				Ok(output) => {
+					for continuation in continuation_queue.take().unwrap() {
+						continuation()
+					}
+					return output;
 				}
-			})) {
-				Ok(()) => {
+				Err(
+					error @ CallbackError {
+						kind: CallbackErrorKind::Returned(_),
+						..
+					},
+				) => {
 					for continuation in continuation_queue.take().unwrap() {
 						continuation()
 					}
+					handle(error_policy, error)
 				}
-				Err(panic) => {
+				Err(error) => {
 					continuation_queue.take(); // Drop continuations.
-					std::panic::resume_unwind(panic)
+					handle(error_policy, error)
 				}
 			}
+			O::default()
 		})
 	}
 
@@ -238,16 +520,103 @@ mod callbacks_on {
 	where
 		fn(DomRef<&'_ T>): CallbackSignature,
 	{
-		let registry = REGISTRY.read().unwrap();
-		if let Some(entry) = registry.entries.get(&key) {
-			let invoke_typed = unsafe {
-				// SAFETY: Pretty much same type as above, just specified.
-				mem::transmute::<usize, fn(usize, usize, DomRef<&'_ T>)>(entry.invoke_typed_address)
-			};
-			invoke_typed(entry.receiver_address, entry.handler_address, parameter)
+		let (error_policy, result) = invoke_guarded::<DomRef<&T>, ()>(key, parameter);
+		if let Err(error) = result {
+			handle(error_policy, error)
+		}
+	}
+
+	/// Runs the handler registered for `key` (if any) while holding the registry's read lock, catching any panic.
+	///
+	/// The returned [`ErrorPolicy`] is the one that applies to that registration.
+	/// Errors are returned rather than handled here so that the lock is released first.
+	///
+	/// If no handler is registered for `key` (anymore), the output is `O::default()`.
+	fn invoke_guarded<T, O: Default>(
+		key: NonZeroU32,
+		parameter: T,
+	) -> (ErrorPolicy, Result<O, CallbackError>) {
+		let mut error_policy = ErrorPolicy::Propagate;
+
+		// UNWIND SAFETY: The only part we examine afterwards is `error_policy`, which is `Copy`,
+		// and callers don't run consumer code while holding a reference to the continuation queue.
+		let result = catch_unwind(AssertUnwindSafe(|| {
+			let registry = REGISTRY.read().unwrap();
+			if let Some(entry) = registry.entries.get(&key) {
+				error_policy = entry.error_policy.unwrap_or(registry.error_policy);
+				let invoke_typed = unsafe {
+					// SAFETY: Same type as in `register…` above.
+					mem::transmute::<usize, fn(NonZeroU32, usize, usize, T) -> HandlerResult<O>>(
+						entry.invoke_typed_address,
+					)
+				};
+				invoke_typed(
+					key,
+					entry.receiver_address,
+					entry.handler_address,
+					parameter,
+				)
+			} else {
+				Ok(O::default())
+			}
+		}));
+
+		let kind = match result {
+			Ok(Ok(output)) => return (error_policy, Ok(output)),
+			Ok(Err(error)) => CallbackErrorKind::Returned(error),
+			Err(panic) => CallbackErrorKind::Panicked(panic),
+		};
+		(
+			error_policy,
+			Err(CallbackError {
+				key,
+				signature: type_name::<fn(T) -> O>(),
+				kind,
+			}),
+		)
+	}
+
+	fn handle(error_policy: ErrorPolicy, error: CallbackError) {
+		match error_policy {
+			ErrorPolicy::Propagate => match error.kind {
+				CallbackErrorKind::Panicked(panic) => std::panic::resume_unwind(panic),
+				CallbackErrorKind::Returned(returned) => panic!(
+					"[lignin] Callback handler {} ({}) returned an error: {:?}",
+					error.key, error.signature, returned
+				),
+			},
+			ErrorPolicy::LogAndSwallow => log_error(&error),
+			ErrorPolicy::Route(handler) => handler(error),
 		}
 	}
 
+	fn log_error(error: &CallbackError) {
+		#[cfg(target_arch = "wasm32")]
+		web_sys::console::error_1(&std::format!("{}", error).into());
+		#[cfg(not(target_arch = "wasm32"))]
+		std::eprintln!("{}", error);
+	}
+
+	pub fn log_warning(warning: fmt::Arguments) {
+		#[cfg(target_arch = "wasm32")]
+		web_sys::console::warn_1(&std::format!("{}", warning).into());
+		#[cfg(not(target_arch = "wasm32"))]
+		std::eprintln!("{}", warning);
+	}
+
+	#[must_use]
+	pub fn error_policy() -> ErrorPolicy {
+		REGISTRY.read().unwrap().error_policy
+	}
+
+	pub fn set_spawn_local(spawn_local: fn(LocalFuture)) {
+		SPAWN_LOCAL.store(spawn_local as usize, Ordering::Release)
+	}
+
+	pub fn set_error_policy(error_policy: ErrorPolicy) {
+		REGISTRY.write().unwrap().error_policy = error_policy;
+	}
+
 	#[must_use]
 	pub fn registry_exhaustion() -> u8 {
 		let registry = REGISTRY.read().unwrap();
@@ -273,6 +642,12 @@ mod callbacks_on {
 		let mut registry = REGISTRY.write().unwrap();
 		registry.entries.clear();
 		registry.key_count = 0;
+		drop(registry);
+		THREAD_SAFE_FLAGS.lock().unwrap().clear();
+		let keys: std::vec::Vec<_> = ASYNC_STATES.lock().unwrap().keys().copied().collect();
+		for key in keys {
+			cancel_async(key)
+		}
 	}
 
 	pub fn when_unlocked_locally<F: 'static + FnOnce()>(continuation: F) {
@@ -287,6 +662,16 @@ mod callbacks_on {
 		})
 	}
 
+	fn is_locked_locally() -> bool {
+		CONTINUATION_QUEUE.with(|continuation_queue| {
+			unsafe {
+				// SAFETY: All access is thread-local and not recursive.
+				&*continuation_queue.as_ptr()
+			}
+			.is_some()
+		})
+	}
+
 	std::thread_local! {
 		#[allow(clippy::type_complexity)]
 		static CONTINUATION_QUEUE: Cell<Option<VecDeque<Box<dyn FnOnce()>>>> = None.into();
@@ -299,6 +684,7 @@ mod callbacks_on {
 #[allow(clippy::needless_pass_by_value)]
 mod callbacks_off {
 	use core::{
+		fmt,
 		marker::{PhantomData, PhantomPinned},
 		num::NonZeroU32,
 		pin::Pin,
@@ -306,13 +692,53 @@ mod callbacks_off {
 
 	use crate::DomRef;
 
-	use super::{CallbackRegistration, CallbackSignature};
+	use super::{
+		CallbackRegistration, CallbackSignature, ErrorPolicy, LocalFuture, ThreadSafeRecord,
+	};
+
+	#[inline(always)]
+	#[must_use]
+	pub fn register<R, T, O>(
+		receiver: Pin<&'_ R>,
+		handler: fn(*const R, T) -> O,
+	) -> CallbackRegistration<R, fn(T) -> O>
+	where
+		fn(T) -> O: CallbackSignature,
+	{
+		let _ = receiver;
+		let _ = handler;
+		CallbackRegistration {
+			key: NonZeroU32::new(u32::MAX).unwrap(),
+			thread_safe_recorded: ThreadSafeRecord::default(),
+			phantom: PhantomData,
+			_pinned: PhantomPinned,
+		}
+	}
+
+	#[inline(always)]
+	#[must_use]
+	pub fn register_fallible<R, T, O, E>(
+		receiver: Pin<&'_ R>,
+		handler: fn(*const R, T) -> Result<O, E>,
+	) -> CallbackRegistration<R, fn(T) -> O>
+	where
+		fn(T) -> O: CallbackSignature,
+	{
+		let _ = receiver;
+		let _ = handler;
+		CallbackRegistration {
+			key: NonZeroU32::new(u32::MAX).unwrap(),
+			thread_safe_recorded: ThreadSafeRecord::default(),
+			phantom: PhantomData,
+			_pinned: PhantomPinned,
+		}
+	}
 
 	#[inline(always)]
 	#[must_use]
-	pub fn register<R, T>(
+	pub fn register_async<R, T, F>(
 		receiver: Pin<&'_ R>,
-		handler: fn(*const R, T),
+		handler: fn(*const R, T) -> F,
 	) -> CallbackRegistration<R, fn(T)>
 	where
 		fn(T): CallbackSignature,
@@ -321,6 +747,7 @@ mod callbacks_off {
 		let _ = handler;
 		CallbackRegistration {
 			key: NonZeroU32::new(u32::MAX).unwrap(),
+			thread_safe_recorded: ThreadSafeRecord::default(),
 			phantom: PhantomData,
 			_pinned: PhantomPinned,
 		}
@@ -339,6 +766,26 @@ mod callbacks_off {
 		let _ = handler;
 		CallbackRegistration {
 			key: NonZeroU32::new(u32::MAX).unwrap(),
+			thread_safe_recorded: ThreadSafeRecord::default(),
+			phantom: PhantomData,
+			_pinned: PhantomPinned,
+		}
+	}
+
+	#[inline(always)]
+	#[must_use]
+	pub fn register_by_ref_fallible<R, T, E>(
+		receiver: Pin<&'_ R>,
+		handler: fn(*const R, DomRef<&'_ T>) -> Result<(), E>,
+	) -> CallbackRegistration<R, fn(DomRef<&'_ T>)>
+	where
+		fn(DomRef<&'_ T>): CallbackSignature,
+	{
+		let _ = receiver;
+		let _ = handler;
+		CallbackRegistration {
+			key: NonZeroU32::new(u32::MAX).unwrap(),
+			thread_safe_recorded: ThreadSafeRecord::default(),
 			phantom: PhantomData,
 			_pinned: PhantomPinned,
 		}
@@ -353,9 +800,37 @@ mod callbacks_off {
 	}
 
 	#[inline(always)]
-	pub fn invoke<T>(key: NonZeroU32, parameter: T) {
+	pub fn set_registration_error_policy<R, C>(
+		registration: &CallbackRegistration<R, C>,
+		error_policy: Option<ErrorPolicy>,
+	) where
+		C: CallbackSignature,
+	{
+		let _ = registration;
+		let _ = error_policy;
+	}
+
+	#[inline(always)]
+	pub fn record_thread_safe<R, C>(registration: &CallbackRegistration<R, C>)
+	where
+		R: Sync,
+		C: CallbackSignature,
+	{
+		let _ = registration;
+	}
+
+	#[inline(always)]
+	#[must_use]
+	pub const fn is_thread_safe(key: NonZeroU32) -> bool {
+		let _ = key;
+		true
+	}
+
+	#[inline(always)]
+	pub fn invoke<T, O: Default>(key: NonZeroU32, parameter: T) -> O {
 		let _ = key;
 		let _ = parameter;
+		O::default()
 	}
 
 	#[inline(always)]
@@ -364,6 +839,27 @@ mod callbacks_off {
 		let _ = parameter;
 	}
 
+	#[inline(always)]
+	pub fn log_warning(warning: fmt::Arguments) {
+		let _ = warning;
+	}
+
+	#[inline(always)]
+	pub fn set_spawn_local(spawn_local: fn(LocalFuture)) {
+		let _ = spawn_local;
+	}
+
+	#[inline(always)]
+	#[must_use]
+	pub const fn error_policy() -> ErrorPolicy {
+		ErrorPolicy::Propagate
+	}
+
+	#[inline(always)]
+	pub fn set_error_policy(error_policy: ErrorPolicy) {
+		let _ = error_policy;
+	}
+
 	#[inline(always)]
 	#[must_use]
 	pub const fn registry_exhaustion() -> u8 {
@@ -391,6 +887,12 @@ use callbacks_on as callbacks;
 #[cfg(not(feature = "callbacks"))]
 use callbacks_off as callbacks;
 
+/// The flag behind [`CallbackRegistration::to_ref`]'s lock-free [`Sync`] record, shared with the callback registry.
+#[cfg(feature = "callbacks")]
+type ThreadSafeRecord = std::sync::Arc<core::sync::atomic::AtomicBool>;
+#[cfg(not(feature = "callbacks"))]
+type ThreadSafeRecord = ();
+
 /// A callback registration handle that should be held onto by the matching receiver `R` or a container with [pin-projection](https://doc.rust-lang.org/stable/core/pin/index.html#pinning-is-structural-for-field) towards that value.
 ///
 /// [`CallbackRegistration`] is [`!Unpin`](`Unpin`) for convenience: A receiver correctly becomes [`!Unpin`](`Unpin`) if it contains for example a `Cell<Option<CallbackRegistration<R, T>>`¹⁻², which can be conveniently initialized in a rendering function called with [`Pin<&…>`](`Pin`) argument.
@@ -432,6 +934,9 @@ where
 	C: CallbackSignature,
 {
 	key: NonZeroU32,
+	/// Whether this registration is recorded as [`Sync`], see [`CallbackRef::try_into_thread_safe`].
+	#[cfg_attr(not(feature = "callbacks"), allow(dead_code))]
+	thread_safe_recorded: ThreadSafeRecord,
 	///FIXME: Can this be written with `&R` (removing the manual `Send` and `Sync` impls below)?
 	phantom: PhantomData<(*const R, C)>,
 	_pinned: PhantomPinned,
@@ -453,8 +958,8 @@ where
 {
 }
 
-/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and expect future broadening.
-impl<R> CallbackRegistration<R, fn(event: web::Event)> {
+/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and [`CallbackParameter`].
+impl<R, P: CallbackParameter, O: CallbackReturn> CallbackRegistration<R, fn(parameter: P) -> O> {
 	/// Creates a new [`CallbackRegistration<R, T>`] with the given `receiver` and `handler`.
 	///
 	/// # Deadlocks / Panics
@@ -476,11 +981,51 @@ impl<R> CallbackRegistration<R, fn(event: web::Event)> {
 	/// Dropping the [`CallbackRegistration`] instance prevents any further calls to `handler` derived from it from running, blocking until this can be guaranteed.
 	#[inline(always)] // Proxy function.
 	#[must_use]
-	pub fn new(receiver: Pin<&'_ R>, handler: fn(receiver: *const R, event: web::Event)) -> Self {
+	pub fn new(receiver: Pin<&'_ R>, handler: fn(receiver: *const R, parameter: P) -> O) -> Self {
 		callbacks::register(receiver, handler)
 	}
+
+	/// Creates a new [`CallbackRegistration<R, T>`] with the given `receiver` and fallible `handler`.
+	///
+	/// Any [`Err`] returned by `handler` is processed according to the applicable [`ErrorPolicy`], after pending [`when_unlocked_locally`] continuations have run.
+	///
+	/// See [`CallbackRegistration::new`] for more information, including **safety notes**.
+	#[inline(always)] // Proxy function.
+	#[must_use]
+	pub fn new_fallible<E: 'static + Debug>(
+		receiver: Pin<&'_ R>,
+		handler: fn(receiver: *const R, parameter: P) -> Result<O, E>,
+	) -> Self {
+		callbacks::register_fallible(receiver, handler)
+	}
+}
+impl<R, P: CallbackParameter> CallbackRegistration<R, fn(parameter: P)> {
+	/// Creates a new [`CallbackRegistration<R, T>`] with the given `receiver` and async `handler`.
+	///
+	/// Each returned [`Future`] is handed to the hook set via [`set_spawn_local`] once the registry is unlocked (see [`when_unlocked_locally`]).  
+	/// If no such hook is set, `handler` isn't called and an error is processed according to the applicable [`ErrorPolicy`] instead.
+	///
+	/// Dropping this [`CallbackRegistration`] wakes all pending [`Future`]s spawned from it, which are then dropped without being polled further.
+	/// Polls that would happen while the current thread holds the callback registry lock are deferred, so that its continuations never run inside another handler.
+	///
+	/// Panics raised while polling the [`Future`] are **not** subject to any [`ErrorPolicy`], but propagate into the executor instead.
+	///
+	/// See [`CallbackRegistration::new`] for more information, including **safety notes**.
+	///
+	/// # Deadlocks (continued)
+	///
+	/// Dropping this [`CallbackRegistration`] blocks while any of its [`Future`]s is being polled, so that `receiver` remains valid for the duration of each poll.  
+	/// Dropping it from within one of those [`Future`]s **will** deadlock. Use [`when_unlocked_locally`] or a separate task to defer this.
+	#[inline(always)] // Proxy function.
+	#[must_use]
+	pub fn new_async<F: 'static + Future<Output = ()>>(
+		receiver: Pin<&'_ R>,
+		handler: fn(receiver: *const R, parameter: P) -> F,
+	) -> Self {
+		callbacks::register_async(receiver, handler)
+	}
 }
-/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and expect future broadening.
+/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and [`CallbackParameter`].
 impl<R, T> CallbackRegistration<R, fn(dom_ref: DomRef<&'_ T>)> {
 	/// Creates a new [`CallbackRegistration<R, T>`] with the given `receiver` and `handler`.
 	///
@@ -509,6 +1054,20 @@ impl<R, T> CallbackRegistration<R, fn(dom_ref: DomRef<&'_ T>)> {
 	) -> Self {
 		callbacks::register_by_ref(receiver, handler)
 	}
+
+	/// Creates a new [`CallbackRegistration<R, T>`] with the given `receiver` and fallible `handler`.
+	///
+	/// Any [`Err`] returned by `handler` is processed according to the applicable [`ErrorPolicy`].
+	///
+	/// See [`CallbackRegistration::new`] for more information, including **safety notes**.
+	#[inline(always)] // Proxy function.
+	#[must_use]
+	pub fn new_fallible<E: 'static + Debug>(
+		receiver: Pin<&'_ R>,
+		handler: fn(receiver: *const R, dom_ref: DomRef<&'_ T>) -> Result<(), E>,
+	) -> Self {
+		callbacks::register_by_ref_fallible(receiver, handler)
+	}
 }
 #[allow(clippy::inline_always)] // All functions are very simple.
 impl<R, C> CallbackRegistration<R, C>
@@ -524,6 +1083,29 @@ where
 			phantom: PhantomData,
 		}
 	}
+
+	/// Overrides the registry-wide [`ErrorPolicy`] (see [`set_error_policy`]) for this [`CallbackRegistration`] only.
+	///
+	/// # Deadlocks / Panics
+	///
+	/// Like creating or dropping a [`CallbackRegistration`], calling this method from within any handler **may** deadlock or panic.
+	#[inline(always)] // Proxy function.
+	#[must_use]
+	pub fn with_error_policy(self, error_policy: ErrorPolicy) -> Self {
+		self.set_error_policy(Some(error_policy));
+		self
+	}
+
+	/// Overrides the registry-wide [`ErrorPolicy`] (see [`set_error_policy`]) for this [`CallbackRegistration`] only,
+	/// or restores the registry-wide default if [`None`] is passed.
+	///
+	/// # Deadlocks / Panics
+	///
+	/// Like creating or dropping a [`CallbackRegistration`], calling this method from within any handler **may** deadlock or panic.
+	#[inline(always)] // Proxy function.
+	pub fn set_error_policy(&self, error_policy: Option<ErrorPolicy>) {
+		callbacks::set_registration_error_policy(self, error_policy)
+	}
 }
 impl<R, C> CallbackRegistration<R, C>
 where
@@ -539,16 +1121,32 @@ where
 	/// > (See also the warning there.)
 	/// >
 	/// > For handwritten code or generated code with stricter thread-safety, please use [`.to_ref_thread_bound()`](`Self::to_ref_thread_bound`) instead whenever possible.
+	///
+	/// This also records this [`CallbackRegistration`] as [`Sync`], see [`.mark_thread_safe()`](`Self::mark_thread_safe`).
 	#[allow(clippy::inline_always)]
-	#[inline(always)] // Basically just a deref-copy.
+	#[inline(always)] // Basically just a deref-copy and an atomic store.
 	#[must_use]
 	pub fn to_ref(&self) -> CallbackRef<ThreadSafe, C> {
+		callbacks::record_thread_safe(self);
 		CallbackRef {
 			key: self.key,
 			phantom: PhantomData,
 		}
 	}
 
+	/// Records this [`CallbackRegistration`] as [`Sync`] in the callback registry,
+	/// which allows [`ThreadBound`] [`CallbackRef`]s with the same identity to be promoted via [`CallbackRef::try_into_thread_safe`].
+	///
+	/// [`.to_ref()`](`Self::to_ref`) does this automatically,
+	/// so this method is only needed if all [`CallbackRef`]s to this registration were created through [`.to_ref_thread_bound()`](`Self::to_ref_thread_bound`).
+	///
+	/// The record is removed when this [`CallbackRegistration`] is dropped. Repeated calls have no further effect.
+	#[allow(clippy::inline_always)]
+	#[inline(always)] // Proxy function.
+	pub fn mark_thread_safe(&self) {
+		callbacks::record_thread_safe(self)
+	}
+
 	/// Destroys a [`CallbackRegistration`] instance without running its destructor.
 	///
 	/// # Safety
@@ -605,21 +1203,48 @@ where
 	pub(crate) key: NonZeroU32,
 	phantom: PhantomData<(S, C)>,
 }
-/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and expect future broadening.
-impl<S> CallbackRef<S, fn(event: web::Event)>
+/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and [`CallbackParameter`].
+impl<S, P: CallbackParameter, O: CallbackReturn> CallbackRef<S, fn(parameter: P) -> O>
 where
 	S: ThreadSafety,
 {
 	/// Invokes the stored handler with the stored receiver and `parameter`,
 	/// provided that the original [`CallbackRegistration`] hasn't been dropped yet.
+	///
+	/// Returns the handler's output, or [`O::default()`](`Default::default`) if the handler didn't run or didn't complete.
 	#[allow(clippy::inline_always)]
 	#[inline(always)] // Proxy function.
-	pub fn call(self, parameter: web::Event) {
+	pub fn call(self, parameter: P) -> O {
 		// `parameter` is name-matched between implementations, to still allow later unification if Rust gains named parameters.
 		callbacks::invoke(self.key, parameter)
 	}
 }
-/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and expect future broadening.
+impl<S, E: web::EventStandIn, O: CallbackReturn> CallbackRef<S, fn(event: E) -> O>
+where
+	S: ThreadSafety,
+{
+	/// Converts this [`CallbackRef`] for a specific event type into one that accepts any [`web::Event`], keeping its identity.
+	///
+	/// This is how typed event callbacks are stored in [`EventBinding`](`crate::EventBinding`)s. See [`EventBinding::typed`](`crate::EventBinding::typed`).
+	///
+	/// # Implementation Contract
+	///
+	/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+	///
+	/// The resulting [`CallbackRef`] must only be called with [`web::Event`]s that actually are instances of `E`'s web type.
+	/// Calling it with a different type of [`web::Event`] is memory-safe, but the handler will likely panic or misbehave when it uses `E`'s methods.
+	#[allow(clippy::inline_always)]
+	#[inline(always)] // No-op.
+	#[must_use]
+	pub fn upcast(self) -> CallbackRef<S, fn(event: web::Event) -> O> {
+		// SAFETY(-ish): All `web::EventStandIn`s are ABI-compatible with `web::Event`, so the erased handler can be called with it.
+		CallbackRef {
+			key: self.key,
+			phantom: PhantomData,
+		}
+	}
+}
+/// Separate `impl`s due to Rust language limitation. See [`CallbackSignature`] and [`CallbackParameter`].
 impl<S, T> CallbackRef<S, fn(dom_ref: DomRef<&'_ T>)>
 where
 	S: ThreadSafety,
@@ -633,6 +1258,103 @@ where
 		callbacks::invoke_with_ref(self.key, parameter)
 	}
 }
+impl<S, C> CallbackRef<S, C>
+where
+	S: ThreadSafety,
+	C: CallbackSignature,
+{
+	/// Promotes this [`CallbackRef`] to [`ThreadSafe`] iff its [`CallbackRegistration`] is recorded as [`Sync`].
+	///
+	/// A registration is recorded as such once [`CallbackRegistration::to_ref`] or [`CallbackRegistration::mark_thread_safe`] has been called on it,
+	/// and stops being recorded when it's dropped, so [`CallbackRef`]s of dropped registrations can't be promoted.
+	///
+	/// Without the `"callbacks"` feature, no handlers are ever invoked, so this always succeeds.
+	///
+	/// # Errors
+	///
+	/// Iff the registration isn't (or isn't anymore) recorded as [`Sync`], in which case `self` is returned unchanged.
+	///
+	/// This includes registrations with a [`Sync`] receiver that were only ever referenced through [`CallbackRegistration::to_ref_thread_bound`],
+	/// as whether `R: Sync` is only known where [`CallbackRegistration::to_ref`] or [`CallbackRegistration::mark_thread_safe`] are available.
+	#[allow(clippy::inline_always)]
+	#[inline(always)] // Proxy function.
+	pub fn try_into_thread_safe(self) -> Result<CallbackRef<ThreadSafe, C>, Self> {
+		if callbacks::is_thread_safe(self.key) {
+			Ok(CallbackRef {
+				key: self.key,
+				phantom: PhantomData,
+			})
+		} else {
+			Err(self)
+		}
+	}
+}
+
+impl<'a, S: ThreadSafety> Node<'a, S> {
+	/// Promotes this [`Node`] to [`ThreadSafe`] iff all [`CallbackRef`]s in its VDOM graph can be promoted individually.
+	///
+	/// See [`CallbackRef::try_into_thread_safe`] for when that's the case.
+	///
+	/// This walks the entire graph, including [`Node::Memoized`] content, but doesn't allocate:
+	/// On success, the same graph is reinterpreted as [`ThreadSafe`], as the two variants share their layout.
+	///
+	/// # Errors
+	///
+	/// Iff any contained [`CallbackRef`] can't be promoted, in which case `self` is returned unchanged.
+	pub fn try_into_thread_safe(self) -> Result<Node<'a, ThreadSafe>, Self> {
+		if is_thread_safe(&self) {
+			Ok(unsafe {
+				// SAFETY:
+				// All VDOM types have the same layout regardless of `ThreadSafety` (see `tests/layout.rs`),
+				// and every callback in the graph was registered with a `Sync` receiver.
+				*addr_of!(self).cast()
+			})
+		} else {
+			Err(self)
+		}
+	}
+}
+
+fn is_thread_safe<S: ThreadSafety>(node: &Node<'_, S>) -> bool {
+	fn binding<S: ThreadSafety, C: CallbackSignature>(binding: Option<CallbackRef<S, C>>) -> bool {
+		binding.map_or(true, |binding| callbacks::is_thread_safe(binding.key))
+	}
+	fn element<S: ThreadSafety>(element: &Element<'_, S>) -> bool {
+		element
+			.event_bindings
+			.iter()
+			.all(|event_binding| callbacks::is_thread_safe(event_binding.callback.key))
+			&& element.shadow_root.map_or(true, |shadow_root| {
+				binding(shadow_root.dom_binding) && is_thread_safe(&shadow_root.content)
+			}) && is_thread_safe(&element.content)
+	}
+
+	match *node {
+		Node::Comment { dom_binding, .. } => binding(dom_binding),
+		Node::Text { dom_binding, .. } => binding(dom_binding),
+		Node::HtmlElement {
+			element: e,
+			dom_binding,
+		}
+		| Node::HeadContribution {
+			element: e,
+			dom_binding,
+		} => binding(dom_binding) && element(e),
+		Node::MathMlElement {
+			element: e,
+			dom_binding,
+		} => binding(dom_binding) && element(e),
+		Node::SvgElement {
+			element: e,
+			dom_binding,
+		} => binding(dom_binding) && element(e),
+		Node::Memoized { content, .. } => is_thread_safe(content),
+		Node::Multi(nodes) => nodes.iter().all(is_thread_safe),
+		Node::Keyed(pairs) => pairs.iter().all(|pair| is_thread_safe(&pair.content)),
+		Node::Portal(portal) => binding(portal.dom_binding) && is_thread_safe(&portal.content),
+		Node::RawHtml(_) | Node::RemnantSite(_) => true,
+	}
+}
 
 /// Indicates how exhausted the global callback registry is on a linear scale, with `0` indicating no or very low exhaustion and `255` indicating almost complete or complete exhaustion.
 #[allow(clippy::inline_always)]
@@ -642,6 +1364,203 @@ pub fn registry_exhaustion() -> u8 {
 	callbacks::registry_exhaustion()
 }
 
+/// Determines how panics in and errors returned from callback handlers are processed.
+///
+/// The registry-wide policy can be changed with [`set_error_policy`] and defaults to [`ErrorPolicy::Propagate`].
+/// Individual [`CallbackRegistration`]s can override it with [`.with_error_policy(…)`](`CallbackRegistration::with_error_policy`).
+///
+/// Without the `"callbacks"` feature, no handlers are ever invoked, so the policy has no effect.
+///
+/// # Panic Notes
+///
+/// Handler panics can only be caught and processed according to the policy if the app is built with `panic = "unwind"`.
+/// With `panic = "abort"`, which is the only strategy available on `wasm32-unknown-unknown`, a panicking handler aborts the app regardless of policy.
+/// There, only [`Err`]s returned from [`new_fallible`](`CallbackRegistration::new_fallible`) handlers are subject to it.
+///
+/// Whenever a handler panics, any continuations it scheduled via [`when_unlocked_locally`] are dropped without being executed, regardless of policy.
+///
+/// Continuations scheduled by a handler that returned an [`Err`] still run, before the error is processed.
+#[derive(Debug, Clone, Copy)]
+pub enum ErrorPolicy {
+	/// Resumes unwinding after a handler panic, and panics with the [`Debug`] representation of a returned error.
+	///
+	/// This is the default, which matches the behaviour of handlers that can't fail.
+	///
+	/// > When targeting Wasm, this usually halts the app entirely.
+	Propagate,
+	/// Prints a description of the error to the browser's JavaScript console (or standard error on other platforms) and then resumes as if the handler completed normally.
+	LogAndSwallow,
+	/// Passes a [`CallbackError`] to an app-defined error handler and then resumes as if the handler completed normally.
+	///
+	/// The error handler itself runs after the callback registry has been unlocked.
+	/// If it panics, that panic is propagated.
+	Route(fn(error: CallbackError)),
+}
+/// Describes a failed callback handler invocation. See [`ErrorPolicy::Route`].
+///
+/// Without the `"callbacks"` feature, the private field that describes the failure is [uninhabited](https://doc.rust-lang.org/nomicon/exotic-sizes.html#empty-types),
+/// so no instances of this type can be created.
+#[derive(Debug)]
+pub struct CallbackError {
+	key: NonZeroU32,
+	signature: &'static str,
+	kind: CallbackErrorKind,
+}
+impl CallbackError {
+	/// The opaque identity of the failed callback.
+	///
+	/// This matches the identity of any [`CallbackRef`] created from the respective [`CallbackRegistration`].
+	#[inline(always)]
+	#[must_use]
+	pub const fn key(&self) -> NonZeroU32 {
+		self.key
+	}
+
+	/// Checks whether this [`CallbackError`] was caused by an invocation of `callback_ref`.
+	#[inline(always)]
+	#[must_use]
+	pub fn is_from<S: ThreadSafety, C: CallbackSignature>(
+		&self,
+		callback_ref: CallbackRef<S, C>,
+	) -> bool {
+		self.key == callback_ref.key
+	}
+
+	/// The [`type_name`](`core::any::type_name`) of the failed callback's [`CallbackSignature`].
+	///
+	/// The exact contents of this string are unspecified and may change between versions of Rust.
+	#[inline(always)]
+	#[must_use]
+	pub const fn signature(&self) -> &'static str {
+		self.signature
+	}
+
+	/// How the handler failed.
+	#[inline(always)]
+	#[must_use]
+	pub const fn kind(&self) -> &CallbackErrorKind {
+		&self.kind
+	}
+
+	/// How the handler failed.
+	#[inline(always)]
+	#[must_use]
+	#[allow(clippy::missing_const_for_fn)] // Not `const` on MSRV.
+	pub fn into_kind(self) -> CallbackErrorKind {
+		self.kind
+	}
+}
+impl Display for CallbackError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"[lignin] Callback handler {} ({}) ",
+			self.key, self.signature
+		)?;
+		match &self.kind {
+			#[cfg(feature = "callbacks")]
+			CallbackErrorKind::Panicked(panic) => {
+				if let Some(message) = panic.downcast_ref::<&str>() {
+					write!(f, "panicked: {}", message)
+				} else if let Some(message) = panic.downcast_ref::<std::string::String>() {
+					write!(f, "panicked: {}", message)
+				} else {
+					write!(f, "panicked.")
+				}
+			}
+			#[cfg(feature = "callbacks")]
+			CallbackErrorKind::Returned(returned) => write!(f, "returned an error: {:?}", returned),
+			#[cfg(not(feature = "callbacks"))]
+			CallbackErrorKind::Panicked(vacant) | CallbackErrorKind::Returned(vacant) => match *vacant {},
+		}
+	}
+}
+
+/// How a callback handler failed. See [`CallbackError`].
+#[derive(Debug)]
+pub enum CallbackErrorKind {
+	/// The handler panicked. Contains the panic payload, as returned by [`catch_unwind`](https://doc.rust-lang.org/stable/std/panic/fn.catch_unwind.html).
+	Panicked(
+		#[cfg(feature = "callbacks")] std::boxed::Box<dyn core::any::Any + Send>,
+		#[cfg(not(feature = "callbacks"))] web::FeatureNeeded,
+	),
+	/// The handler returned an [`Err`]. Contains the boxed error value.
+	Returned(
+		#[cfg(feature = "callbacks")] std::boxed::Box<dyn Debug>,
+		#[cfg(not(feature = "callbacks"))] web::FeatureNeeded,
+	),
+}
+
+/// Retrieves the registry-wide [`ErrorPolicy`].
+#[allow(clippy::inline_always)]
+#[inline(always)] // Proxy function.
+#[must_use]
+pub fn error_policy() -> ErrorPolicy {
+	callbacks::error_policy()
+}
+
+/// Sets the registry-wide [`ErrorPolicy`], which applies to all [`CallbackRegistration`]s without individual override.
+///
+/// # Deadlocks / Panics
+///
+/// Like creating or dropping a [`CallbackRegistration`], calling this function from within any handler **may** deadlock or panic.
+#[allow(clippy::inline_always)]
+#[inline(always)] // Proxy function.
+pub fn set_error_policy(error_policy: ErrorPolicy) {
+	callbacks::set_error_policy(error_policy)
+}
+
+/// A type-erased async handler [`Future`], as passed to the hook set via [`set_spawn_local`].
+///
+/// This type is only inhabited with the `"callbacks"` feature enabled.
+#[must_use = "futures do nothing unless polled"]
+pub struct LocalFuture(
+	#[cfg(feature = "callbacks")] pub(crate) Pin<std::boxed::Box<dyn Future<Output = ()>>>,
+	#[cfg(not(feature = "callbacks"))] web::FeatureNeeded,
+);
+impl Future for LocalFuture {
+	type Output = ();
+
+	#[inline(always)] // Proxy function.
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		#[cfg(feature = "callbacks")]
+		return self.get_mut().0.as_mut().poll(cx);
+		#[cfg(not(feature = "callbacks"))]
+		{
+			let _ = cx;
+			match self.0 {}
+		}
+	}
+}
+impl Debug for LocalFuture {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("LocalFuture").finish()
+	}
+}
+
+/// Sets the local executor hook that futures returned by [`CallbackRegistration::new_async`] handlers are spawned with.
+///
+/// The hook is called outside of any handler, after the registry lock has been released.
+///
+/// # Example
+///
+/// ```rust
+/// # fn wasm_bindgen_futures_spawn_local(future: impl 'static + core::future::Future<Output = ()>) {}
+/// lignin::callback_registry::set_spawn_local(|future| wasm_bindgen_futures_spawn_local(future));
+/// ```
+#[allow(clippy::inline_always)]
+#[inline(always)] // Proxy function.
+pub fn set_spawn_local(spawn_local: fn(future: LocalFuture)) {
+	callbacks::set_spawn_local(spawn_local)
+}
+
+/// Prints a diagnostic the same way [`ErrorPolicy::LogAndSwallow`] logs errors. No-op without the `"callbacks"` feature.
+#[allow(clippy::inline_always)]
+#[inline(always)] // Proxy function.
+pub(crate) fn log_warning(warning: fmt::Arguments) {
+	callbacks::log_warning(warning)
+}
+
 /// These functions are intended as storage optimization for in-browser renderers.
 ///
 /// The [`CallbackRef`]'s raw numerical value can be passed through JavaScript directly,
@@ -779,13 +1698,100 @@ pub unsafe fn yet_more_unsafe_force_clear_callback_registry() {
 
 /// Marks function pointers for which callbacks are implemented.
 ///
-/// > This not being a blanket implementation over [`fn(T)`](https://doc.rust-lang.org/stable/std/primitive.fn.html) is largely related to [Rust#56105](https://github.com/rust-lang/rust/issues/56105).
+/// This trait is sealed, but is implemented for [`fn(P) -> O`](https://doc.rust-lang.org/stable/std/primitive.fn.html) for any `P: `[`CallbackParameter`] and `O: `[`CallbackReturn`].
+/// Additional signatures are declared explicitly through [`callback_parameter!`](`callback_parameter`).
+///
+/// > This not being a blanket implementation over any [`fn(T)`](https://doc.rust-lang.org/stable/std/primitive.fn.html) is largely related to [Rust#56105](https://github.com/rust-lang/rust/issues/56105).
 /// >
 /// > In short, an `impl <T> CallbackSignature for fn(T) {}` currently does not cover for example `fn(web::DomRef<&'_ T>)`, but their collision will become a hard error in the future (as of March 2021/Rust 1.50.0).
+/// >
+/// > [`DomRef`] is not a [`CallbackParameter`] (and can't be declared as one, due to orphan rules, outside this crate), which keeps the two implementations apart.
 pub trait CallbackSignature: Sealed + Sized + Copy {}
-impl CallbackSignature for fn(event: web::Event) {}
+impl<P: CallbackParameter, O: CallbackReturn> CallbackSignature for fn(parameter: P) -> O {}
 impl<T> CallbackSignature for fn(dom_ref: web::DomRef<&'_ T>) {}
 
+/// Marks types that can be passed to a [`CallbackRef`] as its only parameter, through a [`fn(P)`](https://doc.rust-lang.org/stable/std/primitive.fn.html) [`CallbackSignature`].
+///
+/// Declare your own types with [`callback_parameter!`](`callback_parameter`) to add new callback signatures.
+///
+/// This trait isn't sealed, since the macro expands in the declaring crate and can only use public items there.
+/// Implementing it manually isn't covered by semantic versioning, however, as it may gain requirements that the macro takes care of.
+///
+/// Soundness doesn't depend on which types implement this trait: Parameters are only ever moved into handlers that are typed for them.
+pub trait CallbackParameter: Sized {}
+
+/// Canonically located at `callback_registry::callback_parameter`.  
+/// Declares each given type as [`CallbackParameter`], which adds [`fn(P) -> O`](https://doc.rust-lang.org/stable/std/primitive.fn.html) [`CallbackSignature`]s for it.
+///
+/// Use this for example for [***custom events***](https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent) with typed [***detail***](https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent/detail) or app-level callbacks that don't involve the DOM at all.  
+/// Such callbacks use the same registry and have the same [`CallbackRef`] identity semantics as the built-in ones.
+///
+/// Due to orphan rules, you'll have to wrap foreign types (like [`String`](https://doc.rust-lang.org/stable/std/string/struct.String.html)) in a local newtype to do so.
+///
+/// # Erasure
+///
+/// Without the `"callbacks"` feature, [`CallbackRef::call`] is a no-op and handlers are never stored.
+///
+/// Parameter types that only make sense with that feature enabled should become [uninhabited](https://doc.rust-lang.org/nomicon/exotic-sizes.html#empty-types) without it,
+/// like the stand-ins in the [`web`] module do, so that code paths that depend on them are removed entirely.
+///
+/// # Example
+///
+/// ```rust
+/// use core::pin::Pin;
+/// use lignin::{callback_registry::callback_parameter, CallbackRegistration};
+///
+/// pub struct Change(pub String);
+/// callback_parameter!(Change);
+///
+/// struct Receiver;
+/// let receiver = Box::pin(Receiver);
+/// let registration = CallbackRegistration::<_, fn(Change)>::new(
+///   receiver.as_ref(),
+///   |_receiver, change| println!("Changed to {:?}.", change.0),
+/// );
+///
+/// registration.to_ref_thread_bound().call(Change("new value".to_string()));
+/// ```
+#[macro_export]
+macro_rules! callback_parameter {
+	($($type:ty),+$(,)?) => {$(
+		impl $crate::callback_registry::CallbackParameter for $type {}
+	)+};
+}
+
+#[doc(inline)]
+pub use callback_parameter;
+
+/// Marks types that can be returned by callback handlers through a [`fn(P) -> O`](https://doc.rust-lang.org/stable/std/primitive.fn.html) [`CallbackSignature`].
+///
+/// This trait is sealed. It's implemented for `()` and [`EventOutcome`](`crate::EventOutcome`).
+///
+/// [`CallbackRef::call`] returns [`Default::default()`] whenever the handler doesn't produce a value,
+/// for example because its [`CallbackRegistration`] was dropped or because it panicked and the error was swallowed or routed.
+pub trait CallbackReturn: Sealed + Default {}
+impl CallbackReturn for () {}
+impl CallbackReturn for crate::EventOutcome {}
+callback_parameter!(
+	(),
+	bool,
+	char,
+	u8,
+	u16,
+	u32,
+	u64,
+	u128,
+	usize,
+	i8,
+	i16,
+	i32,
+	i64,
+	i128,
+	isize,
+	f32,
+	f64,
+);
+
 /// Causes a continuation to be called when the callback registry is not locked (anymore) by the current thread.
 ///
 /// > **Warning:**
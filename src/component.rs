@@ -0,0 +1,265 @@
+//! Stateful components and a render scheduler on top of [`frames`](`crate::frames`). Requires the `"component"` feature.
+//!
+//! Frameworks built on `lignin` usually share this layer: Components keep their state pinned, so that they can own [`CallbackRegistration`](`crate::CallbackRegistration`)s with themselves as receiver,
+//! render into an arena and are invalidated from within callback handlers.
+//!
+//! A [`Scheduler`] owns the root [`Component`], a [`Frames`] instance and a [`Backend`] that applies each new VDOM iteration (for example to the DOM).
+//! [`Invalidator`]s handed out by it schedule a re-render through [`when_unlocked_locally`],
+//! so that all invalidations from within one callback handler are batched into a single render after it returns.
+//!
+//! > Outside of callback handlers, [`Invalidator::invalidate`] renders immediately.
+//!
+//! # Example
+//!
+//! ```rust
+//! use core::{cell::Cell, pin::Pin};
+//! use lignin::{
+//!   component::{Component, Invalidator, Scheduler},
+//!   frames::Frame,
+//!   Node, ThreadSafe,
+//! };
+//!
+//! struct Counter {
+//!   count: Cell<u32>,
+//!   invalidator: Invalidator,
+//! }
+//!
+//! impl Component<ThreadSafe> for Counter {
+//!   fn render<'a>(self: Pin<&'a Self>, frame: &'a Frame<'a, ThreadSafe>) -> Node<'a, ThreadSafe> {
+//!     frame.bump().alloc_str(&self.count.get().to_string()).into()
+//!   }
+//! }
+//!
+//! let scheduler = Scheduler::new(
+//!   |previous: Option<&Node<ThreadSafe>>, next: &Node<ThreadSafe>| {
+//!     // Diff `previous` into `next` here.
+//!   },
+//!   |invalidator| Counter { count: Cell::new(0), invalidator },
+//! );
+//!
+//! // Usually from within a callback handler with the component as receiver:
+//! let counter = scheduler.root();
+//! counter.count.set(1);
+//! counter.invalidator.invalidate();
+//! ```
+
+extern crate alloc;
+
+use crate::{
+	callback_registry::when_unlocked_locally,
+	frames::{Frame, Frames},
+	Node, ThreadSafety,
+};
+use alloc::rc::{Rc, Weak};
+use core::{
+	cell::{Cell, RefCell},
+	fmt::{self, Debug, Formatter},
+	pin::Pin,
+	ptr::addr_of,
+};
+
+/// A stateful VDOM generator.
+///
+/// Components are rendered through [`Pin<&Self>`](`Pin`), so that they can act as receiver of the [`CallbackRegistration`](`crate::CallbackRegistration`)s they own.
+pub trait Component<S: ThreadSafety> {
+	/// Renders the component's current state into `frame`.
+	///
+	/// Child components can be rendered by calling their [`Component::render`] with the same `frame`,
+	/// and [`memo`](`crate::memo::memo`) can be used to skip unchanged subtrees.
+	fn render<'a>(self: Pin<&'a Self>, frame: &'a Frame<'a, S>) -> Node<'a, S>;
+}
+
+/// Applies rendered VDOM iterations, for example to the DOM or by serialising them.
+///
+/// Implemented on matching closures.
+pub trait Backend<S: ThreadSafety> {
+	/// Applies `next`, which replaces `previous` (or nothing, on the first call).
+	fn update(&mut self, previous: Option<&Node<'_, S>>, next: &Node<'_, S>);
+}
+
+impl<S: ThreadSafety, F> Backend<S> for F
+where
+	F: FnMut(Option<&Node<'_, S>>, &Node<'_, S>),
+{
+	fn update(&mut self, previous: Option<&Node<'_, S>>, next: &Node<'_, S>) {
+		self(previous, next)
+	}
+}
+
+/// Owns a root [`Component`] and re-renders it into a [`Backend`] whenever it's invalidated.
+///
+/// See the [module documentation](`self`) for more information.
+pub struct Scheduler<S, C, B>
+where
+	S: ThreadSafety + 'static,
+	C: Component<S> + 'static,
+	B: Backend<S> + 'static,
+{
+	shared: Rc<Shared<S, C, B>>,
+	root: Pin<Rc<C>>,
+}
+
+/// A handle that schedules a re-render of the [`Scheduler`] it was created by.
+///
+/// Invalidations after that [`Scheduler`] has been dropped are ignored.
+#[derive(Clone)]
+pub struct Invalidator {
+	shared: Weak<dyn Invalidate>,
+}
+
+struct Shared<S, C, B>
+where
+	S: ThreadSafety + 'static,
+{
+	dirty: Cell<bool>,
+	scheduled: Cell<bool>,
+	/// Only [`None`] while the root component is created.
+	rendering: RefCell<Option<Rendering<S, C, B>>>,
+}
+
+struct Rendering<S: ThreadSafety + 'static, C, B> {
+	// Declared before `root`, so that its graphs (which may borrow from `root`) are dropped first.
+	frames: Frames<S>,
+	root: Pin<Rc<C>>,
+	backend: B,
+}
+
+trait Invalidate {
+	fn invalidate(self: Rc<Self>);
+}
+
+impl<S, C, B> Scheduler<S, C, B>
+where
+	S: ThreadSafety + 'static,
+	C: Component<S> + 'static,
+	B: Backend<S> + 'static,
+{
+	/// Creates a new [`Scheduler`] and renders the component returned by `root` once.
+	///
+	/// `root` receives an [`Invalidator`] that it can store in the component's state.
+	pub fn new(backend: B, root: impl FnOnce(Invalidator) -> C) -> Self {
+		let shared = Rc::new(Shared {
+			dirty: Cell::new(true),
+			scheduled: Cell::new(false),
+			rendering: RefCell::new(None),
+		});
+		let root = Rc::pin(root(Invalidator {
+			shared: Rc::downgrade(&shared) as Weak<dyn Invalidate>,
+		}));
+		*shared.rendering.borrow_mut() = Some(Rendering {
+			frames: Frames::new(),
+			root: root.clone(),
+			backend,
+		});
+		shared.run();
+		Self { shared, root }
+	}
+
+	/// Creates a new [`Invalidator`] for this [`Scheduler`].
+	#[must_use]
+	pub fn invalidator(&self) -> Invalidator {
+		Invalidator {
+			shared: Rc::downgrade(&self.shared) as Weak<dyn Invalidate>,
+		}
+	}
+
+	/// Retrieves the root component.
+	#[must_use]
+	pub fn root(&self) -> Pin<&C> {
+		self.root.as_ref()
+	}
+}
+
+impl<S, C, B> Debug for Scheduler<S, C, B>
+where
+	S: ThreadSafety + 'static,
+	C: Component<S> + 'static,
+	B: Backend<S> + 'static,
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Scheduler")
+			.field("dirty", &self.shared.dirty.get())
+			.field("scheduled", &self.shared.scheduled.get())
+			.finish_non_exhaustive()
+	}
+}
+
+impl Invalidator {
+	/// Marks the component tree as changed and schedules a re-render through [`when_unlocked_locally`].
+	///
+	/// Repeated invalidations before that re-render runs are batched, and invalidations during rendering cause one more render afterwards.
+	pub fn invalidate(&self) {
+		if let Some(shared) = self.shared.upgrade() {
+			shared.invalidate()
+		}
+	}
+}
+
+impl Debug for Invalidator {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Invalidator")
+			.field("active", &(self.shared.strong_count() > 0))
+			.finish()
+	}
+}
+
+impl<S, C, B> Invalidate for Shared<S, C, B>
+where
+	S: ThreadSafety + 'static,
+	C: Component<S> + 'static,
+	B: Backend<S> + 'static,
+{
+	fn invalidate(self: Rc<Self>) {
+		self.dirty.set(true);
+		if !self.scheduled.replace(true) {
+			let shared = Rc::downgrade(&self);
+			drop(self);
+			when_unlocked_locally(move || {
+				if let Some(shared) = shared.upgrade() {
+					shared.scheduled.set(false);
+					shared.run()
+				}
+			})
+		}
+	}
+}
+
+impl<S, C, B> Shared<S, C, B>
+where
+	S: ThreadSafety + 'static,
+	C: Component<S> + 'static,
+	B: Backend<S> + 'static,
+{
+	/// Renders until no longer dirty, unless already rendering further up the stack (which then picks up the change).
+	fn run(&self) {
+		if let Ok(mut rendering) = self.rendering.try_borrow_mut() {
+			if let Some(rendering) = rendering.as_mut() {
+				while self.dirty.replace(false) {
+					rendering.render()
+				}
+			}
+		}
+	}
+}
+
+impl<S, C, B> Rendering<S, C, B>
+where
+	S: ThreadSafety + 'static,
+	C: Component<S> + 'static,
+	B: Backend<S>,
+{
+	fn render(&mut self) {
+		let root: Pin<&'static C> = unsafe {
+			// SAFETY:
+			// `Frames` keeps graphs that borrow from `root` as `'static`.
+			// `root` is pinned on the heap, never replaced and kept alive until after `frames` is dropped,
+			// and components can't mutate data they lent out through `Pin<&Self>`.
+			Pin::new_unchecked(&*addr_of!(*self.root))
+		};
+		self.frames.render(|frame| root.render(frame));
+		self.backend.update(
+			self.frames.previous(),
+			self.frames.current().expect("unreachable"),
+		);
+	}
+}
@@ -0,0 +1,507 @@
+//! Time-sliced, resumable structural diffing of VDOM graphs. Requires the `"diff"` feature.
+//!
+//! Diffing a large graph in one go can block the main thread for several frames.
+//! A [`Diff`] instead processes work only until a [`Budget`] runs out and can be [resumed](`Diff::resume`) later, for example in the next animation frame.
+//!
+//! The resulting [`Patch`]es are buffered and only handed out by [`Diff::commit`] once the whole graph has been diffed,
+//! so renderers can apply them atomically without breaking the [`DomRef`](`crate::DomRef`) ordering guarantees.
+//! Both graphs stay borrowed until then, which (with for example [`frames`](`crate::frames`)) also prevents rendering the next VDOM iteration in the meantime.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{diff::{Diff, Patch}, Node, ThreadSafe};
+//!
+//! let previous_nodes = ["a".into(), "b".into()];
+//! let next_nodes = ["a".into(), "c".into()];
+//! let previous: Node<ThreadSafe> = Node::Multi(&previous_nodes);
+//! let next: Node<ThreadSafe> = Node::Multi(&next_nodes);
+//!
+//! let mut diff = Diff::new(&previous, &next);
+//! while diff.resume(&mut 1).is_pending() {
+//!   // Yield to the event loop here.
+//! }
+//!
+//! let mut patches = vec![];
+//! diff.commit(|path, patch| patches.push((path.to_string(), patch))).unwrap();
+//! assert_eq!(patches, [("/1".to_string(), Patch::Update { previous: &previous_nodes[1], next: &next_nodes[1] })]);
+//! ```
+
+extern crate alloc;
+
+use crate::{
+	path::{NodePath, PathStep},
+	Element, Node, ReorderableFragment, ThreadSafety,
+};
+use alloc::{
+	collections::{BTreeMap, BTreeSet},
+	vec,
+	vec::Vec,
+};
+use core::{
+	fmt::{self, Debug, Formatter},
+	ops::Range,
+	ptr,
+	task::Poll,
+};
+
+/// Limits how much work a single call to [`Diff::resume`] does.
+///
+/// Implemented on [`usize`] as number of remaining [`Node`] comparisons,
+/// and on closures that return whether there's time left, for example before a deadline.
+pub trait Budget {
+	/// Called before each [`Node`] comparison. Returns whether to proceed with it.
+	fn proceed(&mut self) -> bool;
+}
+
+impl Budget for usize {
+	fn proceed(&mut self) -> bool {
+		if *self == 0 {
+			false
+		} else {
+			*self -= 1;
+			true
+		}
+	}
+}
+
+impl<F: FnMut() -> bool> Budget for F {
+	fn proceed(&mut self) -> bool {
+		self()
+	}
+}
+
+/// A single change between two VDOM graphs, as handed out by [`Diff::commit`] together with a [`NodePath`].
+///
+/// Paths of [`Patch::Remove`] are valid within the previous graph and those of [`Patch::Insert`] within the next one.
+/// All other paths are valid within both.
+///
+/// Patches are committed in document order, with [`Patch::Unbind`] before and [`Patch::Bind`] after all other patches within the respective subtree,
+/// except that within each [`Node::Multi`] and [`Node::Keyed`], [`Patch::Remove`]s are committed before all other patches
+/// (for trailing [`Node::Multi`] items back to front, for [`Node::Keyed`] fragments in their previous order)
+/// and a [`Patch::Reorder`] is committed after them.
+/// Renderers that apply them in this order fulfil the [`DomRef`](`crate::DomRef`) contract for retained nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Patch<'a, S: ThreadSafety> {
+	/// The subtree must be recreated, including all its [`DomRef`](`crate::DomRef`) bindings.
+	Replace {
+		/// The subtree to tear down.
+		previous: &'a Node<'a, S>,
+		/// The subtree to create in its place.
+		next: &'a Node<'a, S>,
+	},
+	/// The [`Node`] is retained, but its own data changed.
+	///
+	/// For [`Node::Text`] and [`Node::Comment`], this is their text.
+	/// For elements, this is any part of the [`Element`] except its [`Element::name`], [`Element::creation_options`] and [`Element::content`],
+	/// including its [`Element::shadow_root`], which is diffed by the renderer.
+	Update {
+		/// The [`Node`] as it was.
+		previous: &'a Node<'a, S>,
+		/// The [`Node`] as it should be.
+		next: &'a Node<'a, S>,
+	},
+	/// The subtree was removed from a [`Node::Multi`] or [`Node::Keyed`].
+	Remove {
+		/// The removed subtree.
+		previous: &'a Node<'a, S>,
+	},
+	/// The subtree was inserted into a [`Node::Multi`] or [`Node::Keyed`].
+	Insert {
+		/// The inserted subtree.
+		next: &'a Node<'a, S>,
+	},
+	/// The order of [`ReorderableFragment`]s in a [`Node::Keyed`] changed.
+	///
+	/// Committed after all other patches within the [`Node::Keyed`], so the fragments that remain are already up to date.
+	Reorder {
+		/// The fragments as they were.
+		previous: &'a [ReorderableFragment<'a, S>],
+		/// The fragments in their new order.
+		next: &'a [ReorderableFragment<'a, S>],
+	},
+	/// The `dom_binding` of a retained [`Node`] (or its [`Portal`](`crate::Portal`)) changed, and the previous one must be called with [`DomRef::Removing`](`crate::DomRef::Removing`).
+	Unbind {
+		/// The [`Node`] with the previous binding.
+		previous: &'a Node<'a, S>,
+	},
+	/// The `dom_binding` of a retained [`Node`] (or its [`Portal`](`crate::Portal`)) changed, and the next one must be called with [`DomRef::Added`](`crate::DomRef::Added`).
+	Bind {
+		/// The [`Node`] with the next binding.
+		next: &'a Node<'a, S>,
+	},
+}
+
+/// A resumable diff between two VDOM graphs.
+///
+/// See the [module documentation](`self`) for more information.
+pub struct Diff<'a, S: ThreadSafety> {
+	work: Vec<Work<'a, S>>,
+	/// The path of the [`Node`]s currently being diffed.
+	path: Vec<PathStep>,
+	/// Backing storage of all patch paths.
+	steps: Vec<PathStep>,
+	patches: Vec<(Range<usize>, Patch<'a, S>)>,
+}
+
+enum Work<'a, S: ThreadSafety> {
+	Diff {
+		previous: &'a Node<'a, S>,
+		next: &'a Node<'a, S>,
+	},
+	Enter(PathStep),
+	Leave,
+	Emit(Patch<'a, S>),
+}
+
+impl<'a, S: ThreadSafety> Diff<'a, S> {
+	/// Creates a new [`Diff`] from `previous` to `next`, without doing any work yet.
+	#[must_use]
+	pub fn new(previous: &'a Node<'a, S>, next: &'a Node<'a, S>) -> Self {
+		Self {
+			work: vec![Work::Diff { previous, next }],
+			path: Vec::new(),
+			steps: Vec::new(),
+			patches: Vec::new(),
+		}
+	}
+
+	/// Continues diffing until either `budget` is exhausted ([`Poll::Pending`]) or the diff is complete ([`Poll::Ready`]).
+	pub fn resume(&mut self, budget: &mut impl Budget) -> Poll<()> {
+		while let Some(work) = self.work.pop() {
+			match work {
+				Work::Diff { previous, next } => {
+					if !budget.proceed() {
+						self.work.push(Work::Diff { previous, next });
+						return Poll::Pending;
+					}
+					self.diff(previous, next)
+				}
+				Work::Enter(step) => self.path.push(step),
+				Work::Leave => {
+					self.path.pop();
+				}
+				Work::Emit(patch) => self.emit(patch),
+			}
+		}
+		Poll::Ready(())
+	}
+
+	/// Indicates whether the diff is complete, so that it can be [committed](`Diff::commit`).
+	#[must_use]
+	pub fn is_complete(&self) -> bool {
+		self.work.is_empty()
+	}
+
+	/// Hands out all [`Patch`]es in order, with their [`NodePath`]s.
+	///
+	/// # Errors
+	///
+	/// Iff the diff isn't [complete](`Diff::is_complete`) yet, in which case it's returned unchanged.
+	pub fn commit(self, mut apply: impl FnMut(NodePath<'_>, Patch<'a, S>)) -> Result<(), Self> {
+		if !self.is_complete() {
+			return Err(self);
+		}
+		let Self { steps, patches, .. } = self;
+		for (range, patch) in patches {
+			apply(NodePath::new(&steps[range]), patch)
+		}
+		Ok(())
+	}
+
+	fn emit(&mut self, patch: Patch<'a, S>) {
+		let start = self.steps.len();
+		self.steps.extend_from_slice(&self.path);
+		self.patches.push((start..self.steps.len(), patch));
+	}
+
+	fn emit_at(&mut self, step: PathStep, patch: Patch<'a, S>) {
+		self.path.push(step);
+		self.emit(patch);
+		self.path.pop();
+	}
+
+	/// Schedules a patch to be emitted at `step` once the work already scheduled on top of it is done.
+	fn defer_at(&mut self, step: PathStep, patch: Patch<'a, S>) {
+		self.work
+			.extend([Work::Leave, Work::Emit(patch), Work::Enter(step)]);
+	}
+
+	fn child(&mut self, step: PathStep, previous: &'a Node<'a, S>, next: &'a Node<'a, S>) {
+		self.work.extend([
+			Work::Leave,
+			Work::Diff { previous, next },
+			Work::Enter(step),
+		]);
+	}
+
+	/// Emits the patches for a retained [`Node`] itself. Must be called before scheduling its children.
+	///
+	/// `bindings` indicates whether there was and will be a binding, iff it changed.
+	fn retain(
+		&mut self,
+		previous: &'a Node<'a, S>,
+		next: &'a Node<'a, S>,
+		bindings: Option<(bool, bool)>,
+		updated: bool,
+	) {
+		let (unbind, bind) = bindings.unwrap_or_default();
+		if unbind {
+			self.emit(Patch::Unbind { previous })
+		}
+		if updated {
+			self.emit(Patch::Update { previous, next })
+		}
+		if bind {
+			self.work.push(Work::Emit(Patch::Bind { next }))
+		}
+	}
+
+	#[allow(clippy::too_many_lines)] // One arm per variant.
+	fn diff(&mut self, previous: &'a Node<'a, S>, next: &'a Node<'a, S>) {
+		if ptr::eq(previous, next) {
+			return;
+		}
+		match (previous, next) {
+			(
+				Node::Comment {
+					comment: previous_comment,
+					dom_binding: previous_binding,
+				},
+				Node::Comment {
+					comment: next_comment,
+					dom_binding: next_binding,
+				},
+			) => self.retain(
+				previous,
+				next,
+				bindings(*previous_binding, *next_binding),
+				previous_comment != next_comment,
+			),
+			(
+				Node::Text {
+					text: previous_text,
+					dom_binding: previous_binding,
+				},
+				Node::Text {
+					text: next_text,
+					dom_binding: next_binding,
+				},
+			) => self.retain(
+				previous,
+				next,
+				bindings(*previous_binding, *next_binding),
+				previous_text != next_text,
+			),
+			(
+				Node::HtmlElement {
+					element: previous_element,
+					dom_binding: previous_binding,
+				},
+				Node::HtmlElement {
+					element: next_element,
+					dom_binding: next_binding,
+				},
+			) => self.element(
+				previous,
+				next,
+				previous_element,
+				next_element,
+				bindings(*previous_binding, *next_binding),
+			),
+			(
+				Node::MathMlElement {
+					element: previous_element,
+					dom_binding: previous_binding,
+				},
+				Node::MathMlElement {
+					element: next_element,
+					dom_binding: next_binding,
+				},
+			) => self.element(
+				previous,
+				next,
+				previous_element,
+				next_element,
+				bindings(*previous_binding, *next_binding),
+			),
+			(
+				Node::SvgElement {
+					element: previous_element,
+					dom_binding: previous_binding,
+				},
+				Node::SvgElement {
+					element: next_element,
+					dom_binding: next_binding,
+				},
+			) => self.element(
+				previous,
+				next,
+				previous_element,
+				next_element,
+				bindings(*previous_binding, *next_binding),
+			),
+			(
+				Node::Memoized {
+					state_key: previous_key,
+					content: previous_content,
+				},
+				Node::Memoized {
+					state_key: next_key,
+					content: next_content,
+				},
+			) => {
+				if previous_key != next_key {
+					self.child(PathStep::Content, previous_content, next_content)
+				}
+			}
+			(Node::Multi(previous_nodes), Node::Multi(next_nodes)) => {
+				for (index, previous) in previous_nodes
+					.iter()
+					.enumerate()
+					.skip(next_nodes.len())
+					.rev()
+				{
+					self.emit_at(PathStep::Index(index), Patch::Remove { previous })
+				}
+				for (index, next) in next_nodes.iter().enumerate().rev() {
+					match previous_nodes.get(index) {
+						Some(previous) => self.child(PathStep::Index(index), previous, next),
+						None => self.defer_at(PathStep::Index(index), Patch::Insert { next }),
+					}
+				}
+			}
+			(Node::Keyed(previous_fragments), Node::Keyed(next_fragments)) => {
+				self.keyed(previous_fragments, next_fragments)
+			}
+			(Node::Portal(previous_portal), Node::Portal(next_portal))
+				if previous_portal.target == next_portal.target =>
+			{
+				self.retain(
+					previous,
+					next,
+					bindings(previous_portal.dom_binding, next_portal.dom_binding),
+					false,
+				);
+				self.child(
+					PathStep::Content,
+					&previous_portal.content,
+					&next_portal.content,
+				)
+			}
+			(
+				Node::HeadContribution {
+					element: previous_element,
+					dom_binding: previous_binding,
+				},
+				Node::HeadContribution {
+					element: next_element,
+					dom_binding: next_binding,
+				},
+			) if previous_element == next_element => self.retain(
+				previous,
+				next,
+				bindings(*previous_binding, *next_binding),
+				false,
+			),
+			(Node::RawHtml(previous_html), Node::RawHtml(next_html))
+				if previous_html == next_html => {}
+			(Node::RemnantSite(previous_site), Node::RemnantSite(next_site))
+				if previous_site == next_site => {}
+			_ => self.emit(Patch::Replace { previous, next }),
+		}
+	}
+
+	fn element(
+		&mut self,
+		previous: &'a Node<'a, S>,
+		next: &'a Node<'a, S>,
+		previous_element: &'a Element<'a, S>,
+		next_element: &'a Element<'a, S>,
+		bindings: Option<(bool, bool)>,
+	) {
+		if previous_element.name != next_element.name
+			|| previous_element.creation_options != next_element.creation_options
+		{
+			return self.emit(Patch::Replace { previous, next });
+		}
+		let same = ptr::eq(previous_element, next_element);
+		let updated = !same
+			&& (previous_element.attributes != next_element.attributes
+				|| previous_element.properties != next_element.properties
+				|| previous_element.classes != next_element.classes
+				|| previous_element.styles != next_element.styles
+				|| previous_element.shadow_root != next_element.shadow_root
+				|| previous_element.event_bindings != next_element.event_bindings);
+		self.retain(previous, next, bindings, updated);
+		if !same {
+			self.child(
+				PathStep::Content,
+				&previous_element.content,
+				&next_element.content,
+			)
+		}
+	}
+
+	fn keyed(
+		&mut self,
+		previous_fragments: &'a [ReorderableFragment<'a, S>],
+		next_fragments: &'a [ReorderableFragment<'a, S>],
+	) {
+		let previous_by_key: BTreeMap<_, _> = previous_fragments
+			.iter()
+			.map(|fragment| (fragment.dom_key, &fragment.content))
+			.collect();
+		let next_keys: BTreeSet<_> = next_fragments
+			.iter()
+			.map(|fragment| fragment.dom_key)
+			.collect();
+
+		for fragment in previous_fragments {
+			if !next_keys.contains(&fragment.dom_key) {
+				self.emit_at(
+					PathStep::Key(fragment.dom_key),
+					Patch::Remove {
+						previous: &fragment.content,
+					},
+				)
+			}
+		}
+		if !previous_fragments
+			.iter()
+			.map(|fragment| fragment.dom_key)
+			.eq(next_fragments.iter().map(|fragment| fragment.dom_key))
+		{
+			self.work.push(Work::Emit(Patch::Reorder {
+				previous: previous_fragments,
+				next: next_fragments,
+			}))
+		}
+		for fragment in next_fragments.iter().rev() {
+			let step = PathStep::Key(fragment.dom_key);
+			match previous_by_key.get(&fragment.dom_key) {
+				Some(&previous) => self.child(step, previous, &fragment.content),
+				None => self.defer_at(
+					step,
+					Patch::Insert {
+						next: &fragment.content,
+					},
+				),
+			}
+		}
+	}
+}
+
+impl<S: ThreadSafety> Debug for Diff<'_, S> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Diff")
+			.field("complete", &self.is_complete())
+			.field("patches", &self.patches.len())
+			.finish_non_exhaustive()
+	}
+}
+
+/// Returns whether there was and will be a binding, iff it changed.
+fn bindings<T: PartialEq + Copy>(previous: Option<T>, next: Option<T>) -> Option<(bool, bool)> {
+	(previous != next).then(|| (previous.is_some(), next.is_some()))
+}
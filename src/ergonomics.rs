@@ -2,16 +2,17 @@
 #![allow(clippy::match_same_arms)]
 
 use crate::{
-	auto_safety::Align, callback_registry::CallbackSignature, CallbackRef, CallbackRegistration,
-	Element, EventBinding, EventBindingOptions, Node, ReorderableFragment, ThreadBound, ThreadSafe,
+	auto_safety::Align, callback_registry::CallbackSignature, AttributeValue, CallbackRef,
+	CallbackRegistration, Document, Element, EventBinding, EventBindingOptions, Node, Portal,
+	PropertyValue, ReorderableFragment, ShadowRoot, StyleDeclaration, ThreadBound, ThreadSafe,
 	ThreadSafety,
 };
 use core::{
 	any::type_name,
-	cmp::{self, Ordering},
-	fmt::{self, Debug, Formatter},
+	cmp::Ordering,
+	fmt::{self, Debug, Display, Formatter, Write},
 	hash::{Hash, Hasher},
-	matches,
+	iter, matches, str,
 };
 
 impl From<ThreadSafe> for ThreadBound {
@@ -124,8 +125,325 @@ where
 	}
 }
 
+impl<'a> PropertyValue<'a> {
+	/// Orders [`PropertyValue::Number`]s totally by their bit pattern, like `f64::total_cmp` would.
+	fn number_key(number: f64) -> i64 {
+		#[allow(clippy::cast_possible_wrap)]
+		let bits = number.to_bits() as i64;
+		#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+		let key = bits ^ (((bits >> 63) as u64) >> 1) as i64;
+		key
+	}
+
+	const fn discriminant(&self) -> u8 {
+		match self {
+			PropertyValue::Bool(_) => 0,
+			PropertyValue::Integer(_) => 1,
+			PropertyValue::Number(_) => 2,
+			PropertyValue::String(_) => 3,
+		}
+	}
+}
+impl<'a> PartialEq for PropertyValue<'a> {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(PropertyValue::Bool(this), PropertyValue::Bool(other)) => this == other,
+			(PropertyValue::Integer(this), PropertyValue::Integer(other)) => this == other,
+			(PropertyValue::Number(this), PropertyValue::Number(other)) => {
+				this.to_bits() == other.to_bits()
+			}
+			(PropertyValue::String(this), PropertyValue::String(other)) => this == other,
+			_ => false,
+		}
+	}
+}
+impl<'a> Eq for PropertyValue<'a> {}
+impl<'a> Hash for PropertyValue<'a> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.discriminant().hash(state);
+		match self {
+			PropertyValue::Bool(bool) => bool.hash(state),
+			PropertyValue::Integer(integer) => integer.hash(state),
+			PropertyValue::Number(number) => number.to_bits().hash(state),
+			PropertyValue::String(string) => string.hash(state),
+		}
+	}
+}
+impl<'a> PartialOrd for PropertyValue<'a> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl<'a> Ord for PropertyValue<'a> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self, other) {
+			(PropertyValue::Bool(this), PropertyValue::Bool(other)) => this.cmp(other),
+			(PropertyValue::Integer(this), PropertyValue::Integer(other)) => this.cmp(other),
+			(PropertyValue::Number(this), PropertyValue::Number(other)) => {
+				Self::number_key(*this).cmp(&Self::number_key(*other))
+			}
+			(PropertyValue::String(this), PropertyValue::String(other)) => this.cmp(other),
+			_ => self.discriminant().cmp(&other.discriminant()),
+		}
+	}
+}
+
+impl<'a> Display for AttributeValue<'a> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			AttributeValue::String(string) => f.write_str(string),
+			AttributeValue::Bool(_) => Ok(()),
+			AttributeValue::Integer(integer) => Display::fmt(integer, f),
+			AttributeValue::Float(float) if float.is_infinite() => {
+				f.write_str(if float.is_sign_positive() {
+					"Infinity"
+				} else {
+					"-Infinity"
+				})
+			}
+			#[allow(clippy::float_cmp)] // Exact.
+			AttributeValue::Float(float) if *float == 0.0 => f.write_str("0"),
+			AttributeValue::Float(float) => Display::fmt(float, f),
+			AttributeValue::Tokens(tokens) => {
+				for (i, token) in tokens.iter().filter(|token| !token.is_empty()).enumerate() {
+					if i > 0 {
+						f.write_char(' ')?;
+					}
+					f.write_str(token)?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+/// Stack buffer for rendering numeric [`AttributeValue`]s without allocation.
+///
+/// The longest [`f64`] rendering is that of `-f64::MIN_POSITIVE * f64::EPSILON` at 327 bytes.
+struct NumberBuffer {
+	bytes: [u8; 328],
+	len: usize,
+}
+impl Write for NumberBuffer {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let end = self.len + s.len();
+		self.bytes
+			.get_mut(self.len..end)
+			.ok_or(fmt::Error)?
+			.copy_from_slice(s.as_bytes());
+		self.len = end;
+		Ok(())
+	}
+}
+
+impl<'a> AttributeValue<'a> {
+	/// Calls `f` with the presence and rendered bytes of this [`AttributeValue`].
+	pub(crate) fn with_rendered<R>(
+		&self,
+		f: impl FnOnce(bool, &mut dyn Iterator<Item = u8>) -> R,
+	) -> R {
+		match self {
+			AttributeValue::String(string) => f(true, &mut string.bytes()),
+			AttributeValue::Bool(present) => f(*present, &mut iter::empty()),
+			AttributeValue::Integer(_) | AttributeValue::Float(_) => {
+				let mut buffer = NumberBuffer {
+					bytes: [0; 328],
+					len: 0,
+				};
+				write!(buffer, "{}", self).expect("Numeric attribute value too long");
+				f(true, &mut buffer.bytes[..buffer.len].iter().copied())
+			}
+			AttributeValue::Tokens(tokens) => f(
+				true,
+				&mut tokens
+					.iter()
+					.filter(|token| !token.is_empty())
+					.enumerate()
+					.flat_map(|(i, token)| {
+						iter::once(" ")
+							.take(usize::from(i > 0))
+							.chain(iter::once(*token))
+					})
+					.flat_map(str::bytes),
+			),
+		}
+	}
+}
+impl<'a> PartialEq for AttributeValue<'a> {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			// Fast path.
+			(AttributeValue::String(this), AttributeValue::String(other)) => this == other,
+			_ => self.cmp(other) == Ordering::Equal,
+		}
+	}
+}
+impl<'a> Eq for AttributeValue<'a> {}
+impl<'a> Hash for AttributeValue<'a> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.with_rendered(|present, bytes| {
+			present.hash(state);
+			// Fixed-size chunks, so that the result doesn't depend on how the value is split up internally.
+			let mut chunk = [0_u8; 32];
+			let mut len = 0;
+			for byte in bytes {
+				chunk[len] = byte;
+				len += 1;
+				if len == chunk.len() {
+					state.write(&chunk);
+					len = 0;
+				}
+			}
+			state.write(&chunk[..len]);
+			state.write_u8(0xff);
+		})
+	}
+}
+impl<'a> PartialOrd for AttributeValue<'a> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl<'a> Ord for AttributeValue<'a> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.with_rendered(|this_present, this| {
+			other.with_rendered(|other_present, other| {
+				this_present
+					.cmp(&other_present)
+					.then_with(|| this.cmp(other))
+			})
+		})
+	}
+}
+
+impl<'a> From<&'a str> for AttributeValue<'a> {
+	fn from(string: &'a str) -> Self {
+		Self::String(string)
+	}
+}
+impl<'a> From<bool> for AttributeValue<'a> {
+	fn from(present: bool) -> Self {
+		Self::Bool(present)
+	}
+}
+impl<'a> From<i64> for AttributeValue<'a> {
+	fn from(integer: i64) -> Self {
+		Self::Integer(integer)
+	}
+}
+impl<'a> From<i32> for AttributeValue<'a> {
+	fn from(integer: i32) -> Self {
+		Self::Integer(integer.into())
+	}
+}
+impl<'a> From<f64> for AttributeValue<'a> {
+	fn from(float: f64) -> Self {
+		Self::Float(float)
+	}
+}
+impl<'a> From<&'a [&'a str]> for AttributeValue<'a> {
+	fn from(tokens: &'a [&'a str]) -> Self {
+		Self::Tokens(tokens)
+	}
+}
+
+impl<'a> Display for StyleDeclaration<'a> {
+	/// Formats this [`StyleDeclaration`] as in a CSS declaration block, without trailing `;`.
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}", self.property, self.value)?;
+		if self.important {
+			f.write_str(" !important")?;
+		}
+		Ok(())
+	}
+}
+
+/// Order-independent comparisons and hashing of slices, treating them as multisets.
+///
+/// These don't allocate, but are quadratic in the slice length, which is fine for class lists and inline styles.
+pub(crate) mod unordered {
+	use core::{
+		cmp::Ordering,
+		hash::{Hash, Hasher},
+	};
+
+	pub fn eq<T: Ord>(a: &[T], b: &[T]) -> bool {
+		a.len() == b.len() && cmp(a, b) == Ordering::Equal
+	}
+
+	/// Compares `a` and `b` as if both were sorted first.
+	pub fn cmp<T: Ord>(a: &[T], b: &[T]) -> Ordering {
+		/// The smallest item greater than `previous` and how often it occurs.
+		fn next<'a, T: Ord>(slice: &'a [T], previous: Option<&T>) -> Option<(&'a T, usize)> {
+			slice
+				.iter()
+				.filter(|item| previous.map_or(true, |previous| *item > previous))
+				.min()
+				.map(|min| (min, slice.iter().filter(|item| *item == min).count()))
+		}
+
+		let mut previous = None;
+		loop {
+			match (next(a, previous), next(b, previous)) {
+				(None, None) => return Ordering::Equal,
+				(None, Some(_)) => return Ordering::Less,
+				(Some(_), None) => return Ordering::Greater,
+				(Some((a_min, a_count)), Some((b_min, b_count))) => {
+					if a_min != b_min {
+						return a_min.cmp(b_min);
+					}
+					if a_count != b_count {
+						// The sorted slice with fewer copies continues with a greater item or ends first.
+						let (fewer, ordering) = if a_count < b_count {
+							(a, Ordering::Greater)
+						} else {
+							(b, Ordering::Less)
+						};
+						return if next(fewer, Some(a_min)).is_some() {
+							ordering
+						} else {
+							ordering.reverse()
+						};
+					}
+					previous = Some(a_min);
+				}
+			}
+		}
+	}
+
+	pub fn hash<T: Hash, H: Hasher>(slice: &[T], state: &mut H) {
+		let sum = slice.iter().fold(0_u64, |sum, item| {
+			let mut hasher = Fnv1a::default();
+			item.hash(&mut hasher);
+			sum.wrapping_add(hasher.finish())
+		});
+		state.write_usize(slice.len());
+		state.write_u64(sum);
+	}
+
+	/// A small deterministic [`Hasher`] for the individual items.
+	struct Fnv1a(u64);
+	impl Default for Fnv1a {
+		fn default() -> Self {
+			Self(0xcbf2_9ce4_8422_2325)
+		}
+	}
+	impl Hasher for Fnv1a {
+		fn finish(&self) -> u64 {
+			self.0
+		}
+
+		fn write(&mut self, bytes: &[u8]) {
+			for byte in bytes {
+				self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+			}
+		}
+	}
+}
+
 macro_rules! vdom_ergonomics {
 	([$(
+		$(#[$impls:meta])*
 		$VdomName:ident {
 			debug: |&$debug_self:ident, $debug_f:ident| $debug:expr,
 			partial_eq: |&$eq_self:ident, $eq_other:ident| $partial_eq:expr,
@@ -141,6 +459,7 @@ macro_rules! vdom_ergonomics {
 			}
 		}
 
+		$(#[$impls])*
 		impl<'a, S> Debug for $VdomName<'a, S> where
 			S: ThreadSafety,
 		{
@@ -161,6 +480,7 @@ macro_rules! vdom_ergonomics {
 			S: ThreadSafety,
 		{}
 
+		$(#[$impls])*
 		impl<'a, S1, S2> PartialEq<$VdomName<'a, S2>> for $VdomName<'a, S1> where
 			S1: ThreadSafety,
 			S2: ThreadSafety,
@@ -173,6 +493,7 @@ macro_rules! vdom_ergonomics {
 			S: ThreadSafety,
 		{}
 
+		$(#[$impls])*
 		impl<'a, S> Hash for $VdomName<'a, S> where
 			S: ThreadSafety,
 		{
@@ -191,6 +512,7 @@ macro_rules! vdom_ergonomics {
 				Some(Ord::cmp(self.align_ref(), other.align_ref()))
 			}
 		}
+		$(#[$impls])*
 		impl<'a, S> Ord for $VdomName<'a, S>
 		where
 			S: ThreadSafety,
@@ -212,36 +534,86 @@ macro_rules! cmp {
 }
 
 vdom_ergonomics!([
+	#[cfg(not(feature = "alloc"))] // See `iterative`.
+	Document {
+		debug: |&self, f| f
+			.debug_struct("Document")
+			.field("doctype", &self.doctype)
+			.field("html_attributes", &self.html_attributes)
+			.field("head", &self.head) // Recursion.
+			.field("body", &self.body) // Recursion.
+			.finish(),
+		partial_eq: |&self, other| self.doctype == other.doctype
+			&& self.html_attributes == other.html_attributes
+			&& self.head == other.head // Recursion.
+			&& self.body == other.body, // Recursion.
+		hash: |&self, state| {
+			self.doctype.hash(state);
+			self.html_attributes.hash(state);
+			self.head.hash(state); // Recursion.
+			self.body.hash(state); // Recursion.
+		},
+		cmp: |&self, other| {
+			cmp!(self.doctype, other.doctype);
+			cmp!(self.html_attributes, other.html_attributes);
+			cmp!(&self.head, &other.head); // Recursion.
+			self.body.cmp(&other.body) // Recursion.
+		},
+	},
+	#[cfg(not(feature = "alloc"))] // See `iterative`.
 	Element {
 		debug: |&self, f| f
 			.debug_struct("Element")
 			.field("name", &self.name)
 			.field("creation_options", &self.creation_options)
 			.field("attributes", &self.attributes)
+			.field("properties", &self.properties)
+			.field("classes", &self.classes)
+			.field("styles", &self.styles)
 			.field("event_bindings", &self.event_bindings)
 			.field("content", &self.content) // Recursion.
+			.field("shadow_root", &self.shadow_root) // Recursion.
 			.finish(),
 		partial_eq: |&self, other| self.name == other.name
 			&& self.creation_options == other.creation_options
 			&& self.attributes == other.attributes
+			&& self.properties == other.properties
+			&& unordered::eq(self.classes, other.classes)
+			&& unordered::eq(self.styles, other.styles)
 			&& self.event_bindings == other.event_bindings
-			&& self.content == other.content, // Recursion.
+			&& self.content == other.content // Recursion.
+			&& match (self.shadow_root, other.shadow_root) {
+				(None, None) => true,
+				(Some(sr_1), Some(sr_2)) => sr_1 == sr_2, // Recursion.
+				(_, _) => false,
+			},
 		hash: |&self, state| {
 			self.name.hash(state);
 			self.creation_options.hash(state);
 			self.attributes.hash(state);
+			self.properties.hash(state);
+			unordered::hash(self.classes, state);
+			unordered::hash(self.styles, state);
 			self.event_bindings.hash(state);
 			self.content.hash(state); // Recursion.
+			self.shadow_root.hash(state); // Recursion.
 		},
 		cmp: |&self, other| {
 			cmp!(self.name, other.name);
 			cmp!(&self.creation_options, &other.creation_options);
 			cmp!(self.attributes, other.attributes);
-			for i in 0..cmp::min(self.event_bindings.len(), other.event_bindings.len()) {
+			cmp!(self.properties, other.properties);
+			cmp!(
+				&unordered::cmp(self.classes, other.classes),
+				&Ordering::Equal
+			);
+			cmp!(&unordered::cmp(self.styles, other.styles), &Ordering::Equal);
+			for i in 0..core::cmp::min(self.event_bindings.len(), other.event_bindings.len()) {
 				cmp!(&self.event_bindings[i], &other.event_bindings[i]);
 			}
 			cmp!(&self.event_bindings.len(), &other.event_bindings.len());
-			self.content.cmp(&other.content) // Recursion.
+			cmp!(&self.content, &other.content); // Recursion.
+			self.shadow_root.cmp(&other.shadow_root) // Recursion.
 		},
 	},
 	EventBinding {
@@ -265,6 +637,7 @@ vdom_ergonomics!([
 			self.options.cmp(&other.options)
 		},
 	},
+	#[cfg(not(feature = "alloc"))] // See `iterative`.
 	Node {
 		debug: |&self, f| match self {
 			Node::Comment {
@@ -311,6 +684,16 @@ vdom_ergonomics!([
 				.field("text", text)
 				.field("dom_binding", dom_binding)
 				.finish(),
+			Node::RawHtml(html) => f.debug_tuple("Node::RawHtml").field(html).finish(),
+			Node::Portal(portal) => f.debug_tuple("Node::Portal").field(portal).finish(), // Recursion.
+			Node::HeadContribution {
+				element,
+				dom_binding,
+			} => f
+				.debug_struct("Node::HeadContribution")
+				.field("element", element)
+				.field("dom_binding", dom_binding)
+				.finish(),
 			Node::RemnantSite(remnant_site) => f
 				.debug_tuple("Node::RemnantSite")
 				.field(remnant_site)
@@ -415,6 +798,27 @@ vdom_ergonomics!([
 						(_, _) => false,
 					},
 			(Node::Text { .. }, _) => false,
+			(Node::RawHtml(h_1), Node::RawHtml(h_2)) => h_1 == h_2,
+			(Node::RawHtml(_), _) => false,
+			(Node::Portal(p_1), Node::Portal(p_2)) => p_1 == p_2, // Recursion.
+			(Node::Portal(_), _) => false,
+			(
+				Node::HeadContribution {
+					element: e_1,
+					dom_binding: db_1,
+				},
+				Node::HeadContribution {
+					element: e_2,
+					dom_binding: db_2,
+				},
+			) =>
+				e_1 == e_2
+					&& match (db_1, db_2) {
+						(None, None) => true,
+						(Some(db_1), Some(db_2)) => db_1 == db_2,
+						(_, _) => false,
+					},
+			(Node::HeadContribution { .. }, _) => false,
 			(Node::RemnantSite(rs_1), Node::RemnantSite(rs_2)) => rs_1 == rs_2, // Recursion.
 			(Node::RemnantSite(_), _) => false,
 		},
@@ -456,6 +860,15 @@ vdom_ergonomics!([
 				text.hash(state);
 				dom_binding.hash(state)
 			}
+			Node::RawHtml(html) => html.hash(state),
+			Node::Portal(portal) => portal.hash(state), // Recursion.
+			Node::HeadContribution {
+				element,
+				dom_binding,
+			} => {
+				dom_binding.hash(state);
+				element.hash(state); // Recursion.
+			}
 			Node::RemnantSite(remnant_site) => remnant_site.hash(state), // Recursion (eventually).
 		},
 		cmp: |&self, other| match (self, other) {
@@ -543,6 +956,21 @@ vdom_ergonomics!([
 				cmp!(t_1, t_2);
 				db_1.cmp(db_2)
 			}
+			(Node::RawHtml(h_1), Node::RawHtml(h_2)) => h_1.cmp(h_2),
+			(Node::Portal(p_1), Node::Portal(p_2)) => p_1.cmp(p_2), // Recursion.
+			(
+				Node::HeadContribution {
+					element: e_1,
+					dom_binding: db_1,
+				},
+				Node::HeadContribution {
+					element: e_2,
+					dom_binding: db_2,
+				},
+			) => {
+				cmp!(db_1, db_2);
+				e_1.cmp(e_2) // Recursion.
+			}
 			(Node::RemnantSite(rs_1), Node::RemnantSite(rs_2)) => {
 				rs_1.cmp(rs_2)
 			}
@@ -562,8 +990,15 @@ vdom_ergonomics!([
 			(_, Node::Keyed(_)) => Ordering::Greater,
 			(Node::Text { .. }, _) => Ordering::Less,
 			(_, Node::Text { .. }) => Ordering::Greater,
+			(Node::RawHtml(_), _) => Ordering::Less,
+			(_, Node::RawHtml(_)) => Ordering::Greater,
+			(Node::Portal(_), _) => Ordering::Less,
+			(_, Node::Portal(_)) => Ordering::Greater,
+			(Node::HeadContribution { .. }, _) => Ordering::Less,
+			(_, Node::HeadContribution { .. }) => Ordering::Greater,
 		},
 	},
+	#[cfg(not(feature = "alloc"))] // See `iterative`.
 	ReorderableFragment {
 		debug: |&self, f| f
 			.debug_struct("ReorderableFragment")
@@ -579,6 +1014,56 @@ vdom_ergonomics!([
 			cmp!(&self.dom_key, &other.dom_key);
 			self.content.cmp(&other.content) // Recursion.
 		},
+	},
+	#[cfg(not(feature = "alloc"))] // See `iterative`.
+	Portal {
+		debug: |&self, f| f
+			.debug_struct("Portal")
+			.field("target", &self.target)
+			.field("dom_binding", &self.dom_binding)
+			.field("content", &self.content) // Recursion.
+			.finish(),
+		partial_eq: |&self, other| self.target == other.target
+			&& match (self.dom_binding, other.dom_binding) {
+				(None, None) => true,
+				(Some(db_1), Some(db_2)) => db_1 == db_2,
+				(_, _) => false,
+			} && self.content == other.content, // Recursion.
+		hash: |&self, state| {
+			self.target.hash(state);
+			self.dom_binding.hash(state);
+			self.content.hash(state); // Recursion.
+		},
+		cmp: |&self, other| {
+			cmp!(self.target, other.target);
+			cmp!(&self.dom_binding, &other.dom_binding);
+			self.content.cmp(&other.content) // Recursion.
+		},
+	},
+	#[cfg(not(feature = "alloc"))] // See `iterative`.
+	ShadowRoot {
+		debug: |&self, f| f
+			.debug_struct("ShadowRoot")
+			.field("options", &self.options)
+			.field("dom_binding", &self.dom_binding)
+			.field("content", &self.content) // Recursion.
+			.finish(),
+		partial_eq: |&self, other| self.options == other.options
+			&& match (self.dom_binding, other.dom_binding) {
+				(None, None) => true,
+				(Some(db_1), Some(db_2)) => db_1 == db_2,
+				(_, _) => false,
+			} && self.content == other.content, // Recursion.
+		hash: |&self, state| {
+			self.options.hash(state);
+			self.dom_binding.hash(state);
+			self.content.hash(state); // Recursion.
+		},
+		cmp: |&self, other| {
+			cmp!(&self.options, &other.options);
+			cmp!(&self.dom_binding, &other.dom_binding);
+			self.content.cmp(&other.content) // Recursion.
+		},
 	}
 ]);
 
@@ -604,7 +1089,11 @@ where
 	///   name: "DIV",
 	///   creation_options: ElementCreationOptions::new(),
 	///   attributes: &[],
+	///   properties: &[],
+	///   classes: &[],
+	///   styles: &[],
 	///   content: Node::Multi(&[]),
+	///   shadow_root: None,
 	///   event_bindings: &[],
 	/// }).as_html();
 	/// ```
@@ -632,7 +1121,11 @@ where
 	///   name: "SVG",
 	///   creation_options: ElementCreationOptions::new(),
 	///   attributes: &[],
+	///   properties: &[],
+	///   classes: &[],
+	///   styles: &[],
 	///   content: Node::Multi(&[]),
+	///   shadow_root: None,
 	///   event_bindings: &[],
 	/// }).as_svg();
 	/// ```
@@ -693,43 +1186,59 @@ impl<'a, S: ThreadSafety> Node<'a, S> {
 	/// Calculates the aggregate surface level length of this [`Node`] in [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node)s.
 	///
 	/// This operation is recursive across *for example* [`Node::Multi`] and [`Node::Keyed`], which sum up their contents in this regard.
+	///
+	/// [`Element::shadow_root`]s are separate DOM trees and don't count towards this.  
+	/// [`Node::RawHtml`] is opaque and [`Node::Portal`] and [`Node::HeadContribution`] render elsewhere, so these count as zero [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node)s.
 	#[must_use]
-	#[allow(clippy::missing_panics_doc)] // todo!
 	pub fn dom_len(&self) -> usize {
-		match self {
-			Node::Comment { .. }
-			| Node::HtmlElement { .. }
-			| Node::MathMlElement { .. }
-			| Node::SvgElement { .. }
-			| Node::Text { .. } => 1,
-			Node::Memoized { content: node, .. } => node.dom_len(),
-			Node::Multi(nodes) => nodes.iter().map(Node::dom_len).sum(),
-			Node::Keyed(pairs) => pairs.iter().map(|pair| pair.content.dom_len()).sum(),
-			Node::RemnantSite(_) => {
-				todo!("RemnantSite dom_len")
-			}
-		}
+		dom_len(self)
 	}
 
 	/// Determines whether this [`Node`] represents no [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node)s at all.
 	///
 	/// This operation is recursive across *for example* [`Node::Multi`] and [`Node::Keyed`], which sum up their contents in this regard.
 	#[must_use]
-	#[allow(clippy::missing_panics_doc)] // todo!
 	pub fn dom_empty(&self) -> bool {
-		match self {
-			Node::Comment { .. }
-			| Node::HtmlElement { .. }
-			| Node::MathMlElement { .. }
-			| Node::SvgElement { .. }
-			| Node::Text { .. } => false,
-			Node::Memoized { content, .. } => content.dom_empty(),
-			Node::Multi(nodes) => nodes.iter().all(Node::dom_empty),
-			Node::Keyed(pairs) => pairs.iter().all(|pair| pair.content.dom_empty()),
-			Node::RemnantSite(_) => {
-				todo!("RemnantSite dom_empty")
-			}
-		}
+		dom_empty(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+use crate::iterative::{dom_empty, dom_len};
+
+#[cfg(not(feature = "alloc"))] // See `iterative`.
+fn dom_len<S: ThreadSafety>(node: &Node<'_, S>) -> usize {
+	match node {
+		Node::Comment { .. }
+		| Node::HtmlElement { .. }
+		| Node::MathMlElement { .. }
+		| Node::SvgElement { .. }
+		| Node::Text { .. } => 1,
+		Node::Memoized { content: node, .. } => node.dom_len(),
+		Node::Multi(nodes) => nodes.iter().map(Node::dom_len).sum(),
+		Node::Keyed(pairs) => pairs.iter().map(|pair| pair.content.dom_len()).sum(),
+		Node::RawHtml(_)
+		| Node::Portal(_)
+		| Node::HeadContribution { .. }
+		| Node::RemnantSite(_) => 0,
+	}
+}
+
+#[cfg(not(feature = "alloc"))] // See `iterative`.
+fn dom_empty<S: ThreadSafety>(node: &Node<'_, S>) -> bool {
+	match node {
+		Node::Comment { .. }
+		| Node::HtmlElement { .. }
+		| Node::MathMlElement { .. }
+		| Node::SvgElement { .. }
+		| Node::Text { .. } => false,
+		Node::Memoized { content, .. } => content.dom_empty(),
+		Node::Multi(nodes) => nodes.iter().all(Node::dom_empty),
+		Node::Keyed(pairs) => pairs.iter().all(|pair| pair.content.dom_empty()),
+		Node::RawHtml(_)
+		| Node::Portal(_)
+		| Node::HeadContribution { .. }
+		| Node::RemnantSite(_) => true,
 	}
 }
 
@@ -0,0 +1,98 @@
+//! A typed catalogue of common DOM events.
+//!
+//! Each type in this module pairs a DOM event name with the [`web`] stand-in its handlers receive.
+//! Use them with [`EventBinding::typed`] to avoid typos in event names and manual downcasts in handlers.
+//!
+//! Like everything else related to callbacks, this module erases cleanly without the `"callbacks"` feature:
+//! The event names remain available, but the stand-ins become [uninhabited](https://doc.rust-lang.org/nomicon/exotic-sizes.html#empty-types).
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{events, CallbackRegistration, EventBinding, EventBindingOptions, EventOutcome, ThreadSafe, web};
+//!
+//! struct Button;
+//! let button = Box::pin(Button);
+//! let registration = CallbackRegistration::<_, fn(web::MouseEvent) -> EventOutcome>::new(
+//!   button.as_ref(),
+//!   |_button, _event| EventOutcome::new(),
+//! );
+//!
+//! let binding = EventBinding::<ThreadSafe>::typed(
+//!   events::Click,
+//!   registration.to_ref(),
+//!   EventBindingOptions::new(),
+//! );
+//! assert_eq!(binding.name, "click");
+//! ```
+
+#[allow(unused_imports)] // Largely for documentation.
+use crate::{web, EventBinding};
+
+/// Implemented by the event types in this [module](self).
+///
+/// This trait is not sealed, so you can extend the catalogue with further events whose type is one of the [`web::EventStandIn`]s.
+pub trait EventType: Sized {
+	/// The DOM event name, as passed to [***EventTarget.addEventListener()***](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener).
+	const NAME: &'static str;
+
+	/// The [`web`] stand-in handlers of this event receive.
+	type StandIn: web::EventStandIn;
+}
+
+macro_rules! events {
+	{$(
+		$(#[$($attrs:tt)*])*
+		$event:ident($name:literal) => $stand_in:ident
+	),*$(,)?} => {$(
+		$(#[$($attrs)*])*
+		///
+		/// Handlers receive a [`web::
+		#[doc = stringify!($stand_in)]
+		/// `].
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+		pub struct $event;
+		impl EventType for $event {
+			const NAME: &'static str = $name;
+			type StandIn = web::$stand_in;
+		}
+	)*};
+}
+
+events! {
+	/// The [***blur***](https://developer.mozilla.org/en-US/docs/Web/API/Element/blur_event) event.
+	Blur("blur") => FocusEvent,
+
+	/// The [***change***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/change_event) event.
+	Change("change") => Event,
+
+	/// The [***click***](https://developer.mozilla.org/en-US/docs/Web/API/Element/click_event) event.
+	Click("click") => MouseEvent,
+
+	/// The [***dblclick***](https://developer.mozilla.org/en-US/docs/Web/API/Element/dblclick_event) event.
+	DblClick("dblclick") => MouseEvent,
+
+	/// The [***focus***](https://developer.mozilla.org/en-US/docs/Web/API/Element/focus_event) event.
+	Focus("focus") => FocusEvent,
+
+	/// The [***input***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/input_event) event.
+	Input("input") => InputEvent,
+
+	/// The [***keydown***](https://developer.mozilla.org/en-US/docs/Web/API/Element/keydown_event) event.
+	KeyDown("keydown") => KeyboardEvent,
+
+	/// The [***keyup***](https://developer.mozilla.org/en-US/docs/Web/API/Element/keyup_event) event.
+	KeyUp("keyup") => KeyboardEvent,
+
+	/// The [***mousedown***](https://developer.mozilla.org/en-US/docs/Web/API/Element/mousedown_event) event.
+	MouseDown("mousedown") => MouseEvent,
+
+	/// The [***mousemove***](https://developer.mozilla.org/en-US/docs/Web/API/Element/mousemove_event) event.
+	MouseMove("mousemove") => MouseEvent,
+
+	/// The [***mouseup***](https://developer.mozilla.org/en-US/docs/Web/API/Element/mouseup_event) event.
+	MouseUp("mouseup") => MouseEvent,
+
+	/// The [***submit***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLFormElement/submit_event) event.
+	Submit("submit") => Event,
+}
@@ -0,0 +1,242 @@
+//! Double-buffered arenas that keep the previous VDOM iteration alive for diffing. Requires the `"bumpalo"` feature.
+//!
+//! Diffing needs the previous and next VDOM graph at the same time, so apps usually alternate between two [`Bump`]s.
+//! [`Frames`] encapsulates this, including the lifetime extension that's otherwise needed to store the previous graph.
+//!
+//! [`Frame`] also implements [`MemoContext`], so [`memo`](`crate::memo::memo`) reuses subtrees rendered during the previous frame.
+//! These are copied into the current arena with [`Node::copy_into`], as the previous arena is reset once it's two frames old.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{arena::Arena, frames::Frames, memo::memo, Node, ThreadSafe};
+//!
+//! let mut frames = Frames::<ThreadSafe>::new();
+//! for count in 0..3_u32 {
+//!   frames.render(|frame| {
+//!     memo(frame, &count, |frame| Node::Text {
+//!       text: frame.alloc_str(&count.to_string()),
+//!       dom_binding: None,
+//!     })
+//!   });
+//!
+//!   // Diff `frames.previous()` into `frames.current()` here.
+//!   assert_eq!(frames.previous().is_some(), count > 0);
+//! }
+//! ```
+
+use crate::{arena::Arena, memo::MemoContext, Node, ThreadSafety};
+use bumpalo::Bump;
+use core::{
+	cell::Cell,
+	fmt::{self, Debug, Formatter},
+	mem,
+};
+
+/// Owns two [`Bump`] arenas and alternates between them to render successive VDOM iterations.
+///
+/// After each [`Frames::render`], both the just-rendered graph ([`Frames::current`])
+/// and the one before it ([`Frames::previous`]) are available for diffing.
+pub struct Frames<S: ThreadSafety + 'static> {
+	arenas: [Bump; 2],
+	/// The index into `arenas` that `current` and `memos` are allocated in.
+	/// `previous` is allocated in the other one.
+	index: usize,
+	// These `'static`s are extended from the respective arena borrows and must never escape this module.
+	// Each arena is reset only after all references into it have been cleared.
+	previous: Option<Node<'static, S>>,
+	current: Option<Node<'static, S>>,
+	memos: Option<&'static MemoRecord<'static, S>>,
+}
+
+impl<S: ThreadSafety + 'static> Frames<S> {
+	/// Creates a new [`Frames`] instance without any rendered VDOM.
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			arenas: [Bump::new(), Bump::new()],
+			index: 0,
+			previous: None,
+			current: None,
+			memos: None,
+		}
+	}
+
+	/// Renders a new VDOM iteration, which then becomes [`Frames::current`].
+	///
+	/// The graph that was current until now becomes [`Frames::previous`], and the one before it is dropped.
+	///
+	/// `render` must allocate all non-`'static` data it places into the VDOM in the given [`Frame`].
+	pub fn render(&mut self, render: impl for<'f> FnOnce(&'f Frame<'f, S>) -> Node<'f, S>) {
+		let next = 1 - self.index;
+
+		// Clear the only references into the oldest arena before reusing it.
+		self.previous = None;
+		self.arenas[next].reset();
+
+		let (current, memos) = {
+			let frame = Frame {
+				bump: &self.arenas[next],
+				previous: self.memos,
+				records: Cell::new(None),
+			};
+			let current = render(&frame);
+			let memos = frame.records.get();
+			unsafe {
+				// SAFETY:
+				// Everything `render` returns is either `'static` or allocated in `self.arenas[next]`,
+				// as the `Frame` only allocates there and copies memoized content out of the other arena.
+				// That arena isn't reset or dropped before these are cleared (or `self` is dropped, as they have no drop glue).
+				(
+					mem::transmute::<Node<'_, S>, Node<'static, S>>(current),
+					mem::transmute::<
+						Option<&MemoRecord<'_, S>>,
+						Option<&'static MemoRecord<'static, S>>,
+					>(memos),
+				)
+			}
+		};
+
+		self.previous = self.current.take();
+		self.current = Some(current);
+		self.memos = memos;
+		self.index = next;
+	}
+
+	/// Retrieves the VDOM graph rendered by the latest call to [`Frames::render`], if any.
+	#[must_use]
+	pub fn current(&self) -> Option<&Node<'_, S>> {
+		self.current.as_ref()
+	}
+
+	/// Retrieves the VDOM graph rendered by the call to [`Frames::render`] before the latest, if any.
+	#[must_use]
+	pub fn previous(&self) -> Option<&Node<'_, S>> {
+		self.previous.as_ref()
+	}
+}
+
+impl<S: ThreadSafety + 'static> Default for Frames<S> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<S: ThreadSafety + 'static> Debug for Frames<S> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Frames")
+			.field("previous", &self.previous())
+			.field("current", &self.current())
+			.finish_non_exhaustive()
+	}
+}
+
+/// The allocator for a single VDOM iteration, as handed out by [`Frames::render`].
+///
+/// [`MemoContext::recall`] first looks for content memoized during this frame,
+/// then copies content memoized during the previous frame into this one.
+/// Lookups are linear in the number of memoized subtrees per frame.
+pub struct Frame<'f, S: ThreadSafety> {
+	bump: &'f Bump,
+	previous: Option<&'f MemoRecord<'f, S>>,
+	records: Cell<Option<&'f MemoRecord<'f, S>>>,
+}
+
+impl<'f, S: ThreadSafety> Frame<'f, S> {
+	/// Retrieves the underlying [`Bump`] arena.
+	#[must_use]
+	pub fn bump(&self) -> &'f Bump {
+		self.bump
+	}
+
+	/// Records all [`Node::Memoized`] within `node`, so that they remain available during the next frame.
+	fn record_nested(&'f self, node: &'f Node<'f, S>) {
+		match node {
+			Node::HtmlElement { element, .. }
+			| Node::MathMlElement { element, .. }
+			| Node::SvgElement { element, .. }
+			| Node::HeadContribution { element, .. } => {
+				if let Some(shadow_root) = element.shadow_root {
+					self.record_nested(&shadow_root.content)
+				}
+				self.record_nested(&element.content)
+			}
+			Node::Memoized { state_key, content } => {
+				self.record(*state_key, content);
+				self.record_nested(content)
+			}
+			Node::Multi(nodes) => nodes.iter().for_each(|node| self.record_nested(node)),
+			Node::Keyed(pairs) => pairs
+				.iter()
+				.for_each(|pair| self.record_nested(&pair.content)),
+			Node::Portal(portal) => self.record_nested(&portal.content),
+			Node::Comment { .. } | Node::Text { .. } | Node::RawHtml(_) | Node::RemnantSite(_) => {}
+		}
+	}
+}
+
+impl<'f, S: ThreadSafety> Debug for Frame<'f, S> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Frame")
+			.field("bump", &self.bump)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<'f, S: ThreadSafety> Arena for Frame<'f, S> {
+	fn alloc<T: Copy>(&self, value: T) -> &T {
+		self.bump.alloc(value)
+	}
+
+	fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T] {
+		self.bump.alloc_slice_copy(slice)
+	}
+
+	fn alloc_str(&self, string: &str) -> &str {
+		self.bump.alloc_str(string)
+	}
+
+	fn alloc_slice_fill_with<T: Copy>(&self, len: usize, f: impl FnMut(usize) -> T) -> &[T] {
+		self.bump.alloc_slice_fill_with(len, f)
+	}
+}
+
+impl<'f, S: ThreadSafety> MemoContext<'f, S> for Frame<'f, S> {
+	fn recall(&'f self, state_key: u64) -> Option<&'f Node<'f, S>> {
+		if let Some(content) = MemoRecord::find(self.records.get(), state_key) {
+			return Some(content);
+		}
+		let content = MemoRecord::find(self.previous, state_key)?;
+		let content: &'f Node<'f, S> = self.bump.alloc(content.copy_into(self.bump));
+		self.record_nested(content);
+		Some(content)
+	}
+
+	fn record(&'f self, state_key: u64, content: &'f Node<'f, S>) {
+		let next = self.records.get();
+		self.records.set(Some(self.bump.alloc(MemoRecord {
+			state_key,
+			content,
+			next,
+		})));
+	}
+}
+
+/// A singly linked list of memoized content, allocated in the same arena as that content.
+struct MemoRecord<'f, S: ThreadSafety> {
+	state_key: u64,
+	content: &'f Node<'f, S>,
+	next: Option<&'f MemoRecord<'f, S>>,
+}
+
+impl<'f, S: ThreadSafety> MemoRecord<'f, S> {
+	fn find(mut records: Option<&'f Self>, state_key: u64) -> Option<&'f Node<'f, S>> {
+		while let Some(record) = records {
+			if record.state_key == state_key {
+				return Some(record.content);
+			}
+			records = record.next;
+		}
+		None
+	}
+}
@@ -0,0 +1,228 @@
+//! Collection and deduplication of [`Node::HeadContribution`]s.
+//!
+//! Components anywhere in a VDOM graph can contribute `<title>`, `<meta>` and other metadata elements to the document's
+//! [***head***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/head) by placing a [`Node::HeadContribution`] among their content.
+//!
+//! Both HTML and VDOM-to-DOM renderers use [`for_each_contribution`] to find the elements to render, so that they agree on deduplication.
+//! Like the rest of this crate, the functions here don't allocate unless the `"alloc"` feature is enabled,
+//! which makes [`for_each_contribution`] run in linear rather than quadratic time.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{head, Attribute, AttributeValue, Element, ElementCreationOptions, Node, ThreadSafe};
+//!
+//! const fn meta<'a>(attributes: &'a [Attribute<'a>]) -> Element<'a, ThreadSafe> {
+//!   Element {
+//!     name: "META",
+//!     creation_options: ElementCreationOptions::new(),
+//!     attributes,
+//!     properties: &[],
+//!     classes: &[],
+//!     styles: &[],
+//!     content: Node::Multi(&[]),
+//!     shadow_root: None,
+//!     event_bindings: &[],
+//!   }
+//! }
+//!
+//! let old_attributes = [
+//!   Attribute { name: "name", value: AttributeValue::String("description") },
+//!   Attribute { name: "content", value: AttributeValue::String("Outer") },
+//! ];
+//! let new_attributes = [
+//!   Attribute { name: "name", value: AttributeValue::String("description") },
+//!   Attribute { name: "content", value: AttributeValue::String("Inner") },
+//! ];
+//! let old = meta(&old_attributes);
+//! let new = meta(&new_attributes);
+//! let nodes = [
+//!   Node::HeadContribution { element: &old, dom_binding: None },
+//!   Node::HeadContribution { element: &new, dom_binding: None },
+//! ];
+//!
+//! let mut count = 0;
+//! head::for_each_contribution::<_, ()>(&[&Node::Multi(&nodes)], |element, _| {
+//!   assert_eq!(element, &new);
+//!   count += 1;
+//!   Ok(())
+//! }).unwrap();
+//! assert_eq!(count, 1);
+//! ```
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use crate::{web, AttributeValue, CallbackRef, DomRef, Element, Node, ThreadSafety};
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+use core::convert::Infallible;
+
+/// The deduplication key of a head contribution, as determined by [`HeadKey::of`].
+///
+/// Of [`Node::HeadContribution`]s with equal keys, only the last one in VDOM order is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HeadKey<'a> {
+	/// A [`<title>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/title) element.
+	Title,
+	/// A [`<base>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/base) element.
+	Base,
+	/// A [`<meta charset>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-charset) element.
+	Charset,
+	/// A [`<meta>`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta) element with a string-valued `name`, `property`, `http-equiv` or `itemprop` attribute.
+	Meta {
+		/// The lowercase name of the identifying attribute, for example `"property"`.
+		attribute: &'static str,
+		/// The identifying attribute's value, for example `"og:title"`.
+		///
+		/// This is compared case-sensitively.
+		value: &'a str,
+	},
+}
+
+impl<'a> HeadKey<'a> {
+	/// Determines the [`HeadKey`] of `element`, if any.
+	///
+	/// Element and attribute names are compared ASCII-case-insensitively.
+	/// Other contributions, including `<link>` and `<script>` elements, have no key and are never deduplicated.
+	#[must_use]
+	pub fn of<S: ThreadSafety>(element: &Element<'a, S>) -> Option<Self> {
+		let name = element.name;
+		if name.eq_ignore_ascii_case("title") {
+			Some(Self::Title)
+		} else if name.eq_ignore_ascii_case("base") {
+			Some(Self::Base)
+		} else if name.eq_ignore_ascii_case("meta") {
+			let present = |attribute: &str| {
+				element.attributes.iter().find(|candidate| {
+					candidate.name.eq_ignore_ascii_case(attribute) && candidate.value.is_present()
+				})
+			};
+			if present("charset").is_some() {
+				return Some(Self::Charset);
+			}
+			["name", "property", "http-equiv", "itemprop"]
+				.iter()
+				.find_map(|&attribute| match present(attribute)?.value {
+					AttributeValue::String(value) => Some(Self::Meta { attribute, value }),
+					_ => None,
+				})
+		} else {
+			None
+		}
+	}
+}
+
+/// Calls `f` with each [`Node::HeadContribution`]'s element and `dom_binding` in `roots`, in VDOM order, skipping superseded ones.
+///
+/// A contribution is superseded iff a later one, across all of `roots`, has an equal [`HeadKey`].
+///
+/// Contributions are found in [`Element::content`], [`Element::shadow_root`]s and [`Portal`](`crate::Portal`) content,
+/// but not inside other contributions.
+///
+/// # Errors
+///
+/// Iff `f` returns an error, which stops the iteration.
+pub fn for_each_contribution<'a, S: ThreadSafety, E>(
+	roots: &[&Node<'a, S>],
+	mut f: impl FnMut(
+		&'a Element<'a, S>,
+		Option<CallbackRef<S, fn(dom_ref: DomRef<&'_ web::HtmlElement>)>>,
+	) -> Result<(), E>,
+) -> Result<(), E> {
+	let is_superseded = supersession(roots);
+	let mut ordinal = 0_usize;
+	visit_all(roots, &mut |element, dom_binding| {
+		let superseded = matches!(HeadKey::of(element), Some(key) if is_superseded(key, ordinal));
+		ordinal += 1;
+		if superseded {
+			Ok(())
+		} else {
+			f(element, dom_binding)
+		}
+	})
+}
+
+/// Returns a predicate that determines whether the contribution with `key` at `ordinal` (in VDOM order) is superseded.
+///
+/// The last ordinal of each [`HeadKey`] is collected in one pass over `roots`, so that [`for_each_contribution`] runs in linear time.
+#[cfg(feature = "alloc")]
+fn supersession<'a, S: ThreadSafety>(
+	roots: &[&Node<'a, S>],
+) -> impl Fn(HeadKey<'a>, usize) -> bool {
+	let mut last = BTreeMap::new();
+	let mut ordinal = 0_usize;
+	match visit_all::<_, Infallible>(roots, &mut |element, _| {
+		if let Some(key) = HeadKey::of(element) {
+			last.insert(key, ordinal);
+		}
+		ordinal += 1;
+		Ok(())
+	}) {
+		Ok(()) => (),
+		Err(never) => match never {},
+	}
+	move |key, ordinal| last.get(&key) != Some(&ordinal)
+}
+
+/// Returns a predicate that determines whether the contribution with `key` at `ordinal` (in VDOM order) is superseded.
+///
+/// Without `"alloc"`, each keyed contribution is checked against all others, so [`for_each_contribution`] runs in quadratic time.
+#[cfg(not(feature = "alloc"))]
+fn supersession<'r, 'a: 'r, S: ThreadSafety>(
+	roots: &'r [&Node<'a, S>],
+) -> impl 'r + Fn(HeadKey<'a>, usize) -> bool {
+	move |key, ordinal| {
+		let mut later = 0_usize;
+		let mut superseded = false;
+		match visit_all::<_, Infallible>(roots, &mut |candidate, _| {
+			superseded |= later > ordinal && HeadKey::of(candidate) == Some(key);
+			later += 1;
+			Ok(())
+		}) {
+			Ok(()) => (),
+			Err(never) => match never {},
+		}
+		superseded
+	}
+}
+
+type Visitor<'v, 'a, S, E> = dyn 'v
+	+ FnMut(
+		&'a Element<'a, S>,
+		Option<CallbackRef<S, fn(dom_ref: DomRef<&'_ web::HtmlElement>)>>,
+	) -> Result<(), E>;
+
+fn visit_all<'a, S: ThreadSafety, E>(
+	roots: &[&Node<'a, S>],
+	f: &mut Visitor<'_, 'a, S, E>,
+) -> Result<(), E> {
+	roots.iter().try_for_each(|root| visit(root, f))
+}
+
+fn visit<'a, S: ThreadSafety, E>(
+	node: &Node<'a, S>,
+	f: &mut Visitor<'_, 'a, S, E>,
+) -> Result<(), E> {
+	match *node {
+		Node::HtmlElement { element, .. }
+		| Node::MathMlElement { element, .. }
+		| Node::SvgElement { element, .. } => {
+			if let Some(shadow_root) = element.shadow_root {
+				visit(&shadow_root.content, f)?;
+			}
+			visit(&element.content, f)
+		}
+		Node::Memoized { content, .. } => visit(content, f),
+		Node::Multi(nodes) => nodes.iter().try_for_each(|node| visit(node, f)),
+		Node::Keyed(pairs) => pairs.iter().try_for_each(|pair| visit(&pair.content, f)),
+		Node::Portal(portal) => visit(&portal.content, f),
+		Node::HeadContribution {
+			element,
+			dom_binding,
+		} => f(element, dom_binding),
+		Node::Comment { .. } | Node::Text { .. } | Node::RawHtml(_) | Node::RemnantSite(_) => {
+			Ok(())
+		}
+	}
+}
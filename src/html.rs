@@ -0,0 +1,751 @@
+//! HTML serialisation of [`Node`] graphs, for example for server-side rendering.
+//!
+//! The functions in this module write into any [`fmt::Write`] and don't allocate, so they are available without the `"callbacks"` feature.
+//! Callbacks, including [`EventBinding`](`crate::EventBinding`)s and [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node) reference bindings, are ignored.
+//!
+//! Content that can't be represented safely is refused with an [`Error`] instead of being written verbatim,
+//! as required by the implementation contracts on the [`Vdom`](`crate::Vdom`) types.
+//! Note that the output may be incomplete in that case.
+//!
+//! [`Node::RawHtml`] fragments are only written by a [`Serializer`] with [`Sanitizer`].
+//! [`Node::Portal`] content is written separately, through [`Serializer::write_portals`].
+//!
+//! Use [`Serializer::write_document`] to write a complete page from a [`Document`].
+//! This also collects [`Node::HeadContribution`]s into `<head>`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{html, Element, ElementCreationOptions, Node, ShadowRoot, ShadowRootOptions, ThreadSafe};
+//!
+//! let slot = Element {
+//!   name: "SLOT",
+//!   creation_options: ElementCreationOptions::new(),
+//!   attributes: &[],
+//!   properties: &[],
+//!   classes: &[],
+//!   styles: &[],
+//!   content: Node::Multi(&[]),
+//!   shadow_root: None,
+//!   event_bindings: &[],
+//! };
+//! let shadow_content = ["Hello, ".into(), slot.as_html()];
+//! let shadow_root = ShadowRoot::<ThreadSafe> {
+//!   options: ShadowRootOptions::new().with_delegates_focus(true),
+//!   content: Node::Multi(&shadow_content),
+//!   dom_binding: None,
+//! };
+//!
+//! let element = Element {
+//!   name: "X-GREETING",
+//!   creation_options: ElementCreationOptions::new(),
+//!   attributes: &[],
+//!   properties: &[],
+//!   classes: &[],
+//!   styles: &[],
+//!   content: "<World>".into(),
+//!   shadow_root: Some(&shadow_root),
+//!   event_bindings: &[],
+//! };
+//!
+//! let mut html = String::new();
+//! html::write_node(&element.as_html(), &mut html).unwrap();
+//! assert_eq!(
+//!   html,
+//!   "<X-GREETING>\
+//!     <template shadowrootmode=\"open\" shadowrootdelegatesfocus>Hello, <SLOT></SLOT></template>\
+//!     &lt;World&gt;\
+//!   </X-GREETING>",
+//! );
+//! ```
+
+use crate::{
+	head, AttributeValue, Document, Element, Node, PropertyValue, Sanitizer, ShadowRoot,
+	ShadowRootMode, StyleDeclaration, ThreadSafety, TrustedHtml,
+};
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+/// Reasons why a [`Node`] graph can't be serialised as HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Error<'a> {
+	/// The [`fmt::Write`] target returned an error.
+	Format,
+	/// An element or attribute name contains characters that aren't valid in its position, or is empty.
+	InvalidName(&'a str),
+	/// A [`Node::Comment`] contains a sequence that's illegal in HTML comments.
+	///
+	/// See <https://html.spec.whatwg.org/multipage/syntax.html#comments>.
+	InvalidComment(&'a str),
+	/// A [***void element***](https://html.spec.whatwg.org/multipage/syntax.html#void-elements) has content or a shadow root.
+	VoidElementContent(&'a str),
+	/// The content of a `<script>` or `<style>` element is not plain text or would end the element early.
+	InvalidRawText(&'a str),
+	/// A [`Node::RawHtml`] fragment was refused by the [`Sanitizer`], or there is none.
+	RawHtmlRefused(TrustedHtml<'a>),
+	/// [`Document::head`] contains an element, text or raw HTML that isn't allowed there.
+	///
+	/// Contains the element name or text.
+	InvalidHeadContent(&'a str),
+}
+
+impl<'a> From<fmt::Error> for Error<'a> {
+	fn from(_: fmt::Error) -> Self {
+		Self::Format
+	}
+}
+
+impl<'a> Display for Error<'a> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Format => f.write_str("formatter error"),
+			Error::InvalidName(name) => write!(f, "invalid name {:?}", name),
+			Error::InvalidComment(comment) => write!(f, "invalid comment {:?}", comment),
+			Error::VoidElementContent(name) => write!(f, "void element {:?} has content", name),
+			Error::InvalidRawText(name) => write!(f, "invalid raw text in {:?}", name),
+			Error::RawHtmlRefused(html) => write!(f, "raw HTML refused: {:?}", html.as_str()),
+			Error::InvalidHeadContent(content) => write!(f, "invalid head content {:?}", content),
+		}
+	}
+}
+
+/// The namespace an [`Element`] is serialised in, which affects how its start and end tags and its content are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ElementKind {
+	/// An element in the HTML namespace, as in [`Node::HtmlElement`].
+	Html,
+	/// An element in the [MathML](https://developer.mozilla.org/en-US/docs/Web/MathML) namespace, as in [`Node::MathMlElement`].
+	MathMl,
+	/// An element in the SVG namespace, as in [`Node::SvgElement`].
+	Svg,
+}
+impl ElementKind {
+	/// The [***namespaceURI***](https://developer.mozilla.org/en-US/docs/Web/API/Element/namespaceURI) of elements of this kind.
+	#[must_use]
+	pub const fn namespace(self) -> &'static str {
+		match self {
+			ElementKind::Html => "http://www.w3.org/1999/xhtml",
+			ElementKind::MathMl => "http://www.w3.org/1998/Math/MathML",
+			ElementKind::Svg => "http://www.w3.org/2000/svg",
+		}
+	}
+}
+
+/// A configurable HTML serialiser.
+///
+/// The free functions in this module use [`Serializer::new()`], which refuses [`Node::RawHtml`].
+#[derive(Clone, Copy)]
+pub struct Serializer<'s> {
+	sanitizer: Option<&'s dyn Sanitizer>,
+}
+impl<'s> Default for Serializer<'s> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<'s> Debug for Serializer<'s> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Serializer")
+			.field("sanitizer", &self.sanitizer.map(|_| ..))
+			.finish()
+	}
+}
+#[allow(clippy::inline_always)] // Trivial getters and setters.
+impl<'s> Serializer<'s> {
+	/// Creates a new [`Serializer`] without [`Sanitizer`].
+	#[inline(always)]
+	#[must_use]
+	pub fn new() -> Self {
+		// Not `const`, as trait objects in `const fn`s require Rust 1.61.
+		Self { sanitizer: None }
+	}
+
+	/// Retrieves the [`Sanitizer`] that [`Node::RawHtml`] fragments are passed through, if any.
+	#[inline(always)]
+	#[must_use]
+	pub fn sanitizer(&self) -> Option<&'s dyn Sanitizer> {
+		self.sanitizer
+	}
+	/// Sets the [`Sanitizer`] that [`Node::RawHtml`] fragments are passed through.
+	///
+	/// Without one, they are refused with [`Error::RawHtmlRefused`].
+	#[inline(always)]
+	pub fn set_sanitizer(&mut self, sanitizer: Option<&'s dyn Sanitizer>) {
+		self.sanitizer = sanitizer
+	}
+	/// Sets the [`Sanitizer`] that [`Node::RawHtml`] fragments are passed through.
+	///
+	/// Without one, they are refused with [`Error::RawHtmlRefused`].
+	#[inline(always)]
+	#[must_use]
+	pub fn with_sanitizer(self, sanitizer: &'s dyn Sanitizer) -> Self {
+		Self {
+			sanitizer: Some(sanitizer),
+		}
+	}
+
+	/// Serialises `node` as HTML fragment into `w`.
+	///
+	/// Shadow roots are written as [declarative shadow root](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template#shadowrootmode) `<template>`s.
+	/// See [`ShadowRoot`] for details.
+	///
+	/// # Errors
+	///
+	/// Iff `node` contains anything that can't be serialised safely, or if `w` returns an error. See [`Error`].
+	#[allow(clippy::missing_panics_doc)] // todo!
+	pub fn write_node<'a, S: ThreadSafety>(
+		&self,
+		node: &Node<'a, S>,
+		w: &mut impl Write,
+	) -> Result<(), Error<'a>> {
+		match *node {
+			Node::Comment { comment, .. } => {
+				if !is_valid_comment(comment) {
+					return Err(Error::InvalidComment(comment));
+				}
+				write!(w, "<!--{}-->", comment)?;
+			}
+			Node::HtmlElement { element, .. } => {
+				self.write_element(element, ElementKind::Html, w)?
+			}
+			Node::MathMlElement { element, .. } => {
+				self.write_element(element, ElementKind::MathMl, w)?
+			}
+			Node::SvgElement { element, .. } => self.write_element(element, ElementKind::Svg, w)?,
+			Node::Memoized { content, .. } => self.write_node(content, w)?,
+			Node::Multi(nodes) => {
+				for node in nodes {
+					self.write_node(node, w)?;
+				}
+			}
+			Node::Keyed(pairs) => {
+				for pair in pairs {
+					self.write_node(&pair.content, w)?;
+				}
+			}
+			Node::Text { text, .. } => EscapingWriter { w, quot: false }.write_str(text)?,
+			Node::RawHtml(html) => self.write_raw_html(html, w)?,
+			Node::Portal(_) => (),               // See `write_portals`.
+			Node::HeadContribution { .. } => (), // See `write_head_contributions`.
+			Node::RemnantSite(_) => {
+				todo!("RemnantSite HTML serialisation")
+			}
+		}
+		Ok(())
+	}
+
+	/// Serialises `element` as `kind` of element into `w`, including its attributes, properties, classes, styles and shadow root.
+	///
+	/// Reflected [`Element::properties`] (see [`Property`](`crate::Property`)) override [`Element::attributes`] of the same name,
+	/// and non-empty [`Element::classes`] and [`Element::styles`] override the `class` and `style` attribute respectively.
+	/// [`StyleDeclaration`]s that can't be serialised safely are skipped.
+	///
+	/// # Errors
+	///
+	/// Iff `element` contains anything that can't be serialised safely, or if `w` returns an error. See [`Error`].
+	pub fn write_element<'a, S: ThreadSafety>(
+		&self,
+		element: &Element<'a, S>,
+		kind: ElementKind,
+		w: &mut impl Write,
+	) -> Result<(), Error<'a>> {
+		let name = element.name;
+		if !is_valid_name(name) {
+			return Err(Error::InvalidName(name));
+		}
+		let is_html = matches!(kind, ElementKind::Html);
+
+		write!(w, "<{}", name)?;
+		if let Some(is) = element.creation_options.is() {
+			write_attribute("is", AttributeValue::String(is), w)?;
+		}
+		for attribute in element.attributes {
+			if !attribute.value.is_present()
+				|| (attribute.name == "class" && !element.classes.is_empty())
+				|| (attribute.name == "style" && !element.styles.is_empty())
+				|| element
+					.properties
+					.iter()
+					.any(|property| property.reflected_attribute() == Some(attribute.name))
+			{
+				continue;
+			}
+			write_attribute(attribute.name, attribute.value, w)?;
+		}
+		let mut textarea_value = None;
+		for property in element.properties {
+			if is_html && property.name == "value" && name.eq_ignore_ascii_case("textarea") {
+				textarea_value = Some(property.value);
+				continue;
+			}
+			if let Some(attribute_name) = property.reflected_attribute() {
+				write_attribute(attribute_name, reflected_value(property.value), w)?;
+			}
+		}
+		if !element.classes.is_empty() {
+			write_attribute("class", AttributeValue::Tokens(element.classes), w)?;
+		}
+		if element.styles.iter().any(is_safe_style) {
+			w.write_str(" style=\"")?;
+			for (i, style) in element
+				.styles
+				.iter()
+				.filter(|s| is_safe_style(s))
+				.enumerate()
+			{
+				if i > 0 {
+					w.write_str("; ")?;
+				}
+				write!(EscapingWriter { w, quot: true }, "{}", style)?;
+			}
+			w.write_char('"')?;
+		}
+
+		let has_content = !element.content.dom_empty() || is_raw_html(&element.content);
+		if is_html && is_void(name) {
+			if has_content || element.shadow_root.is_some() {
+				return Err(Error::VoidElementContent(name));
+			}
+			w.write_char('>')?;
+			return Ok(());
+		}
+		if !is_html && element.shadow_root.is_none() && !has_content {
+			w.write_str("/>")?;
+			return Ok(());
+		}
+		w.write_char('>')?;
+
+		if let Some(shadow_root) = element.shadow_root {
+			self.write_shadow_root(shadow_root, w)?;
+		}
+		if let Some(value) = textarea_value {
+			write_property_value(value, false, w)?;
+		} else if is_html
+			&& (name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style"))
+		{
+			write_raw_text(name, &element.content, &mut 0, w)?;
+		} else {
+			self.write_node(&element.content, w)?;
+		}
+
+		write!(w, "</{}>", name)?;
+		Ok(())
+	}
+
+	/// Serialises `shadow_root` as [declarative shadow root](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template#shadowrootmode) into `w`.
+	///
+	/// [`Serializer::write_element`] calls this right after the host's start tag.
+	///
+	/// # Errors
+	///
+	/// Iff [`ShadowRoot::content`] contains anything that can't be serialised safely, or if `w` returns an error. See [`Error`].
+	pub fn write_shadow_root<'a, S: ThreadSafety>(
+		&self,
+		shadow_root: &ShadowRoot<'a, S>,
+		w: &mut impl Write,
+	) -> Result<(), Error<'a>> {
+		let options = shadow_root.options;
+		w.write_str(match options.mode() {
+			ShadowRootMode::Open => "<template shadowrootmode=\"open\"",
+			ShadowRootMode::Closed => "<template shadowrootmode=\"closed\"",
+		})?;
+		if options.delegates_focus() {
+			w.write_str(" shadowrootdelegatesfocus")?;
+		}
+		w.write_char('>')?;
+		self.write_node(&shadow_root.content, w)?;
+		w.write_str("</template>")?;
+		Ok(())
+	}
+
+	/// Serialises `document` as complete HTML document into `w`.
+	///
+	/// All of `<!DOCTYPE …>`, `<html>`, `<head>` and `<body>` are written explicitly, so browsers won't imply or move any of them.
+	///
+	/// [`Node::HeadContribution`]s from anywhere in the document are written at the end of `<head>`.
+	///
+	/// # Errors
+	///
+	/// Iff `document` contains anything that can't be serialised safely, or if `w` returns an error. See [`Error`].
+	///
+	/// In particular, [`Document::head`] is checked against [`Document`]'s implementation contract,
+	/// as anything else there would make browsers close `<head>` early.
+	pub fn write_document<'a, S: ThreadSafety>(
+		&self,
+		document: &Document<'a, S>,
+		w: &mut impl Write,
+	) -> Result<(), Error<'a>> {
+		if !is_valid_name(document.doctype) {
+			return Err(Error::InvalidName(document.doctype));
+		}
+		write!(w, "<!DOCTYPE {}><html", document.doctype)?;
+		for attribute in document.html_attributes {
+			if !attribute.value.is_present() {
+				continue;
+			}
+			write_attribute(attribute.name, attribute.value, w)?;
+		}
+		w.write_str("><head>")?;
+		check_head(&document.head)?;
+		self.write_node(&document.head, w)?;
+		self.write_head_contributions(&[&document.head, &document.body], w)?;
+		w.write_str("</head><body>")?;
+		self.write_node(&document.body, w)?;
+		w.write_str("</body></html>")?;
+		Ok(())
+	}
+
+	/// Serialises the content of all [`Node::Portal`]s in `node` that name `target` into `w`, in the order they appear in.
+	///
+	/// [`Serializer::write_node`] skips portals, so call this where the respective container's content ends.
+	///
+	/// # Errors
+	///
+	/// Iff any matching portal content can't be serialised safely, or if `w` returns an error. See [`Error`].
+	pub fn write_portals<'a, S: ThreadSafety>(
+		&self,
+		node: &Node<'a, S>,
+		target: &str,
+		w: &mut impl Write,
+	) -> Result<(), Error<'a>> {
+		match *node {
+			Node::HtmlElement { element, .. }
+			| Node::MathMlElement { element, .. }
+			| Node::SvgElement { element, .. } => {
+				if let Some(shadow_root) = element.shadow_root {
+					self.write_portals(&shadow_root.content, target, w)?;
+				}
+				self.write_portals(&element.content, target, w)?;
+			}
+			Node::Memoized { content, .. } => self.write_portals(content, target, w)?,
+			Node::Multi(nodes) => {
+				for node in nodes {
+					self.write_portals(node, target, w)?;
+				}
+			}
+			Node::Keyed(pairs) => {
+				for pair in pairs {
+					self.write_portals(&pair.content, target, w)?;
+				}
+			}
+			Node::Portal(portal) => {
+				if portal.target == target {
+					self.write_node(&portal.content, w)?;
+				}
+				self.write_portals(&portal.content, target, w)?;
+			}
+			Node::Comment { .. }
+			| Node::Text { .. }
+			| Node::RawHtml(_)
+			| Node::HeadContribution { .. }
+			| Node::RemnantSite(_) => {}
+		}
+		Ok(())
+	}
+
+	/// Serialises the deduplicated [`Node::HeadContribution`]s from `roots` into `w`, as by [`head::for_each_contribution`].
+	///
+	/// [`Serializer::write_document`] calls this with [`Document::head`] and [`Document::body`] at the end of `<head>`.
+	///
+	/// # Errors
+	///
+	/// Iff any rendered contribution isn't [metadata content](https://html.spec.whatwg.org/multipage/dom.html#metadata-content-2)
+	/// or can't be serialised safely, or if `w` returns an error. See [`Error`].
+	pub fn write_head_contributions<'a, S: ThreadSafety>(
+		&self,
+		roots: &[&Node<'a, S>],
+		w: &mut impl Write,
+	) -> Result<(), Error<'a>> {
+		head::for_each_contribution(roots, |element, _| {
+			let node = element.as_html();
+			check_head(&node)?;
+			self.write_node(&node, w)
+		})
+	}
+
+	fn write_raw_html<'a>(
+		&self,
+		html: TrustedHtml<'a>,
+		w: &mut impl Write,
+	) -> Result<(), Error<'a>> {
+		let sanitizer = self.sanitizer.ok_or(Error::RawHtmlRefused(html))?;
+		let mut tracking = TrackingWriter { w, failed: false };
+		match sanitizer.sanitize(html, &mut tracking) {
+			Ok(()) => Ok(()),
+			Err(fmt::Error) if tracking.failed => Err(Error::Format),
+			Err(fmt::Error) => Err(Error::RawHtmlRefused(html)),
+		}
+	}
+}
+
+/// Serialises `node` as HTML fragment into `w`, using [`Serializer::new()`].
+///
+/// # Errors
+///
+/// See [`Serializer::write_node`].
+pub fn write_node<'a, S: ThreadSafety>(
+	node: &Node<'a, S>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	Serializer::new().write_node(node, w)
+}
+
+/// Serialises `element` as `kind` of element into `w`, using [`Serializer::new()`].
+///
+/// # Errors
+///
+/// See [`Serializer::write_element`].
+pub fn write_element<'a, S: ThreadSafety>(
+	element: &Element<'a, S>,
+	kind: ElementKind,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	Serializer::new().write_element(element, kind, w)
+}
+
+/// Serialises `shadow_root` as declarative shadow root into `w`, using [`Serializer::new()`].
+///
+/// # Errors
+///
+/// See [`Serializer::write_shadow_root`].
+pub fn write_shadow_root<'a, S: ThreadSafety>(
+	shadow_root: &ShadowRoot<'a, S>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	Serializer::new().write_shadow_root(shadow_root, w)
+}
+
+fn write_attribute<'a>(
+	name: &'a str,
+	value: AttributeValue<'a>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	if !is_valid_name(name) {
+		return Err(Error::InvalidName(name));
+	}
+	match value {
+		AttributeValue::Bool(false) => (),
+		AttributeValue::Bool(true) => write!(w, " {}", name)?,
+		value => {
+			write!(w, " {}=\"", name)?;
+			let mut escaping = EscapingWriter { w, quot: true };
+			write!(escaping, "{}", value)?;
+			w.write_char('"')?;
+		}
+	}
+	Ok(())
+}
+
+/// The [`AttributeValue`] a [`Property`](`crate::Property`) is reflected as.
+pub(crate) fn reflected_value(value: PropertyValue<'_>) -> AttributeValue<'_> {
+	match value {
+		PropertyValue::Bool(present) => AttributeValue::Bool(present),
+		PropertyValue::Integer(integer) => AttributeValue::Integer(integer.into()),
+		PropertyValue::Number(number) => AttributeValue::Float(number),
+		PropertyValue::String(string) => AttributeValue::String(string),
+	}
+}
+
+fn write_property_value(value: PropertyValue<'_>, quot: bool, w: &mut impl Write) -> fmt::Result {
+	let mut escaping = EscapingWriter { w, quot };
+	match value {
+		PropertyValue::Bool(bool) => write!(escaping, "{}", bool),
+		PropertyValue::Integer(integer) => write!(escaping, "{}", integer),
+		PropertyValue::Number(number) => write!(escaping, "{}", AttributeValue::Float(number)),
+		PropertyValue::String(string) => escaping.write_str(string),
+	}
+}
+
+/// Writes the text in `content` verbatim, refusing any `</` + `name` (ASCII-case-insensitively), as that would end the element early.
+///
+/// `matched` is the length of the prefix of that sequence the text written so far ends with, so that it's also found across [`Node::Text`] boundaries.
+fn write_raw_text<'a, S: ThreadSafety>(
+	name: &'a str,
+	content: &Node<'a, S>,
+	matched: &mut usize,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	match *content {
+		Node::Text { text, .. } => {
+			for b in text.bytes() {
+				*matched = match *matched {
+					1 if b == b'/' => 2,
+					m if m >= 2 && name.as_bytes()[m - 2].eq_ignore_ascii_case(&b) => m + 1,
+					_ => usize::from(b == b'<'),
+				};
+				if *matched == name.len() + 2 {
+					return Err(Error::InvalidRawText(name));
+				}
+			}
+			w.write_str(text)?;
+		}
+		Node::Memoized { content, .. } => write_raw_text(name, content, matched, w)?,
+		Node::Multi(nodes) => {
+			for node in nodes {
+				write_raw_text(name, node, matched, w)?;
+			}
+		}
+		Node::Keyed(pairs) => {
+			for pair in pairs {
+				write_raw_text(name, &pair.content, matched, w)?;
+			}
+		}
+		_ => return Err(Error::InvalidRawText(name)),
+	}
+	Ok(())
+}
+
+/// See [`Document`]'s implementation contract.
+fn check_head<'a, S: ThreadSafety>(node: &Node<'a, S>) -> Result<(), Error<'a>> {
+	match *node {
+		Node::HtmlElement { element, .. } => {
+			if [
+				"base", "link", "meta", "noscript", "script", "style", "template", "title",
+			]
+			.iter()
+			.any(|metadata| metadata.eq_ignore_ascii_case(element.name))
+			{
+				Ok(())
+			} else {
+				Err(Error::InvalidHeadContent(element.name))
+			}
+		}
+		Node::MathMlElement { element, .. } | Node::SvgElement { element, .. } => {
+			Err(Error::InvalidHeadContent(element.name))
+		}
+		Node::Text { text, .. } => {
+			// Inter-element whitespace.
+			if text
+				.bytes()
+				.all(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\x0c' | b'\r'))
+			{
+				Ok(())
+			} else {
+				Err(Error::InvalidHeadContent(text))
+			}
+		}
+		Node::RawHtml(html) => Err(Error::InvalidHeadContent(html.as_str())),
+		Node::Memoized { content, .. } => check_head(content),
+		Node::Multi(nodes) => nodes.iter().try_for_each(check_head),
+		Node::Keyed(pairs) => pairs.iter().try_for_each(|pair| check_head(&pair.content)),
+		Node::Comment { .. }
+		| Node::Portal(_)
+		| Node::HeadContribution { .. }
+		| Node::RemnantSite(_) => Ok(()),
+	}
+}
+
+/// Records whether the inner [`Write`] failed, to tell [`Sanitizer`] refusals apart.
+struct TrackingWriter<'w, W: Write> {
+	w: &'w mut W,
+	failed: bool,
+}
+impl<'w, W: Write> Write for TrackingWriter<'w, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let result = self.w.write_str(s);
+		self.failed |= result.is_err();
+		result
+	}
+}
+
+/// Whether `node` is (optionally [`Memoized`](`Node::Memoized`)) [`Node::RawHtml`].
+fn is_raw_html<S: ThreadSafety>(node: &Node<'_, S>) -> bool {
+	match node {
+		Node::RawHtml(_) => true,
+		Node::Memoized { content, .. } => is_raw_html(content),
+		_ => false,
+	}
+}
+
+/// Escapes `&`, `<`, `>`, non-breaking spaces and optionally `"`.
+struct EscapingWriter<'w, W: Write> {
+	w: &'w mut W,
+	quot: bool,
+}
+impl<'w, W: Write> Write for EscapingWriter<'w, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let mut rest = s;
+		while let Some(i) = rest.find(|c| matches!(c, '&' | '<' | '>' | '\u{a0}' | '"')) {
+			let (verbatim, special) = rest.split_at(i);
+			self.w.write_str(verbatim)?;
+			let c = special.chars().next().expect("unreachable");
+			self.w.write_str(match c {
+				'&' => "&amp;",
+				'<' => "&lt;",
+				'>' => "&gt;",
+				'\u{a0}' => "&nbsp;",
+				'"' if self.quot => "&quot;",
+				_ => "\"",
+			})?;
+			rest = &special[c.len_utf8()..];
+		}
+		self.w.write_str(rest)
+	}
+}
+
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#comments>.
+fn is_valid_comment(comment: &str) -> bool {
+	!(comment.starts_with('>')
+		|| comment.starts_with("->")
+		|| comment.contains("<!--")
+		|| comment.contains("-->")
+		|| comment.contains("--!>")
+		|| comment.ends_with("<!-"))
+}
+
+/// Conservative: Tag and attribute names must be non-empty and can't contain whitespace, controls, quotes, `/`, `<`, `=` or `>`.
+fn is_valid_name(name: &str) -> bool {
+	!name.is_empty()
+		&& !name.chars().any(|c| {
+			c.is_whitespace()
+				|| c.is_control()
+				|| matches!(c, '"' | '\'' | '/' | '<' | '=' | '>' | '\u{fffd}')
+		})
+}
+
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#void-elements>.
+fn is_void(name: &str) -> bool {
+	[
+		"area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source",
+		"track", "wbr",
+	]
+	.iter()
+	.any(|void| void.eq_ignore_ascii_case(name))
+}
+
+/// See [`StyleDeclaration`]'s implementation contract.
+pub(crate) fn is_safe_style(style: &StyleDeclaration<'_>) -> bool {
+	let balanced = |text: &str| {
+		let mut stack = [0_u8; 32];
+		let mut depth = 0;
+		let mut quote = None;
+		for b in text.bytes() {
+			match (quote, b) {
+				(Some(q), b) if b == q => quote = None,
+				(None, b'\'') => quote = Some(b'\''),
+				(None, b'(' | b'[' | b'{') => {
+					if depth == stack.len() {
+						return false;
+					}
+					stack[depth] = b;
+					depth += 1;
+				}
+				(None, b')' | b']' | b'}') => {
+					let open = match b {
+						b')' => b'(',
+						b']' => b'[',
+						_ => b'{',
+					};
+					if depth == 0 || stack[depth - 1] != open {
+						return false;
+					}
+					depth -= 1;
+				}
+				_ => (),
+			}
+		}
+		quote.is_none() && depth == 0
+	};
+	[style.property, style.value]
+		.iter()
+		.all(|text| !text.contains(|c| matches!(c, ';' | '"' | '<' | '\\')) && balanced(text))
+}
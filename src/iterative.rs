@@ -0,0 +1,936 @@
+//! Stack-safe [`Debug`], [`PartialEq`], [`Hash`] and [`Ord`] implementations for the recursive VDOM types,
+//! as well as [`Node::dom_len`] and [`Node::dom_empty`]. Requires the `"alloc"` feature.
+//!
+//! These replace the recursive implementations in `ergonomics` and must stay observably identical to them,
+//! except that pending work is kept in a heap-allocated explicit stack rather than on the call stack.
+//!
+//! > [`Debug`] only forwards [`Formatter::alternate`] to fields, as other [`Formatter`] options can't be set on stable Rust.
+
+extern crate alloc;
+
+use crate::{
+	auto_safety::Align, Document, Element, Node, Portal, ReorderableFragment, ShadowRoot,
+	ThreadBound, ThreadSafety,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+	cmp::Ordering,
+	fmt::{self, Debug, Formatter, Write},
+	hash::{Hash, Hasher},
+};
+
+/// A reference to one of the (mutually) recursive parts of a VDOM graph.
+enum Item<'a, S: ThreadSafety> {
+	Document(&'a Document<'a, S>),
+	Element(&'a Element<'a, S>),
+	Node(&'a Node<'a, S>),
+	Nodes(&'a [Node<'a, S>]),
+	Portal(&'a Portal<'a, S>),
+	ReorderableFragment(&'a ReorderableFragment<'a, S>),
+	ReorderableFragments(&'a [ReorderableFragment<'a, S>]),
+	ShadowRoot(&'a ShadowRoot<'a, S>),
+	/// [`Element::shadow_root`].
+	OptionShadowRoot(Option<&'a ShadowRoot<'a, S>>),
+}
+#[allow(clippy::expl_impl_clone_on_copy)] // Derived `Copy` would be bounded on `S: Copy`.
+impl<'a, S: ThreadSafety> Clone for Item<'a, S> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<'a, S: ThreadSafety> Copy for Item<'a, S> {}
+
+macro_rules! iterative_impls {
+	($($Name:ident),*$(,)?) => {$(
+		impl<'a, S> Debug for $Name<'a, S>
+		where
+			S: ThreadSafety,
+		{
+			fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+				debug(Item::$Name(self), f)
+			}
+		}
+
+		impl<'a, S1, S2> PartialEq<$Name<'a, S2>> for $Name<'a, S1>
+		where
+			S1: ThreadSafety,
+			S2: ThreadSafety,
+		{
+			fn eq(&self, other: &$Name<'a, S2>) -> bool {
+				eq(
+					Item::<ThreadBound>::$Name(self.align_ref()),
+					Item::$Name(other.align_ref()),
+				)
+			}
+		}
+
+		impl<'a, S> Hash for $Name<'a, S>
+		where
+			S: ThreadSafety,
+		{
+			fn hash<H: Hasher>(&self, state: &mut H) {
+				hash(Item::$Name(self), state)
+			}
+		}
+
+		impl<'a, S> Ord for $Name<'a, S>
+		where
+			S: ThreadSafety,
+		{
+			fn cmp(&self, other: &Self) -> Ordering {
+				cmp(
+					Item::<ThreadBound>::$Name(self.align_ref()),
+					Item::$Name(other.align_ref()),
+				)
+			}
+		}
+	)*};
+}
+
+iterative_impls!(
+	Document,
+	Element,
+	Node,
+	Portal,
+	ReorderableFragment,
+	ShadowRoot,
+);
+
+fn eq(a: Item<'_, ThreadBound>, b: Item<'_, ThreadBound>) -> bool {
+	let mut stack = vec![(a, b)];
+	while let Some(pair) = stack.pop() {
+		let shallow_eq =
+			match pair {
+				(Item::Document(a), Item::Document(b)) => {
+					stack.push((Item::Node(&a.head), Item::Node(&b.head)));
+					stack.push((Item::Node(&a.body), Item::Node(&b.body)));
+					a.doctype == b.doctype && a.html_attributes == b.html_attributes
+				}
+				(Item::Element(a), Item::Element(b)) => {
+					stack.push((Item::Node(&a.content), Item::Node(&b.content)));
+					stack.push((
+						Item::OptionShadowRoot(a.shadow_root),
+						Item::OptionShadowRoot(b.shadow_root),
+					));
+					a.name == b.name
+						&& a.creation_options == b.creation_options
+						&& a.attributes == b.attributes
+						&& a.properties == b.properties
+						&& crate::ergonomics::unordered::eq(a.classes, b.classes)
+						&& crate::ergonomics::unordered::eq(a.styles, b.styles)
+						&& a.event_bindings == b.event_bindings
+				}
+				(Item::Nodes(a), Item::Nodes(b)) => {
+					stack.extend(a.iter().zip(b).map(|(a, b)| (Item::Node(a), Item::Node(b))));
+					a.len() == b.len()
+				}
+				(Item::Portal(a), Item::Portal(b)) => {
+					stack.push((Item::Node(&a.content), Item::Node(&b.content)));
+					a.target == b.target && a.dom_binding == b.dom_binding
+				}
+				(Item::ReorderableFragment(a), Item::ReorderableFragment(b)) => {
+					stack.push((Item::Node(&a.content), Item::Node(&b.content)));
+					a.dom_key == b.dom_key
+				}
+				(Item::ReorderableFragments(a), Item::ReorderableFragments(b)) => {
+					stack.extend(a.iter().zip(b).map(|(a, b)| {
+						(Item::ReorderableFragment(a), Item::ReorderableFragment(b))
+					}));
+					a.len() == b.len()
+				}
+				(Item::ShadowRoot(a), Item::ShadowRoot(b)) => {
+					stack.push((Item::Node(&a.content), Item::Node(&b.content)));
+					a.options == b.options && a.dom_binding == b.dom_binding
+				}
+				(Item::OptionShadowRoot(a), Item::OptionShadowRoot(b)) => match (a, b) {
+					(None, None) => true,
+					(Some(a), Some(b)) => {
+						stack.push((Item::ShadowRoot(a), Item::ShadowRoot(b)));
+						true
+					}
+					(_, _) => false,
+				},
+				(Item::Node(a), Item::Node(b)) => node_eq(a, b, &mut stack),
+				(_, _) => unreachable!("Only items of the same kind are compared."),
+			};
+		if !shallow_eq {
+			return false;
+		}
+	}
+	true
+}
+
+fn node_eq<'a>(
+	a: &'a Node<'a, ThreadBound>,
+	b: &'a Node<'a, ThreadBound>,
+	stack: &mut Vec<(Item<'a, ThreadBound>, Item<'a, ThreadBound>)>,
+) -> bool {
+	match (a, b) {
+		(
+			Node::Comment {
+				comment: c_1,
+				dom_binding: db_1,
+			},
+			Node::Comment {
+				comment: c_2,
+				dom_binding: db_2,
+			},
+		) => c_1 == c_2 && db_1 == db_2,
+		(
+			Node::HtmlElement {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::HtmlElement {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push((Item::Element(e_1), Item::Element(e_2)));
+			db_1 == db_2
+		}
+		(
+			Node::MathMlElement {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::MathMlElement {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push((Item::Element(e_1), Item::Element(e_2)));
+			db_1 == db_2
+		}
+		(
+			Node::SvgElement {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::SvgElement {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push((Item::Element(e_1), Item::Element(e_2)));
+			db_1 == db_2
+		}
+		(
+			Node::HeadContribution {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::HeadContribution {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push((Item::Element(e_1), Item::Element(e_2)));
+			db_1 == db_2
+		}
+		(
+			Node::Memoized {
+				state_key: sk_1, ..
+			},
+			Node::Memoized {
+				state_key: sk_2, ..
+			},
+		) => sk_1 == sk_2,
+		(Node::Multi(n_1), Node::Multi(n_2)) => {
+			stack.push((Item::Nodes(n_1), Item::Nodes(n_2)));
+			true
+		}
+		(Node::Keyed(p_1), Node::Keyed(p_2)) => {
+			stack.push((
+				Item::ReorderableFragments(p_1),
+				Item::ReorderableFragments(p_2),
+			));
+			true
+		}
+		(
+			Node::Text {
+				text: t_1,
+				dom_binding: db_1,
+			},
+			Node::Text {
+				text: t_2,
+				dom_binding: db_2,
+			},
+		) => t_1 == t_2 && db_1 == db_2,
+		(Node::RawHtml(h_1), Node::RawHtml(h_2)) => h_1 == h_2,
+		(Node::Portal(p_1), Node::Portal(p_2)) => {
+			stack.push((Item::Portal(p_1), Item::Portal(p_2)));
+			true
+		}
+		(Node::RemnantSite(rs_1), Node::RemnantSite(rs_2)) => rs_1 == rs_2,
+		(_, _) => false,
+	}
+}
+
+/// Either a pair of items to compare or the result of a previous (shallow) comparison, deferred until after preceding items.
+enum CmpWork<'a> {
+	Items(Item<'a, ThreadBound>, Item<'a, ThreadBound>),
+	Then(Ordering),
+}
+
+fn cmp(a: Item<'_, ThreadBound>, b: Item<'_, ThreadBound>) -> Ordering {
+	let mut stack = vec![CmpWork::Items(a, b)];
+	while let Some(work) = stack.pop() {
+		let ordering = match work {
+			CmpWork::Then(ordering) => ordering,
+			CmpWork::Items(a, b) => {
+				let start = stack.len();
+				let ordering = shallow_cmp(a, b, &mut stack);
+				stack[start..].reverse();
+				ordering
+			}
+		};
+		if ordering != Ordering::Equal {
+			return ordering;
+		}
+	}
+	Ordering::Equal
+}
+
+/// Compares the non-recursive parts of `a` and `b` and pushes the rest in order.
+///
+/// Since recursive fields come last in every VDOM type, this preserves the lexicographic order.
+fn shallow_cmp<'a>(
+	a: Item<'a, ThreadBound>,
+	b: Item<'a, ThreadBound>,
+	stack: &mut Vec<CmpWork<'a>>,
+) -> Ordering {
+	match (a, b) {
+		(Item::Document(a), Item::Document(b)) => {
+			stack.push(CmpWork::Items(Item::Node(&a.head), Item::Node(&b.head)));
+			stack.push(CmpWork::Items(Item::Node(&a.body), Item::Node(&b.body)));
+			a.doctype
+				.cmp(b.doctype)
+				.then_with(|| a.html_attributes.cmp(b.html_attributes))
+		}
+		(Item::Element(a), Item::Element(b)) => {
+			stack.push(CmpWork::Items(
+				Item::Node(&a.content),
+				Item::Node(&b.content),
+			));
+			stack.push(CmpWork::Items(
+				Item::OptionShadowRoot(a.shadow_root),
+				Item::OptionShadowRoot(b.shadow_root),
+			));
+			a.name
+				.cmp(b.name)
+				.then_with(|| a.creation_options.cmp(&b.creation_options))
+				.then_with(|| a.attributes.cmp(b.attributes))
+				.then_with(|| a.properties.cmp(b.properties))
+				.then_with(|| crate::ergonomics::unordered::cmp(a.classes, b.classes))
+				.then_with(|| crate::ergonomics::unordered::cmp(a.styles, b.styles))
+				.then_with(|| a.event_bindings.cmp(b.event_bindings))
+		}
+		(Item::Nodes(a), Item::Nodes(b)) => {
+			stack.extend(
+				a.iter()
+					.zip(b)
+					.map(|(a, b)| CmpWork::Items(Item::Node(a), Item::Node(b))),
+			);
+			stack.push(CmpWork::Then(a.len().cmp(&b.len())));
+			Ordering::Equal
+		}
+		(Item::Portal(a), Item::Portal(b)) => {
+			stack.push(CmpWork::Items(
+				Item::Node(&a.content),
+				Item::Node(&b.content),
+			));
+			a.target
+				.cmp(b.target)
+				.then_with(|| a.dom_binding.cmp(&b.dom_binding))
+		}
+		(Item::ReorderableFragment(a), Item::ReorderableFragment(b)) => {
+			stack.push(CmpWork::Items(
+				Item::Node(&a.content),
+				Item::Node(&b.content),
+			));
+			a.dom_key.cmp(&b.dom_key)
+		}
+		(Item::ReorderableFragments(a), Item::ReorderableFragments(b)) => {
+			stack.extend(a.iter().zip(b).map(|(a, b)| {
+				CmpWork::Items(Item::ReorderableFragment(a), Item::ReorderableFragment(b))
+			}));
+			stack.push(CmpWork::Then(a.len().cmp(&b.len())));
+			Ordering::Equal
+		}
+		(Item::ShadowRoot(a), Item::ShadowRoot(b)) => {
+			stack.push(CmpWork::Items(
+				Item::Node(&a.content),
+				Item::Node(&b.content),
+			));
+			a.options
+				.cmp(&b.options)
+				.then_with(|| a.dom_binding.cmp(&b.dom_binding))
+		}
+		(Item::OptionShadowRoot(a), Item::OptionShadowRoot(b)) => match (a, b) {
+			(Some(a), Some(b)) => {
+				stack.push(CmpWork::Items(Item::ShadowRoot(a), Item::ShadowRoot(b)));
+				Ordering::Equal
+			}
+			(a, b) => a.is_some().cmp(&b.is_some()),
+		},
+		(Item::Node(a), Item::Node(b)) => node_cmp(a, b, stack),
+		(_, _) => unreachable!("Only items of the same kind are compared."),
+	}
+}
+
+#[allow(clippy::too_many_lines)] // One arm per variant.
+fn node_cmp<'a>(
+	a: &'a Node<'a, ThreadBound>,
+	b: &'a Node<'a, ThreadBound>,
+	stack: &mut Vec<CmpWork<'a>>,
+) -> Ordering {
+	match (a, b) {
+		(
+			Node::Comment {
+				comment: c_1,
+				dom_binding: db_1,
+			},
+			Node::Comment {
+				comment: c_2,
+				dom_binding: db_2,
+			},
+		) => c_1.cmp(c_2).then_with(|| db_1.cmp(db_2)),
+		(
+			Node::HtmlElement {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::HtmlElement {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push(CmpWork::Items(Item::Element(e_1), Item::Element(e_2)));
+			db_1.cmp(db_2)
+		}
+		(
+			Node::MathMlElement {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::MathMlElement {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push(CmpWork::Items(Item::Element(e_1), Item::Element(e_2)));
+			db_1.cmp(db_2)
+		}
+		(
+			Node::SvgElement {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::SvgElement {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push(CmpWork::Items(Item::Element(e_1), Item::Element(e_2)));
+			db_1.cmp(db_2)
+		}
+		(
+			Node::HeadContribution {
+				element: e_1,
+				dom_binding: db_1,
+			},
+			Node::HeadContribution {
+				element: e_2,
+				dom_binding: db_2,
+			},
+		) => {
+			stack.push(CmpWork::Items(Item::Element(e_1), Item::Element(e_2)));
+			db_1.cmp(db_2)
+		}
+		(
+			Node::Memoized {
+				state_key: sk_1,
+				content: c_1,
+			},
+			Node::Memoized {
+				state_key: sk_2,
+				content: c_2,
+			},
+		) => {
+			stack.push(CmpWork::Items(Item::Node(c_1), Item::Node(c_2)));
+			sk_1.cmp(sk_2)
+		}
+		(Node::Multi(n_1), Node::Multi(n_2)) => {
+			stack.push(CmpWork::Items(Item::Nodes(n_1), Item::Nodes(n_2)));
+			Ordering::Equal
+		}
+		(Node::Keyed(p_1), Node::Keyed(p_2)) => {
+			stack.push(CmpWork::Items(
+				Item::ReorderableFragments(p_1),
+				Item::ReorderableFragments(p_2),
+			));
+			Ordering::Equal
+		}
+		(
+			Node::Text {
+				text: t_1,
+				dom_binding: db_1,
+			},
+			Node::Text {
+				text: t_2,
+				dom_binding: db_2,
+			},
+		) => t_1.cmp(t_2).then_with(|| db_1.cmp(db_2)),
+		(Node::RawHtml(h_1), Node::RawHtml(h_2)) => h_1.cmp(h_2),
+		(Node::Portal(p_1), Node::Portal(p_2)) => {
+			stack.push(CmpWork::Items(Item::Portal(p_1), Item::Portal(p_2)));
+			Ordering::Equal
+		}
+		(Node::RemnantSite(rs_1), Node::RemnantSite(rs_2)) => rs_1.cmp(rs_2),
+		(a, b) => variant_index(a).cmp(&variant_index(b)),
+	}
+}
+
+/// The position of `node`'s variant in the [`Ord`] of distinct [`Node`] variants.
+fn variant_index<S: ThreadSafety>(node: &Node<'_, S>) -> u8 {
+	match node {
+		Node::Comment { .. } => 0,
+		Node::HtmlElement { .. } => 1,
+		Node::MathMlElement { .. } => 2,
+		Node::SvgElement { .. } => 3,
+		Node::Memoized { .. } => 4,
+		Node::Multi(_) => 5,
+		Node::Keyed(_) => 6,
+		Node::Text { .. } => 7,
+		Node::RawHtml(_) => 8,
+		Node::Portal(_) => 9,
+		Node::HeadContribution { .. } => 10,
+		Node::RemnantSite(_) => 11,
+	}
+}
+
+fn hash<S: ThreadSafety, H: Hasher>(item: Item<'_, S>, state: &mut H) {
+	let mut stack = vec![item];
+	while let Some(item) = stack.pop() {
+		let start = stack.len();
+		match item {
+			Item::Document(document) => {
+				document.doctype.hash(state);
+				document.html_attributes.hash(state);
+				stack.push(Item::Node(&document.head));
+				stack.push(Item::Node(&document.body));
+			}
+			Item::Element(element) => {
+				element.name.hash(state);
+				element.creation_options.hash(state);
+				element.attributes.hash(state);
+				element.properties.hash(state);
+				crate::ergonomics::unordered::hash(element.classes, state);
+				crate::ergonomics::unordered::hash(element.styles, state);
+				element.event_bindings.hash(state);
+				stack.push(Item::Node(&element.content));
+				stack.push(Item::OptionShadowRoot(element.shadow_root));
+			}
+			Item::Nodes(nodes) => {
+				state.write_usize(nodes.len());
+				stack.extend(nodes.iter().map(Item::Node));
+			}
+			Item::Portal(portal) => {
+				portal.target.hash(state);
+				portal.dom_binding.hash(state);
+				stack.push(Item::Node(&portal.content));
+			}
+			Item::ReorderableFragment(fragment) => {
+				fragment.dom_key.hash(state);
+				stack.push(Item::Node(&fragment.content));
+			}
+			Item::ReorderableFragments(fragments) => {
+				state.write_usize(fragments.len());
+				stack.extend(fragments.iter().map(Item::ReorderableFragment));
+			}
+			Item::ShadowRoot(shadow_root) => {
+				shadow_root.options.hash(state);
+				shadow_root.dom_binding.hash(state);
+				stack.push(Item::Node(&shadow_root.content));
+			}
+			Item::OptionShadowRoot(shadow_root) => {
+				// Hashes the discriminant just like `Option<&ShadowRoot>`.
+				shadow_root.map(|_| ()).hash(state);
+				stack.extend(shadow_root.map(Item::ShadowRoot));
+			}
+			Item::Node(node) => match node {
+				Node::Comment {
+					comment,
+					dom_binding,
+				} => {
+					comment.hash(state);
+					dom_binding.hash(state);
+				}
+				Node::HtmlElement {
+					element,
+					dom_binding,
+				} => {
+					dom_binding.hash(state);
+					stack.push(Item::Element(element));
+				}
+				Node::MathMlElement {
+					element,
+					dom_binding,
+				} => {
+					dom_binding.hash(state);
+					stack.push(Item::Element(element));
+				}
+				Node::SvgElement {
+					element,
+					dom_binding,
+				} => {
+					dom_binding.hash(state);
+					stack.push(Item::Element(element));
+				}
+				Node::HeadContribution {
+					element,
+					dom_binding,
+				} => {
+					dom_binding.hash(state);
+					stack.push(Item::Element(element));
+				}
+				Node::Memoized { state_key, .. } => state_key.hash(state),
+				Node::Multi(nodes) => stack.push(Item::Nodes(nodes)),
+				Node::Keyed(pairs) => stack.push(Item::ReorderableFragments(pairs)),
+				Node::Text { text, dom_binding } => {
+					text.hash(state);
+					dom_binding.hash(state);
+				}
+				Node::RawHtml(html) => html.hash(state),
+				Node::Portal(portal) => stack.push(Item::Portal(portal)),
+				Node::RemnantSite(remnant_site) => remnant_site.hash(state),
+			},
+		}
+		stack[start..].reverse();
+	}
+}
+
+/// A formatting step.
+enum DebugWork<'a, S: ThreadSafety> {
+	Str(&'static str),
+	Leaf(&'a dyn Debug),
+	Item(Item<'a, S>),
+	Indent,
+	Outdent,
+}
+#[allow(clippy::expl_impl_clone_on_copy)]
+impl<'a, S: ThreadSafety> Clone for DebugWork<'a, S> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<'a, S: ThreadSafety> Copy for DebugWork<'a, S> {}
+
+/// Equivalent to the nested indentation adapters [`Formatter`]'s helpers use with [`Formatter::alternate`].
+struct Indented<'a, 'b> {
+	f: &'a mut Formatter<'b>,
+	depth: usize,
+	on_newline: bool,
+}
+impl Write for Indented<'_, '_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for line in s.split_inclusive('\n') {
+			if self.on_newline {
+				for _ in 0..self.depth {
+					self.f.write_str("    ")?;
+				}
+			}
+			self.on_newline = line.ends_with('\n');
+			self.f.write_str(line)?;
+		}
+		Ok(())
+	}
+}
+
+fn debug<S: ThreadSafety>(item: Item<'_, S>, f: &mut Formatter<'_>) -> fmt::Result {
+	let pretty = f.alternate();
+	let mut stack = vec![DebugWork::Item(item)];
+	let mut f = Indented {
+		f,
+		depth: 0,
+		on_newline: false,
+	};
+	while let Some(work) = stack.pop() {
+		match work {
+			DebugWork::Str(str) => f.write_str(str)?,
+			DebugWork::Leaf(leaf) if pretty => write!(f, "{:#?}", leaf)?,
+			DebugWork::Leaf(leaf) => write!(f, "{:?}", leaf)?,
+			DebugWork::Item(item) => {
+				let start = stack.len();
+				debug_layout(
+					item,
+					&mut Layout {
+						stack: &mut stack,
+						pretty,
+					},
+				);
+				stack[start..].reverse();
+			}
+			DebugWork::Indent => f.depth += 1,
+			DebugWork::Outdent => f.depth -= 1,
+		}
+	}
+	Ok(())
+}
+
+/// Mirrors [`Formatter::debug_struct`], [`Formatter::debug_tuple`] and [`Formatter::debug_list`] as [`DebugWork`].
+struct Layout<'s, 'a, S: ThreadSafety> {
+	stack: &'s mut Vec<DebugWork<'a, S>>,
+	pretty: bool,
+}
+impl<'s, 'a, S: ThreadSafety> Layout<'s, 'a, S> {
+	fn structure(&mut self, name: &'static str, fields: &[(&'static str, DebugWork<'a, S>)]) {
+		self.stack.push(DebugWork::Str(name));
+		if self.pretty {
+			self.stack
+				.extend([DebugWork::Str(" {\n"), DebugWork::Indent]);
+			for &(field, value) in fields {
+				self.stack.extend([
+					DebugWork::Str(field),
+					DebugWork::Str(": "),
+					value,
+					DebugWork::Str(",\n"),
+				]);
+			}
+			self.stack.extend([DebugWork::Outdent, DebugWork::Str("}")]);
+		} else {
+			for (i, &(field, value)) in fields.iter().enumerate() {
+				self.stack.extend([
+					DebugWork::Str(if i == 0 { " { " } else { ", " }),
+					DebugWork::Str(field),
+					DebugWork::Str(": "),
+					value,
+				]);
+			}
+			self.stack.push(DebugWork::Str(" }"));
+		}
+	}
+
+	fn tuple(&mut self, name: &'static str, field: DebugWork<'a, S>) {
+		if self.pretty {
+			self.stack.extend([
+				DebugWork::Str(name),
+				DebugWork::Str("(\n"),
+				DebugWork::Indent,
+				field,
+				DebugWork::Str(",\n"),
+				DebugWork::Outdent,
+				DebugWork::Str(")"),
+			]);
+		} else {
+			self.stack.extend([
+				DebugWork::Str(name),
+				DebugWork::Str("("),
+				field,
+				DebugWork::Str(")"),
+			]);
+		}
+	}
+
+	fn list(&mut self, entries: impl ExactSizeIterator<Item = Item<'a, S>>) {
+		if entries.len() == 0 {
+			self.stack.push(DebugWork::Str("[]"));
+		} else if self.pretty {
+			self.stack
+				.extend([DebugWork::Str("[\n"), DebugWork::Indent]);
+			for entry in entries {
+				self.stack
+					.extend([DebugWork::Item(entry), DebugWork::Str(",\n")]);
+			}
+			self.stack.extend([DebugWork::Outdent, DebugWork::Str("]")]);
+		} else {
+			self.stack.push(DebugWork::Str("["));
+			for (i, entry) in entries.enumerate() {
+				if i > 0 {
+					self.stack.push(DebugWork::Str(", "));
+				}
+				self.stack.push(DebugWork::Item(entry));
+			}
+			self.stack.push(DebugWork::Str("]"));
+		}
+	}
+}
+
+/// Pushes the formatting steps for `item` onto `layout` in order.
+#[allow(clippy::too_many_lines)] // One arm per variant.
+fn debug_layout<'a, S: ThreadSafety>(item: Item<'a, S>, layout: &mut Layout<'_, 'a, S>) {
+	use DebugWork::{Item as I, Leaf as L};
+
+	match item {
+		Item::Document(document) => layout.structure(
+			"Document",
+			&[
+				("doctype", L(&document.doctype)),
+				("html_attributes", L(&document.html_attributes)),
+				("head", I(Item::Node(&document.head))),
+				("body", I(Item::Node(&document.body))),
+			],
+		),
+		Item::Element(element) => layout.structure(
+			"Element",
+			&[
+				("name", L(&element.name)),
+				("creation_options", L(&element.creation_options)),
+				("attributes", L(&element.attributes)),
+				("properties", L(&element.properties)),
+				("classes", L(&element.classes)),
+				("styles", L(&element.styles)),
+				("event_bindings", L(&element.event_bindings)),
+				("content", I(Item::Node(&element.content))),
+				(
+					"shadow_root",
+					I(Item::OptionShadowRoot(element.shadow_root)),
+				),
+			],
+		),
+		Item::Nodes(nodes) => layout.list(nodes.iter().map(Item::Node)),
+		Item::Portal(portal) => layout.structure(
+			"Portal",
+			&[
+				("target", L(&portal.target)),
+				("dom_binding", L(&portal.dom_binding)),
+				("content", I(Item::Node(&portal.content))),
+			],
+		),
+		Item::ReorderableFragment(fragment) => layout.structure(
+			"ReorderableFragment",
+			&[
+				("dom_key", L(&fragment.dom_key)),
+				("content", I(Item::Node(&fragment.content))),
+			],
+		),
+		Item::ReorderableFragments(fragments) => {
+			layout.list(fragments.iter().map(Item::ReorderableFragment))
+		}
+		Item::ShadowRoot(shadow_root) => layout.structure(
+			"ShadowRoot",
+			&[
+				("options", L(&shadow_root.options)),
+				("dom_binding", L(&shadow_root.dom_binding)),
+				("content", I(Item::Node(&shadow_root.content))),
+			],
+		),
+		Item::OptionShadowRoot(None) => layout.stack.push(DebugWork::Str("None")),
+		Item::OptionShadowRoot(Some(shadow_root)) => {
+			layout.tuple("Some", I(Item::ShadowRoot(shadow_root)))
+		}
+		Item::Node(node) => match node {
+			Node::Comment {
+				comment,
+				dom_binding,
+			} => layout.structure(
+				"Node::Comment",
+				&[("comment", L(comment)), ("dom_binding", L(dom_binding))],
+			),
+			Node::HtmlElement {
+				element,
+				dom_binding,
+			} => layout.structure(
+				"Node::HtmlElement",
+				&[
+					("element", I(Item::Element(element))),
+					("dom_binding", L(dom_binding)),
+				],
+			),
+			Node::MathMlElement {
+				element,
+				dom_binding,
+			} => layout.structure(
+				"Node::HtmlElement", // Matches the recursive implementation.
+				&[
+					("element", I(Item::Element(element))),
+					("dom_binding", L(dom_binding)),
+				],
+			),
+			Node::SvgElement {
+				element,
+				dom_binding,
+			} => layout.structure(
+				"Node::SvgElement",
+				&[
+					("element", I(Item::Element(element))),
+					("dom_binding", L(dom_binding)),
+				],
+			),
+			Node::Memoized { state_key, content } => layout.structure(
+				"Node::Memoized",
+				&[
+					("state_key", L(state_key)),
+					("content", I(Item::Node(content))),
+				],
+			),
+			Node::Multi(nodes) => layout.tuple("Node::Multi", I(Item::Nodes(nodes))),
+			Node::Keyed(fragments) => {
+				layout.tuple("Node::Keyed", I(Item::ReorderableFragments(fragments)))
+			}
+			Node::Text { text, dom_binding } => layout.structure(
+				"Node::Text",
+				&[("text", L(text)), ("dom_binding", L(dom_binding))],
+			),
+			Node::RawHtml(html) => layout.tuple("Node::RawHtml", L(html)),
+			Node::Portal(portal) => layout.tuple("Node::Portal", I(Item::Portal(portal))),
+			Node::HeadContribution {
+				element,
+				dom_binding,
+			} => layout.structure(
+				"Node::HeadContribution",
+				&[
+					("element", I(Item::Element(element))),
+					("dom_binding", L(dom_binding)),
+				],
+			),
+			Node::RemnantSite(remnant_site) => layout.tuple("Node::RemnantSite", L(remnant_site)),
+		},
+	}
+}
+
+/// See [`Node::dom_len`].
+pub fn dom_len<S: ThreadSafety>(node: &Node<'_, S>) -> usize {
+	let mut len = 0;
+	let mut stack = vec![node];
+	while let Some(node) = stack.pop() {
+		match node {
+			Node::Comment { .. }
+			| Node::HtmlElement { .. }
+			| Node::MathMlElement { .. }
+			| Node::SvgElement { .. }
+			| Node::Text { .. } => len += 1,
+			Node::Memoized { content, .. } => stack.push(content),
+			Node::Multi(nodes) => stack.extend(nodes.iter()),
+			Node::Keyed(pairs) => stack.extend(pairs.iter().map(|pair| &pair.content)),
+			Node::RawHtml(_)
+			| Node::Portal(_)
+			| Node::HeadContribution { .. }
+			| Node::RemnantSite(_) => (),
+		}
+	}
+	len
+}
+
+/// See [`Node::dom_empty`].
+pub fn dom_empty<S: ThreadSafety>(node: &Node<'_, S>) -> bool {
+	let mut stack = vec![node];
+	while let Some(node) = stack.pop() {
+		match node {
+			Node::Comment { .. }
+			| Node::HtmlElement { .. }
+			| Node::MathMlElement { .. }
+			| Node::SvgElement { .. }
+			| Node::Text { .. } => return false,
+			Node::Memoized { content, .. } => stack.push(content),
+			Node::Multi(nodes) => stack.extend(nodes.iter()),
+			Node::Keyed(pairs) => stack.extend(pairs.iter().map(|pair| &pair.content)),
+			Node::RawHtml(_)
+			| Node::Portal(_)
+			| Node::HeadContribution { .. }
+			| Node::RemnantSite(_) => (),
+		}
+	}
+	true
+}
@@ -58,6 +58,14 @@
 //!
 //! # Features
 //!
+//! ## `"alloc"`
+//!
+//! Makes the [`Debug`](`core::fmt::Debug`), [`PartialEq`], [`Hash`](`core::hash::Hash`) and [`Ord`] implementations of VDOM types
+//! as well as [`Node::dom_len`] and [`Node::dom_empty`] iterative, with an explicit stack on the heap instead of recursion.
+//! Requires [`alloc`](https://doc.rust-lang.org/stable/alloc/index.html).
+//!
+//! Enable this feature if your app may generate deeply nested VDOM, as WebAssembly's call stack is fairly small by default.
+//!
 //! ## `"callbacks"`
 //!
 //! Enables DOM callback support. Requires [`std`](https://doc.rust-lang.org/stable/std/index.html).
@@ -66,6 +74,21 @@
 //!
 //! Always test VDOM generators with the `"callbacks"` feature enabled if they make use of them at all, but only depend on it in order to *invoke* callbacks.
 //!
+//! ## `"bumpalo"`
+//!
+//! Implements [`arena::Arena`] for [`bumpalo::Bump`](https://docs.rs/bumpalo/3/bumpalo/struct.Bump.html)
+//! and enables the `frames` module, which manages double-buffered arenas across VDOM iterations.
+//!
+//! ## `"component"`
+//!
+//! Enables the `component` module, which contains a `Component` trait and a render scheduler for frameworks to build on.
+//! Implies `"bumpalo"` and requires [`alloc`](https://doc.rust-lang.org/stable/alloc/index.html).
+//!
+//! ## `"diff"`
+//!
+//! Enables the `diff` module, which diffs VDOM graphs in resumable time slices and buffers the resulting patches until they can be committed at once.
+//! Requires [`alloc`](https://doc.rust-lang.org/stable/alloc/index.html).
+//!
 //! # Notes on Performance
 //!
 //! ## Clone
@@ -78,6 +101,8 @@
 //! [`Hash`](`core::hash::Hash`) is implemented recursively in this crate and is potentially expensive.
 //! The same applies to [`PartialEq`], [`Eq`], [`PartialOrd`] and [`Ord`].
 //!
+//! These implementations recurse on the call stack unless the `"alloc"` feature is enabled.
+//!
 //! As an exception, [`Node::Memoized`] instances are compared only by their [`state_key`](`Node::Memoized::state_key`).
 //! Their [`content`](`Node::Memoized::content`) is ignored for comparisons and does not factor into their [hash](`core::hash`).
 //!
@@ -93,6 +118,8 @@
 //! >
 //! > The implementation itself would be quite error-prone on types that are [`Copy`] due to implicit by-value copies there. Proceed with caution if you must!
 //!
+//! To point at a specific [`Node`] anyway, for example in diagnostics, use a structural [`path::NodePath`].
+//!
 //! Element and attribute names are always plain `&str`s, which isn't ideal for software that renders its GUI more directly than through a web browser.
 //! I'm open to maintaining a generic fork if there's interest in this regard.
 //!
@@ -127,23 +154,46 @@
 //! Implied elements are also still present in the browser DOM, even if both their start and end tag have been omitted.
 //!
 //! As such, these elements should normally be explicit in the VDOM.
+//! For complete pages, [`Document`] models `<html>`, `<head>` and `<body>` explicitly.
 //! HTML renderers may omit tags from the serialised document or fragment [according to the HTML specification](https://html.spec.whatwg.org/multipage/syntax.html#optional-tags).
 #[cfg(doctest)]
 #[doc = include_str!("../README.md")]
 mod readme {}
 
+pub mod arena;
 pub mod auto_safety;
 pub mod callback_registry;
+#[cfg(feature = "component")]
+pub mod component;
+#[cfg(feature = "diff")]
+pub mod diff;
+pub mod events;
+#[cfg(feature = "bumpalo")]
+pub mod frames;
+pub mod head;
+pub mod html;
+pub mod memo;
+pub mod path;
 mod remnants;
+pub mod stable_hash;
+pub mod strip;
 pub mod web;
+pub mod xml;
 
 use callback_registry::CallbackSignature;
 pub use callback_registry::{CallbackRef, CallbackRegistration};
 pub use web::{DomRef, Materialize};
 
 mod ergonomics;
+#[cfg(feature = "alloc")]
+mod iterative;
 
-use core::{convert::Infallible, fmt::Debug, hash::Hash, marker::PhantomData};
+use core::{
+	convert::Infallible,
+	fmt::{self, Debug},
+	hash::Hash,
+	marker::PhantomData,
+};
 use remnants::RemnantSite;
 use sealed::Sealed;
 
@@ -240,13 +290,14 @@ pub enum Node<'a, S: ThreadSafety> {
 	/// > However, this often happens with matching or near-matching fragments during hydration of a web app.
 	/// >
 	/// > *If you already have a function to strip subscriptions* (e.g. [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node) reference bindings) from a DOM and VDOM tree,
-	/// > or even just one to strip all callbacks (but this is less efficient), it's likely more efficient to do so and then recurse.
+	/// > or even just one to strip all callbacks (like [`Node::strip_callbacks`], but this is less efficient), it's likely more efficient to do so and then recurse.
 	/// >
 	/// > Make sure the trees are actually somewhat compatible first, or you may end up processing the old VDOM twice for nothing.
 	Memoized {
 		/// A value that's (very likely to be) distinct between VDOM graphs where the path of two [`Node::Memoized`] instances matches but their [`Node::Memoized::content`] is distinct.
 		///
 		/// Consider using a (good enough) hash of [`content`](`Node::Memoized::content`) for this purpose.
+		/// [`Node::stable_hash`] calculates one that's consistent between server and client.
 		state_key: u64,
 		/// The VDOM tree memoized by this [`Node`].
 		content: &'a Node<'a, S>,
@@ -357,12 +408,90 @@ pub enum Node<'a, S: ThreadSafety> {
 		///
 		/// Live components also have the option of using for example [`Node::HtmlElement::dom_binding`] to set [***Element.innerHTML***](https://developer.mozilla.org/en-US/docs/Web/API/Element/innerHTML),
 		/// but this is not recommended due to the difficulty of implementing allow-listing with such an approach.
+		/// Prefer [`Node::RawHtml`] for markup from trusted sources.
 		text: &'a str,
 		/// Registers for [***Text***](https://developer.mozilla.org/en-US/docs/Web/API/Text) reference updates.
 		///
 		/// See [`DomRef`] for more information.
 		dom_binding: Option<CallbackRef<S, fn(dom_ref: DomRef<&'_ web::Text>)>>,
 	},
+	/// Represents an opaque fragment of raw HTML, like CMS- or Markdown-produced markup, as the entire content of an [`Element`] or [`ShadowRoot`].
+	///
+	/// Raw HTML is opt-in twice: Producers have to vouch for the markup via [`TrustedHtml::assume_trusted`],
+	/// and renderers only accept it if the app supplied them with a [`Sanitizer`].
+	///
+	/// # Implementation Contract
+	///
+	/// > **This is not a soundness contract**. Code using this crate must not rely on it for soundness.
+	/// > However, it is free to panic when encountering an incorrect implementation.
+	///
+	/// This variant **should** only appear as (optionally [`Memoized`](`Node::Memoized`)) [`Element::content`] or [`ShadowRoot::content`].
+	/// Renderers may refuse it in any other position.
+	///
+	/// The resulting [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node)s are opaque to the VDOM:
+	/// [`Node::dom_len`] counts zero for this variant and the host's child list is owned by the fragment.
+	///
+	/// ## **Security**
+	///
+	/// Renderers **must** pass the fragment through a [`Sanitizer`] supplied by the app and use only its output.
+	/// Renderers without a [`Sanitizer`] **must** refuse this variant.
+	///
+	/// ## For VDOM-to-DOM renderers:
+	///
+	/// Renderers set the sanitised markup as [***Element.innerHTML***](https://developer.mozilla.org/en-US/docs/Web/API/Element/innerHTML)
+	/// (or [***ShadowRoot.innerHTML***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/innerHTML)) of the host.
+	///
+	/// When diffing, renderers **must** replace the host's children iff the [`TrustedHtml`] changed by value (or the host was recreated),
+	/// and **must not** otherwise inspect or modify them.
+	///
+	/// When hydrating, renderers **should** adopt the host's existing children as-is, without sanitising the fragment again.
+	/// This is correct as long as the server used an equivalent [`Sanitizer`], and avoids a visible re-render.
+	///
+	/// ## For HTML renderers:
+	///
+	/// The sanitised markup is written verbatim in place of the host's children. See [`html::Serializer::with_sanitizer`].
+	RawHtml(TrustedHtml<'a>),
+	/// Renders its content into a different DOM container, like an overlay root for modals, tooltips and toasts.
+	///
+	/// See [`Portal`] for more information.
+	Portal(&'a Portal<'a, S>),
+	/// Contributes an [***HTMLElement***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement) like `<title>`, `<meta>` or `<link rel=preload>`
+	/// to the document's [***head***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/head), from anywhere in the VDOM.
+	///
+	/// # Implementation Contract
+	///
+	/// > **This is not a soundness contract**. Code using this crate must not rely on it for soundness.
+	/// > However, it is free to panic when encountering an incorrect implementation.
+	///
+	/// Like [`Node::Portal`], this variant counts zero towards [`Node::dom_len`] at its logical position.
+	///
+	/// Contributions are deduplicated by their [`head::HeadKey`]: Of contributions with equal keys, only the last one in VDOM order is rendered.
+	/// Contributions without key are all rendered. [`head::for_each_contribution`] implements these rules.
+	///
+	/// `element` **should** be [metadata content](https://html.spec.whatwg.org/multipage/dom.html#metadata-content-2).
+	///
+	/// ## For VDOM-to-DOM renderers:
+	///
+	/// Renderers append the deduplicated contributions from all VDOM they manage to [***Document.head***](https://developer.mozilla.org/en-US/docs/Web/API/Document/head),
+	/// after any [`Document::head`] content, and **must not** modify other children of the head.
+	///
+	/// When diffing, contributions with equal [`head::HeadKey`] are path-matching and **should** be updated in place.
+	/// Contributions without key are matched by their order.
+	///
+	/// `dom_binding` is handled as for [`Node::HtmlElement`], with the callback order following the deduplicated contribution order.
+	/// Superseded contributions are not rendered, so their `dom_binding` is never called.
+	///
+	/// ## For HTML renderers:
+	///
+	/// Contributions write nothing at their logical position. See [`html::Serializer::write_head_contributions`].
+	HeadContribution {
+		/// The [`Element`] to render into the head.
+		element: &'a Element<'a, S>,
+		/// Registers for [***HTMLElement***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement) reference updates.
+		///
+		/// See [`DomRef`] for more information.
+		dom_binding: Option<CallbackRef<S, fn(dom_ref: DomRef<&'_ web::HtmlElement>)>>,
+	},
 	/// Currently unused.
 	///
 	/// The plan here is to allow fragments to linger in the DOM after being diffed out, which seems like the most economical way to enable e.g. fade-out animations.
@@ -370,6 +499,175 @@ pub enum Node<'a, S: ThreadSafety> {
 	RemnantSite(&'a RemnantSite),
 }
 
+/// [`Vdom`] Represents a complete HTML document as `doctype`, `html_attributes`, `head` and `body`.
+///
+/// Unlike a [`Node`] graph, this makes the otherwise implied [***html***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/html),
+/// [***head***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/head) and [***body***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/body) elements explicit.
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// [`Document::head`] **should** only contain [metadata content](https://html.spec.whatwg.org/multipage/dom.html#metadata-content-2)
+/// (`<base>`, `<link>`, `<meta>`, `<noscript>`, `<script>`, `<style>`, `<template>` and `<title>` elements), comments and inter-element whitespace.
+///
+/// ## For VDOM-to-DOM renderers:
+///
+/// Renderers diff [`Document::html_attributes`] against [***Document.documentElement***](https://developer.mozilla.org/en-US/docs/Web/API/Document/documentElement),
+/// [`Document::head`] against the children of [***Document.head***](https://developer.mozilla.org/en-US/docs/Web/API/Document/head)
+/// and [`Document::body`] against the children of [***Document.body***](https://developer.mozilla.org/en-US/docs/Web/API/Document/body),
+/// but only as far as their [`DocumentScope`] says. [`Document::doctype`] is never updated.
+///
+/// ## For HTML renderers:
+///
+/// See [`html::Serializer::write_document`].
+pub struct Document<'a, S: ThreadSafety> {
+	/// The [***DocumentType.name***](https://developer.mozilla.org/en-US/docs/Web/API/DocumentType/name), which is `"html"` for all modern HTML documents.
+	///
+	/// # Implementation Contract
+	///
+	/// ## Security
+	///
+	/// The same restrictions as on [`Attribute::name`] apply.
+	pub doctype: &'a str,
+	/// The [`Attribute`]s of the [***html***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/html) element, like `lang`.
+	pub html_attributes: &'a [Attribute<'a>],
+	/// The content of the [***head***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/head) element.
+	pub head: Node<'a, S>,
+	/// The content of the [***body***](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/body) element.
+	pub body: Node<'a, S>,
+}
+
+/// Selects which parts of a [`Document`] a VDOM-to-DOM renderer manages.
+///
+/// Renderers that accept [`Document`]s **should** let apps choose this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DocumentScope {
+	/// The renderer manages [`Document::html_attributes`], [`Document::head`] and [`Document::body`].
+	Full,
+	/// The renderer manages only [`Document::head`], for example next to a separate renderer mounted in `<body>`.
+	///
+	/// The renderer **must not** modify [***Document.body***](https://developer.mozilla.org/en-US/docs/Web/API/Document/body)
+	/// or the attributes of [***Document.documentElement***](https://developer.mozilla.org/en-US/docs/Web/API/Document/documentElement).
+	/// [`Document::body`] is ignored entirely, including any callbacks in it.
+	HeadOnly,
+}
+
+/// [`Vdom`] Represents a subtree that lives logically at the position of a [`Node::Portal`], but is rendered into a different DOM container.
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// [`Portal::content`] doesn't exist at the portal's logical position: [`Node::dom_len`] counts zero for [`Node::Portal`].
+///
+/// Portals are otherwise transparent. Their content is processed where the [`Node::Portal`] appears in the VDOM, as if it was there,
+/// which includes [`DomRef`] callback order relative to surrounding [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node)s.
+///
+/// ## For VDOM-to-DOM renderers:
+///
+/// Renderers resolve [`Portal::target`] through a lookup they supply, for example by `id` or from a set of containers the app registered with the renderer.
+/// Renderers **should** fail loudly if a target can't be resolved.
+///
+/// Portal content is appended to the container's children, in the order the portals targeting it appear in the VDOM.
+/// Renderers **must not** modify other children of the container.
+///
+/// Any [`dom_binding`](`Portal::dom_binding`) is [`Added`](`DomRef::Added`) with the container after the portal's content has been rendered into it,
+/// and [`Removing`](`DomRef::Removing`) before the content is removed. This matches the order of [***Element***](https://developer.mozilla.org/en-US/docs/Web/API/Element) references and their children.
+///
+/// [`Portal::target`]s are compared by value. If the target of a path-matching portal changes, renderers **must** behave as if the portal was removed
+/// and a new one inserted instead: The old content is torn down (including [`DomRef::Removing`] calls) and removed from the old container,
+/// before the new content is rendered into the new container.
+///
+/// ## For HTML renderers:
+///
+/// Portals write nothing at their logical position.
+/// Renderers collect their content per target instead, as with [`html::Serializer::write_portals`].
+pub struct Portal<'a, S: ThreadSafety> {
+	/// The name of the target container.
+	///
+	/// What this means depends on the renderer's lookup, but an element ID like `"overlay-root"` is a good default.
+	pub target: &'a str,
+	/// The [`Node`] to render into the target container.
+	pub content: Node<'a, S>,
+	/// Registers for target container reference updates.
+	///
+	/// See [`DomRef`] for more information.
+	pub dom_binding: Option<CallbackRef<S, fn(dom_ref: DomRef<&'_ web::Element>)>>,
+}
+
+/// [`Vdom`] A raw HTML fragment that a producer vouched for, for use in [`Node::RawHtml`].
+///
+/// This is only a marker for the intent to render markup, **not** a guarantee that it is safe to do so.
+/// Renderers still pass it through a [`Sanitizer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TrustedHtml<'a>(&'a str);
+#[allow(clippy::inline_always)] // Trivial constructor and getter.
+impl<'a> TrustedHtml<'a> {
+	/// Marks `html` as trusted to be rendered as markup (after sanitisation).
+	///
+	/// The name is intentionally verbose, so that this call stands out in code review.
+	#[inline(always)]
+	#[must_use]
+	pub const fn assume_trusted(html: &'a str) -> Self {
+		Self(html)
+	}
+
+	/// Retrieves the unsanitised markup.
+	#[inline(always)]
+	#[must_use]
+	pub const fn as_str(&self) -> &'a str {
+		self.0
+	}
+}
+
+/// App-supplied hook that renderers pass [`Node::RawHtml`] fragments through before rendering them.
+///
+/// This trait is implemented for matching closures.
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// The output **must** be a well-formed HTML fragment that's safe to render in any element, without unclosed elements or comments.
+///
+/// Implementations **should** be deterministic, as hydration relies on the server and client output being equivalent.
+///
+/// # Example
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use lignin::{Sanitizer, TrustedHtml};
+///
+/// // Allows only plain text, as a very strict example.
+/// let sanitizer = |html: TrustedHtml, output: &mut dyn Write| {
+///   if html.as_str().contains(|c| matches!(c, '<' | '&')) {
+///     return Err(core::fmt::Error);
+///   }
+///   output.write_str(html.as_str())
+/// };
+///
+/// let mut output = String::new();
+/// assert!(sanitizer.sanitize(TrustedHtml::assume_trusted("Hello!"), &mut output).is_ok());
+/// assert!(sanitizer.sanitize(TrustedHtml::assume_trusted("<script>"), &mut output).is_err());
+/// ```
+pub trait Sanitizer {
+	/// Writes a sanitised version of `html` to `output`.
+	///
+	/// # Errors
+	///
+	/// To refuse `html` entirely, or if writing to `output` fails.
+	fn sanitize(&self, html: TrustedHtml<'_>, output: &mut dyn fmt::Write) -> fmt::Result;
+}
+impl<F> Sanitizer for F
+where
+	F: Fn(TrustedHtml<'_>, &mut dyn fmt::Write) -> fmt::Result,
+{
+	fn sanitize(&self, html: TrustedHtml<'_>, output: &mut dyn fmt::Write) -> fmt::Result {
+		self(html, output)
+	}
+}
+
 /// [`Vdom`] A VDOM node that has its DOM identity preserved during DOM updates even after being repositioned within a (path-)matching [`Node::Keyed`].
 ///
 /// For more information, see [`Node::Keyed`].
@@ -383,7 +681,7 @@ pub struct ReorderableFragment<'a, S: ThreadSafety> {
 }
 
 #[allow(clippy::doc_markdown)]
-/// [`Vdom`] Represents a single [***HTMLElement***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement) as `name`, `attributes`, `content` and `event_bindings`.
+/// [`Vdom`] Represents a single [***HTMLElement***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement) as `name`, `attributes`, `properties`, `classes`, `styles`, `content`, `shadow_root` and `event_bindings`.
 pub struct Element<'a, S: ThreadSafety> {
 	/// The [***Element.tag_name***](https://developer.mozilla.org/en-US/docs/Web/API/Element/tagName).
 	///
@@ -398,14 +696,248 @@ pub struct Element<'a, S: ThreadSafety> {
 	///
 	/// Note that while this collection is unordered in the browser, reordering attributes will generally affect diffing performance.
 	pub attributes: &'a [Attribute<'a>],
+	/// DOM ***properties***, like [***HTMLInputElement.value***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLInputElement#properties).
+	///
+	/// See [`Property`] for diffing and serialisation semantics.
+	pub properties: &'a [Property<'a>],
+	/// Tokens of the [***Element.classList***](https://developer.mozilla.org/en-US/docs/Web/API/Element/classList).
+	///
+	/// Their order is irrelevant: [`Element`] comparisons and hashing treat this as multiset.
+	///
+	/// # Implementation Contract
+	///
+	/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+	///
+	/// Tokens **should** be non-empty and not contain ASCII whitespace.
+	///
+	/// An [`Element`] with any classes here **should not** also have a `class` [`Attribute`]. How renderers combine the two is unspecified.
+	///
+	/// ## For VDOM-to-DOM renderers:
+	///
+	/// Renderers **should** diff classes per token, via [***DOMTokenList.add()***](https://developer.mozilla.org/en-US/docs/Web/API/DOMTokenList/add)
+	/// and [***DOMTokenList.remove()***](https://developer.mozilla.org/en-US/docs/Web/API/DOMTokenList/remove).
+	///
+	/// ## For HTML renderers:
+	///
+	/// Classes are serialised as `class` attribute, like [`AttributeValue::Tokens`].
+	pub classes: &'a [&'a str],
+	/// Declarations of the [***HTMLElement.style***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/style).
+	///
+	/// Their order is irrelevant: [`Element`] comparisons and hashing treat this as multiset.
+	///
+	/// # Implementation Contract
+	///
+	/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+	///
+	/// Each [`StyleDeclaration::property`] **should** appear at most once. Otherwise, which of the declarations applies is unspecified.
+	///
+	/// An [`Element`] with any declarations here **should not** also have a `style` [`Attribute`]. How renderers combine the two is unspecified.
+	///
+	/// ## For VDOM-to-DOM renderers:
+	///
+	/// Renderers **should** diff declarations per property, via [***CSSStyleDeclaration.setProperty()***](https://developer.mozilla.org/en-US/docs/Web/API/CSSStyleDeclaration/setProperty)
+	/// and [***CSSStyleDeclaration.removeProperty()***](https://developer.mozilla.org/en-US/docs/Web/API/CSSStyleDeclaration/removeProperty).
+	///
+	/// ## For HTML renderers:
+	///
+	/// Declarations are serialised as `style` attribute, each formatted through [`StyleDeclaration`]'s [`Display`](`core::fmt::Display`) implementation and separated by `"; "`, then escaped like any other attribute value.
+	pub styles: &'a [StyleDeclaration<'a>],
 	/// Maps to [***Node.childNodes***](https://developer.mozilla.org/en-US/docs/Web/API/Node/childNodes).
+	///
+	/// These are the element's ***light DOM*** children. [`Element::shadow_root`] content is never part of them.
 	pub content: Node<'a, S>,
+	/// An optional [***ShadowRoot***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot) attached to this [`Element`].
+	///
+	/// See [`ShadowRoot`] for more information.
+	pub shadow_root: Option<&'a ShadowRoot<'a, S>>,
 	/// DOM event bindings requested by a component.
 	///
 	/// See [`EventBinding`] for more information.
 	pub event_bindings: &'a [EventBinding<'a, S>],
 }
 
+impl<'a, S: ThreadSafety> Element<'a, S> {
+	/// Creates a new [`Element`] with the given `name` and `content`,
+	/// default [`creation_options`](`Element::creation_options`) and otherwise empty.
+	///
+	/// Use struct update syntax to set further fields:
+	///
+	/// ```rust
+	/// use lignin::{Attribute, Element, Node, ThreadSafe};
+	///
+	/// let attributes = [Attribute::new("href", "#top".into())];
+	/// let element = Element::<ThreadSafe> {
+	///   attributes: &attributes,
+	///   ..Element::new("A", "Back to top".into())
+	/// };
+	/// ```
+	#[inline]
+	#[must_use]
+	pub fn new(name: &'a str, content: Node<'a, S>) -> Self {
+		Self {
+			name,
+			creation_options: ElementCreationOptions::new(),
+			attributes: &[],
+			properties: &[],
+			classes: &[],
+			styles: &[],
+			content,
+			shadow_root: None,
+			event_bindings: &[],
+		}
+	}
+}
+
+/// [`Vdom`] Represents a [***ShadowRoot***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot) as `options`, `content` and `dom_binding`.
+///
+/// Shadow content is rendered *instead of* the host's light DOM [`content`](`Element::content`), which is projected into its `<slot>`s.
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// [`ShadowRoot::content`] forms a separate DOM tree: It never contributes to [`Node::dom_len`] or [`Node::dom_empty`] of the host's parent,
+/// and the host's light DOM children do not include it.
+///
+/// ## For VDOM-to-DOM renderers:
+///
+/// Renderers **must** diff [`ShadowRoot::content`] only against the previous shadow content of the same host, never against light DOM children.
+///
+/// A shadow root can't be detached or reconfigured once attached. If [`ShadowRoot::options`] change or the shadow root is removed,
+/// renderers **must** recreate the host [`Element`] as if its name had changed.
+/// Only if a shadow root is added to a previously shadow-less host *may* the renderer call [***Element.attachShadow()***](https://developer.mozilla.org/en-US/docs/Web/API/Element/attachShadow) on the existing element instead.
+///
+/// [`dom_binding`](`ShadowRoot::dom_binding`) is [`Added`](`DomRef::Added`) after the shadow content was rendered and before the host's own [***Node***](https://developer.mozilla.org/en-US/docs/Web/API/Node) reference binding,
+/// and [`Removing`](`DomRef::Removing`) in the opposite order.
+///
+/// ## For HTML renderers:
+///
+/// Shadow roots are serialised as [declarative shadow root](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template#shadowrootmode),
+/// that is a `<template shadowrootmode="open">` or `<template shadowrootmode="closed">` element that's the host's first child
+/// and contains [`ShadowRoot::content`]. [`ShadowRootOptions::delegates_focus`] adds the `shadowrootdelegatesfocus` attribute.
+///
+/// [`SlotAssignmentMode::Manual`] can't be expressed declaratively and is applied only once hydrated.
+///
+/// See also [`html::write_element`].
+pub struct ShadowRoot<'a, S: ThreadSafety> {
+	/// Controls the ***options*** parameter of [***Element.attachShadow()***](https://developer.mozilla.org/en-US/docs/Web/API/Element/attachShadow).
+	pub options: ShadowRootOptions,
+	/// The shadow tree's content, as children of the [***ShadowRoot***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot).
+	pub content: Node<'a, S>,
+	/// Registers for [***ShadowRoot***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot) reference updates.
+	///
+	/// This is the only way to get hold of a `"closed"` shadow root.
+	///
+	/// See [`DomRef`] for more information.
+	pub dom_binding: Option<CallbackRef<S, fn(dom_ref: DomRef<&'_ web::ShadowRoot>)>>,
+}
+
+/// [`Vdom`] Maps to the ***options*** parameter of [***Element.attachShadow()***](https://developer.mozilla.org/en-US/docs/Web/API/Element/attachShadow).
+///
+/// The [`Default`] is an `"open"` shadow root without focus delegation and with `"named"` slot assignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShadowRootOptions {
+	mode: ShadowRootMode,
+	delegates_focus: bool,
+	slot_assignment: SlotAssignmentMode,
+}
+impl Default for ShadowRootOptions {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+#[allow(clippy::inline_always)] // Trivial getters and setters.
+impl ShadowRootOptions {
+	/// Creates a new [`ShadowRootOptions`] instance for an `"open"` shadow root that doesn't delegate focus and uses `"named"` slot assignment.
+	#[inline(always)]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			mode: ShadowRootMode::Open,
+			delegates_focus: false,
+			slot_assignment: SlotAssignmentMode::Named,
+		}
+	}
+
+	/// Retrieves the [***mode***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/mode).
+	#[inline(always)]
+	#[must_use]
+	pub const fn mode(&self) -> ShadowRootMode {
+		self.mode
+	}
+	/// Sets the [***mode***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/mode).
+	#[inline(always)]
+	pub fn set_mode(&mut self, mode: ShadowRootMode) {
+		self.mode = mode
+	}
+	/// Sets the [***mode***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/mode).
+	#[inline(always)]
+	#[must_use]
+	pub const fn with_mode(self, mode: ShadowRootMode) -> Self {
+		Self { mode, ..self }
+	}
+
+	/// Retrieves whether the shadow root [***delegatesFocus***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/delegatesFocus).
+	#[inline(always)]
+	#[must_use]
+	pub const fn delegates_focus(&self) -> bool {
+		self.delegates_focus
+	}
+	/// Sets whether the shadow root [***delegatesFocus***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/delegatesFocus).
+	#[inline(always)]
+	pub fn set_delegates_focus(&mut self, delegates_focus: bool) {
+		self.delegates_focus = delegates_focus
+	}
+	/// Sets whether the shadow root [***delegatesFocus***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/delegatesFocus).
+	#[inline(always)]
+	#[must_use]
+	pub const fn with_delegates_focus(self, delegates_focus: bool) -> Self {
+		Self {
+			delegates_focus,
+			..self
+		}
+	}
+
+	/// Retrieves the [***slotAssignment***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/slotAssignment) mode.
+	#[inline(always)]
+	#[must_use]
+	pub const fn slot_assignment(&self) -> SlotAssignmentMode {
+		self.slot_assignment
+	}
+	/// Sets the [***slotAssignment***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/slotAssignment) mode.
+	#[inline(always)]
+	pub fn set_slot_assignment(&mut self, slot_assignment: SlotAssignmentMode) {
+		self.slot_assignment = slot_assignment
+	}
+	/// Sets the [***slotAssignment***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/slotAssignment) mode.
+	#[inline(always)]
+	#[must_use]
+	pub const fn with_slot_assignment(self, slot_assignment: SlotAssignmentMode) -> Self {
+		Self {
+			slot_assignment,
+			..self
+		}
+	}
+}
+
+/// The [***mode***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/mode) of a [`ShadowRoot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShadowRootMode {
+	/// `"open"`: The shadow root is accessible through [***Element.shadowRoot***](https://developer.mozilla.org/en-US/docs/Web/API/Element/shadowRoot).
+	Open,
+	/// `"closed"`: The shadow root is only accessible through [`ShadowRoot::dom_binding`].
+	Closed,
+}
+
+/// The [***slotAssignment***](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/slotAssignment) mode of a [`ShadowRoot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SlotAssignmentMode {
+	/// `"named"`: Light DOM children are assigned to `<slot>`s by their `slot` attribute.
+	Named,
+	/// `"manual"`: Light DOM children are assigned through [***HTMLSlotElement.assign()***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLSlotElement/assign).
+	Manual,
+}
+
 /// [`Vdom`] Maps to ***options*** parameter values of [***Document.createElement()***](https://developer.mozilla.org/en-US/docs/Web/API/Document/createElement)
 /// (including ***undefined***) *or* (currently only) the global [***is***](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/is) attribute.
 ///
@@ -485,7 +1017,10 @@ pub struct EventBinding<'a, S: ThreadSafety> {
 	/// The event name.
 	pub name: &'a str,
 	/// A callback reference created via [`CallbackRegistration`].
-	pub callback: CallbackRef<S, fn(event: web::Event)>,
+	///
+	/// The handler's [`EventOutcome`] tells the renderer or event delegation layer what to do with the event afterwards.  
+	/// Prefer [`EventBinding::call`] over calling this directly, as it checks the outcome against [`options`](`EventBinding::options`).
+	pub callback: CallbackRef<S, fn(event: web::Event) -> EventOutcome>,
 	/// Controls the ***options*** parameter of [***EventTarget.addEventListener()***](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener).
 	///
 	/// Note that [`EventBindingOptions`] is created with the [`EventBindingOptions.passive()`] flag already enabled!
@@ -619,7 +1154,202 @@ mod event_bindings_impl {
 	}
 }
 
+/// Returned by [`EventBinding`] handlers to decide what happens to the [`web::Event`] after it was handled.
+///
+/// [`EventOutcome::new()`] and [`Default::default()`] create an instance with all flags cleared,
+/// which means the event continues normally.
+///
+/// Like [`EventBindingOptions`], these flags aren't part of any soundness contract! Don't rely on them for memory safety.
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// ## For VDOM-to-DOM renderers and event delegation layers:
+///
+/// After invoking an [`EventBinding`], apply the returned flags to the [`web::Event`] in the order they are listed below.
+///
+/// If the [`EventBinding`] is [`passive`](`EventBindingOptions::passive`), the `prevent_default` flag **must** be ignored.
+/// [`EventBinding::call`] already takes care of this and additionally prints a diagnostic in debug builds.
+///
+/// # Flags
+///
+/// ## `prevent_default`
+///
+/// Requests a call to [***Event.preventDefault()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault).
+///
+/// **This requires a non-[`passive`](`EventBindingOptions::passive`) [`EventBinding`].**
+///
+/// ## `stop_propagation`
+///
+/// Requests a call to [***Event.stopPropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopPropagation).
+///
+/// ## `stop_immediate_propagation`
+///
+/// Requests a call to [***Event.stopImmediatePropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopImmediatePropagation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EventOutcome(u8);
+mod event_outcome_impl {
+	#![allow(clippy::inline_always)] // Trivial bit manipulation.
+	#![allow(clippy::trivially_copy_pass_by_ref)] // Erased by inlining.
+
+	#[allow(unused_imports)] // Largely for documentation.
+	use crate::{
+		events::EventType, web, CallbackRef, EventBinding, EventBindingOptions, EventOutcome,
+	};
+
+	pub const PREVENT_DEFAULT: u8 = 0b_0001;
+	pub const STOP_PROPAGATION: u8 = 0b_0010;
+	pub const STOP_IMMEDIATE_PROPAGATION: u8 = 0b_0100;
+
+	#[allow(clippy::match_bool)]
+	impl EventOutcome {
+		/// Creates a new [`EventOutcome`] instance with all flags cleared.
+		#[inline(always)]
+		#[must_use]
+		pub const fn new() -> Self {
+			Self(0)
+		}
+
+		/// Indicates whether [***Event.preventDefault()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault) should be called. [See more.](#prevent_default)
+		#[inline(always)]
+		#[must_use]
+		pub const fn prevent_default(&self) -> bool {
+			self.0 & PREVENT_DEFAULT == PREVENT_DEFAULT
+		}
+		/// Sets whether [***Event.preventDefault()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault) should be called. [See more.](#prevent_default)
+		#[inline(always)]
+		pub fn set_prevent_default(&mut self, prevent_default: bool) {
+			*self = self.with_prevent_default(prevent_default)
+		}
+		/// Sets whether [***Event.preventDefault()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault) should be called. [See more.](#prevent_default)
+		#[inline(always)]
+		#[must_use]
+		pub const fn with_prevent_default(self, prevent_default: bool) -> Self {
+			Self(match prevent_default {
+				true => self.0 | PREVENT_DEFAULT,
+				false => self.0 & !PREVENT_DEFAULT,
+			})
+		}
+
+		/// Indicates whether [***Event.stopPropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopPropagation) should be called.
+		#[inline(always)]
+		#[must_use]
+		pub const fn stop_propagation(&self) -> bool {
+			self.0 & STOP_PROPAGATION == STOP_PROPAGATION
+		}
+		/// Sets whether [***Event.stopPropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopPropagation) should be called.
+		#[inline(always)]
+		pub fn set_stop_propagation(&mut self, stop_propagation: bool) {
+			*self = self.with_stop_propagation(stop_propagation)
+		}
+		/// Sets whether [***Event.stopPropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopPropagation) should be called.
+		#[inline(always)]
+		#[must_use]
+		pub const fn with_stop_propagation(self, stop_propagation: bool) -> Self {
+			Self(match stop_propagation {
+				true => self.0 | STOP_PROPAGATION,
+				false => self.0 & !STOP_PROPAGATION,
+			})
+		}
+
+		/// Indicates whether [***Event.stopImmediatePropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopImmediatePropagation) should be called.
+		#[inline(always)]
+		#[must_use]
+		pub const fn stop_immediate_propagation(&self) -> bool {
+			self.0 & STOP_IMMEDIATE_PROPAGATION == STOP_IMMEDIATE_PROPAGATION
+		}
+		/// Sets whether [***Event.stopImmediatePropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopImmediatePropagation) should be called.
+		#[inline(always)]
+		pub fn set_stop_immediate_propagation(&mut self, stop_immediate_propagation: bool) {
+			*self = self.with_stop_immediate_propagation(stop_immediate_propagation)
+		}
+		/// Sets whether [***Event.stopImmediatePropagation()***](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopImmediatePropagation) should be called.
+		#[inline(always)]
+		#[must_use]
+		pub const fn with_stop_immediate_propagation(
+			self,
+			stop_immediate_propagation: bool,
+		) -> Self {
+			Self(match stop_immediate_propagation {
+				true => self.0 | STOP_IMMEDIATE_PROPAGATION,
+				false => self.0 & !STOP_IMMEDIATE_PROPAGATION,
+			})
+		}
+
+		/// Clears [`prevent_default`](`EventOutcome::prevent_default`) if `options` are [`passive`](`EventBindingOptions::passive`).
+		///
+		/// In debug builds, a passive binding requesting to prevent the default action also prints a diagnostic
+		/// (to the JavaScript console on Wasm, to stderr otherwise), as the handler likely expects an effect that can't happen.
+		/// This requires the `"callbacks"` feature.
+		#[must_use]
+		pub fn checked_against(self, options: EventBindingOptions) -> Self {
+			if options.passive() && self.prevent_default() {
+				if cfg!(debug_assertions) {
+					crate::callback_registry::log_warning(format_args!(
+						"[lignin] A passive event binding requested `preventDefault()`, which is ignored. Use `EventBindingOptions::with_passive(false)` for this binding."
+					));
+				}
+				self.with_prevent_default(false)
+			} else {
+				self
+			}
+		}
+	}
+
+	impl<'a, S: crate::ThreadSafety> EventBinding<'a, S> {
+		/// Creates a new [`EventBinding`] for the [`events`](`crate::events`) catalogue entry `E`,
+		/// with a `callback` that receives `E`'s [`web`] stand-in.
+		///
+		/// `event` is only used for type inference.
+		#[allow(clippy::needless_pass_by_value)] // Type inference only.
+		#[inline]
+		#[must_use]
+		pub fn typed<E: EventType>(
+			event: E,
+			callback: CallbackRef<S, fn(event: E::StandIn) -> EventOutcome>,
+			options: EventBindingOptions,
+		) -> Self {
+			let _ = event;
+			Self {
+				name: E::NAME,
+				callback: callback.upcast(),
+				options,
+			}
+		}
+
+		/// Invokes [`callback`](`EventBinding::callback`) with `event`
+		/// and returns its [`EventOutcome`] [checked against](`EventOutcome::checked_against`) [`options`](`EventBinding::options`).
+		#[inline]
+		#[must_use]
+		pub fn call(&self, event: web::Event) -> EventOutcome {
+			self.callback.call(event).checked_against(self.options)
+		}
+	}
+}
+
 /// [`Vdom`] Represents a single HTML [***Attr***](https://developer.mozilla.org/en-US/docs/Web/API/Attr) with `name` and `value`.
+///
+/// # Namespaces
+///
+/// On SVG and [MathML](https://developer.mozilla.org/en-US/docs/Web/MathML) elements, attributes with an `xlink:`, `xml:` or `xmlns` prefix have a [***namespaceURI***](https://developer.mozilla.org/en-US/docs/Web/API/Attr/namespaceURI),
+/// the same way the HTML parser [adjusts foreign attributes](https://html.spec.whatwg.org/multipage/parsing.html#adjust-foreign-attributes).
+/// [`Attribute::namespace`] derives it from [`Attribute::name`], which is then the [***qualified name***](https://dom.spec.whatwg.org/#concept-attribute-qualified-name).
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// ## For VDOM-to-DOM renderers:
+///
+/// Attributes of [`Node::SvgElement`] and [`Node::MathMlElement`] elements with a [`namespace`](`Attribute::namespace`)
+/// **must** be set with [***Element.setAttributeNS()***](https://developer.mozilla.org/en-US/docs/Web/API/Element/setAttributeNS)
+/// and removed with [***Element.removeAttributeNS()***](https://developer.mozilla.org/en-US/docs/Web/API/Element/removeAttributeNS).
+/// All other attributes, including all attributes of [`Node::HtmlElement`] elements, **must** be handled with the non-namespaced methods, as before.
+///
+/// ## For HTML renderers:
+///
+/// HTML serialisation uses the qualified [`Attribute::name`] unchanged.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub struct Attribute<'a> {
 	/// The [***name***](https://developer.mozilla.org/en-US/docs/Web/API/Attr#properties).
@@ -638,31 +1368,268 @@ pub struct Attribute<'a> {
 	/// > Serializing an invalid attribute name to HTML is a **very** bad idea, so renderers must never do so.
 	pub name: &'a str,
 	/// The unescaped [***value***](https://developer.mozilla.org/en-US/docs/Web/API/Attr#properties).
+	///
+	/// See [`AttributeValue`] for how typed values are rendered.
+	pub value: AttributeValue<'a>,
+}
+
+#[allow(clippy::inline_always)] // Trivial constructor and getters.
+impl<'a> Attribute<'a> {
+	/// The [XLink](https://www.w3.org/TR/xlink11/) namespace, used with the `xlink:` prefix.
+	pub const XLINK_NAMESPACE: &'static str = "http://www.w3.org/1999/xlink";
+	/// The [XML](https://www.w3.org/XML/1998/namespace) namespace, used with the `xml:` prefix.
+	pub const XML_NAMESPACE: &'static str = "http://www.w3.org/XML/1998/namespace";
+	/// The [XMLNS](https://www.w3.org/2000/xmlns/) namespace, used for `xmlns` and with the `xmlns:` prefix.
+	pub const XMLNS_NAMESPACE: &'static str = "http://www.w3.org/2000/xmlns/";
+
+	/// Creates a new [`Attribute`].
+	#[inline(always)]
+	#[must_use]
+	pub const fn new(name: &'a str, value: AttributeValue<'a>) -> Self {
+		Self { name, value }
+	}
+
+	/// The [***namespaceURI***](https://developer.mozilla.org/en-US/docs/Web/API/Attr/namespaceURI) this attribute has on SVG and MathML elements, if any.
+	///
+	/// This is [`XLINK_NAMESPACE`](`Attribute::XLINK_NAMESPACE`) for `xlink:…`, [`XML_NAMESPACE`](`Attribute::XML_NAMESPACE`) for `xml:…`
+	/// and [`XMLNS_NAMESPACE`](`Attribute::XMLNS_NAMESPACE`) for `xmlns` and `xmlns:…` [`name`](`Attribute::name`)s, and [`None`] otherwise.
+	#[must_use]
+	pub fn namespace(&self) -> Option<&'static str> {
+		match self.name.split_once(':') {
+			Some(("xlink", _)) => Some(Self::XLINK_NAMESPACE),
+			Some(("xml", _)) => Some(Self::XML_NAMESPACE),
+			Some(("xmlns", _)) => Some(Self::XMLNS_NAMESPACE),
+			None if self.name == "xmlns" => Some(Self::XMLNS_NAMESPACE),
+			_ => None,
+		}
+	}
+
+	/// The [***prefix***](https://developer.mozilla.org/en-US/docs/Web/API/Attr/prefix) part of [`Attribute::name`], if present.
+	///
+	/// This is always [`None`] for attributes without [`namespace`](`Attribute::namespace`).
+	#[must_use]
+	pub fn prefix(&self) -> Option<&'a str> {
+		self.namespace()?;
+		self.name.split_once(':').map(|(prefix, _)| prefix)
+	}
+
+	/// The [***localName***](https://developer.mozilla.org/en-US/docs/Web/API/Attr/localName) part of [`Attribute::name`].
+	///
+	/// This is the whole name for attributes without [`namespace`](`Attribute::namespace`).
+	#[must_use]
+	pub fn local_name(&self) -> &'a str {
+		match self.namespace() {
+			Some(_) => self
+				.name
+				.split_once(':')
+				.map_or(self.name, |(_, local)| local),
+			None => self.name,
+		}
+	}
+}
+
+/// [`Vdom`] A typed [`Attribute::value`].
+///
+/// Values are rendered to strings only when needed, via their [`Display`](`core::fmt::Display`) implementation, which doesn't allocate.
+///
+/// # Rendering
+///
+/// - [`String`](`AttributeValue::String`) renders as-is.
+/// - [`Bool`](`AttributeValue::Bool`) controls the attribute's presence. A present boolean attribute renders as empty string.
+/// - [`Integer`](`AttributeValue::Integer`) renders in decimal.
+/// - [`Float`](`AttributeValue::Float`) renders like [`f64`]'s [`Display`](`core::fmt::Display`) implementation,
+///   except that `-0.0` renders as `0` and infinities render as `Infinity` and `-Infinity`, as they would in JavaScript.
+/// - [`Tokens`](`AttributeValue::Tokens`) renders as its non-empty tokens separated by single spaces.
+///
+/// # Comparisons
+///
+/// Equality, ordering and hashing are semantic: [`AttributeValue`]s compare by presence and then by their rendered value.
+/// For example, `Float(1.0)`, `Integer(1)` and `String("1")` are all equal, as are `Bool(true)` and `String("")`.
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// ## For renderers:
+///
+/// An [`Attribute`] whose value isn't [present](`AttributeValue::is_present`) **must** be treated as if it was missing from [`Element::attributes`].
+///
+/// Renderers **must not** update an attribute if its old and new values are equal according to [`PartialEq`], even if their variants differ.
+#[derive(Debug, Clone, Copy)]
+pub enum AttributeValue<'a> {
+	/// An unescaped string value.
+	String(&'a str),
+	/// A ***boolean attribute*** like [***disabled***](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/disabled), which is present iff `true`.
+	Bool(bool),
+	/// An integer value.
+	Integer(i64),
+	/// A floating point value.
+	Float(f64),
+	/// A space-separated token list, like [***class***](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/class) or [***rel***](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/rel).
+	Tokens(&'a [&'a str]),
+}
+
+#[allow(clippy::inline_always)] // Trivial.
+impl<'a> AttributeValue<'a> {
+	/// Indicates whether the [`Attribute`] is present at all, which is the case unless this is `Bool(false)`.
+	#[inline(always)]
+	#[must_use]
+	pub const fn is_present(&self) -> bool {
+		!matches!(self, Self::Bool(false))
+	}
+}
+
+/// [`Vdom`] Represents a single DOM ***property*** (like [***HTMLInputElement.value***](https://developer.mozilla.org/en-US/docs/Web/API/HTMLInputElement#properties)) with `name` and `value`.
+///
+/// Unlike [`Attribute`]s, properties reflect the **live** state of an element, which can diverge from the VDOM after user interaction.
+/// Use them for controlled form inputs (`value`, `checked`, `selectedIndex`, `indeterminate`, …).
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this type must not rely on it for soundness. However, it is free to panic when encountering an incorrect implementation.
+///
+/// ## For VDOM-to-DOM renderers:
+///
+/// When diffing, renderers **must** compare each [`Property`] against the **live DOM** property value rather than (only) the previous VDOM,
+/// and assign it iff they differ. This restores the VDOM state after user interaction, but avoids e.g. resetting a text input's cursor position needlessly.
+///
+/// Properties **should** be assigned after [`Element::attributes`] and [`Element::content`] have been processed,
+/// so that for example a `<select>`'s ***selectedIndex*** refers to up-to-date options.
+///
+/// Properties that are removed from the VDOM are left as they are in the DOM.
+///
+/// ## For HTML renderers:
+///
+/// Properties **should** be reflected to attributes where possible, as determined by [`Property::reflected_attribute`].
+/// Reflected attributes take precedence over any same-named [`Attribute`] on the same [`Element`].  
+/// [`PropertyValue::Bool`] reflects as attribute presence (`true`) or absence (`false`).
+///
+/// Properties that don't reflect are skipped.
+///
+/// > The `value` property of a `<textarea>` instead sets its text content in HTML, replacing [`Element::content`].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
+pub struct Property<'a> {
+	/// The property name, as used in JavaScript, for example `"selectedIndex"`.
+	///
+	/// # Implementation Contract
+	///
+	/// ## Security
+	///
+	/// The same restrictions as on [`Attribute::name`] apply.
+	pub name: &'a str,
+	/// The (unescaped) property value.
+	pub value: PropertyValue<'a>,
+}
+
+/// [`Vdom`] A single CSS declaration in [`Element::styles`], like `color: red !important`.
+///
+/// # Implementation Contract
+///
+/// ## Security
+///
+/// [`StyleDeclaration::property`] and [`StyleDeclaration::value`] are passed to the DOM verbatim, but may contain characters that are unexpected in their position.
+///
+/// HTML renderers **must not** serialise declarations whose `property` or `value` contain `;`, `"`, `<` or unbalanced quotes and brackets verbatim.
+/// (Skipping them is sufficient.)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
+pub struct StyleDeclaration<'a> {
+	/// The CSS property name in its hyphenated form, for example `"background-color"` or `"--custom-property"`.
+	pub property: &'a str,
+	/// The CSS value, for example `"red"`, without `!important`.
 	pub value: &'a str,
+	/// Whether the declaration has the `!important` priority.
+	pub important: bool,
+}
+
+/// [`Vdom`] A typed [`Property`] value.
+///
+/// Comparisons and hashing treat [`PropertyValue::Number`]s by their bit pattern,
+/// which means for example that `NaN` equals itself but `0.0` and `-0.0` are distinct.
+#[derive(Debug, Clone, Copy)]
+pub enum PropertyValue<'a> {
+	/// A boolean property, like ***checked*** or ***indeterminate***.
+	Bool(bool),
+	/// An integral property, like ***selectedIndex***.
+	Integer(i32),
+	/// A floating point property, like ***valueAsNumber***.
+	Number(f64),
+	/// A string property, like ***value***.
+	String(&'a str),
+}
+
+impl<'a> Property<'a> {
+	/// Returns the name of the HTML attribute this [`Property`] is reflected as during serialisation, if any.
+	///
+	/// This is an explicit table of common properties that [reflect](https://html.spec.whatwg.org/multipage/common-dom-interfaces.html#reflecting-content-attributes-in-idl-attributes)
+	/// a content attribute, like ***className*** (`class`) or ***disabled***.
+	///
+	/// The live-state properties ***value***, ***checked*** and ***selected*** don't reflect, but are included as well:
+	/// They're serialised as the attribute that determines their default state, which is what they are initialised from when the HTML is parsed.
+	///
+	/// Any other property, for example ***indeterminate***, ***selectedIndex*** or ***textContent***, isn't reflected.
+	#[must_use]
+	pub fn reflected_attribute(&self) -> Option<&'a str> {
+		Some(match self.name {
+			"value" | "defaultValue" => "value",
+			"checked" | "defaultChecked" => "checked",
+			"selected" | "defaultSelected" => "selected",
+			"accessKey" => "accesskey",
+			"className" => "class",
+			"colSpan" => "colspan",
+			"formNoValidate" => "formnovalidate",
+			"htmlFor" => "for",
+			"maxLength" => "maxlength",
+			"minLength" => "minlength",
+			"noValidate" => "novalidate",
+			"readOnly" => "readonly",
+			"rowSpan" => "rowspan",
+			"tabIndex" => "tabindex",
+			name @ ("accept" | "alt" | "autocomplete" | "autofocus" | "cols" | "dir"
+			| "disabled" | "download" | "height" | "hidden" | "href" | "id" | "label"
+			| "lang" | "max" | "min" | "multiple" | "name" | "open" | "pattern"
+			| "placeholder" | "rel" | "required" | "rows" | "size" | "src" | "step"
+			| "target" | "title" | "type" | "width" | "wrap") => name,
+			_ => return None,
+		})
+	}
 }
 
 mod sealed {
 	use super::{ThreadBound, ThreadSafe};
 	use crate::{
-		callback_registry::CallbackSignature, remnants::RemnantSite, web, Attribute, CallbackRef,
-		CallbackRegistration, DomRef, Element, ElementCreationOptions, EventBinding,
-		EventBindingOptions, Node, ReorderableFragment, ThreadSafety,
+		callback_registry::{CallbackParameter, CallbackReturn, CallbackSignature},
+		remnants::RemnantSite,
+		Attribute, AttributeValue, CallbackRef, CallbackRegistration, Document, DomRef, Element,
+		ElementCreationOptions, EventBinding, EventBindingOptions, EventOutcome, Node, Portal,
+		Property, PropertyValue, ReorderableFragment, ShadowRoot, ShadowRootOptions,
+		StyleDeclaration, ThreadSafety, TrustedHtml,
 	};
 
 	pub trait Sealed {}
-	impl Sealed for fn(web::Event) {}
+	impl<P: CallbackParameter, O: CallbackReturn> Sealed for fn(P) -> O {}
+	impl Sealed for () {}
+	impl Sealed for EventOutcome {}
 	impl<T> Sealed for fn(DomRef<&'_ T>) {}
 	impl Sealed for ThreadBound {}
 	impl Sealed for ThreadSafe {}
 	impl<'a> Sealed for Attribute<'a> {}
+	impl<'a> Sealed for AttributeValue<'a> {}
+	impl<'a> Sealed for Property<'a> {}
+	impl<'a> Sealed for StyleDeclaration<'a> {}
+	impl<'a> Sealed for TrustedHtml<'a> {}
+	impl<'a> Sealed for PropertyValue<'a> {}
 	impl<'a> Sealed for ElementCreationOptions<'a> {}
 	impl Sealed for EventBindingOptions {}
+	impl Sealed for ShadowRootOptions {}
 	impl<R, C: CallbackSignature> Sealed for CallbackRegistration<R, C> {}
 	impl<S: ThreadSafety, C: CallbackSignature> Sealed for CallbackRef<S, C> {}
 	impl<'a, S: ThreadSafety> Sealed for Element<'a, S> {}
 	impl<'a, S: ThreadSafety> Sealed for EventBinding<'a, S> {}
 	impl<'a, S: ThreadSafety> Sealed for Node<'a, S> {}
 	impl<'a, S: ThreadSafety> Sealed for ReorderableFragment<'a, S> {}
+	impl<'a, S: ThreadSafety> Sealed for ShadowRoot<'a, S> {}
+	impl<'a, S: ThreadSafety> Sealed for Portal<'a, S> {}
+	impl<'a, S: ThreadSafety> Sealed for Document<'a, S> {}
 	impl Sealed for RemnantSite {}
 }
 
@@ -694,8 +1661,11 @@ impl ThreadSafety for ThreadSafe {}
 
 /// Marker trait for VDOM data types, which (almost) all vary by [`ThreadSafety`].
 ///
-/// Somewhat uselessly implemented on [`Attribute`], [`ElementCreationOptions`] and [`EventBindingOptions`], which are always [`ThreadSafe`].
-pub trait Vdom: Sealed
+/// Somewhat uselessly implemented on [`Attribute`], [`AttributeValue`], [`Property`], [`PropertyValue`], [`StyleDeclaration`], [`TrustedHtml`], [`ElementCreationOptions`], [`EventBindingOptions`] and [`ShadowRootOptions`], which are always [`ThreadSafe`].
+///
+/// Also implemented on [`Option`]s, references, arrays and slice references of [`Vdom`] types, with the same [`ThreadSafety`],
+/// and on custom types through [`auto_safety::VdomSeal`].
+pub trait Vdom: auto_safety::VdomSeal
 where
 	Self: Sized + Debug + Clone + Copy + PartialEq + Eq + PartialOrd + Ord + Hash,
 {
@@ -709,6 +1679,26 @@ impl<'a> Vdom for Attribute<'a> {
 	type ThreadSafety = ThreadSafe;
 }
 
+impl<'a> Vdom for AttributeValue<'a> {
+	type ThreadSafety = ThreadSafe;
+}
+
+impl<'a> Vdom for StyleDeclaration<'a> {
+	type ThreadSafety = ThreadSafe;
+}
+
+impl<'a> Vdom for TrustedHtml<'a> {
+	type ThreadSafety = ThreadSafe;
+}
+
+impl<'a> Vdom for Property<'a> {
+	type ThreadSafety = ThreadSafe;
+}
+
+impl<'a> Vdom for PropertyValue<'a> {
+	type ThreadSafety = ThreadSafe;
+}
+
 impl<'a> Vdom for ElementCreationOptions<'a> {
 	type ThreadSafety = ThreadSafe;
 }
@@ -717,6 +1707,10 @@ impl Vdom for EventBindingOptions {
 	type ThreadSafety = ThreadSafe;
 }
 
+impl Vdom for ShadowRootOptions {
+	type ThreadSafety = ThreadSafe;
+}
+
 macro_rules! vdom_impls {
 	($($name:ident),*$(,)?) => {$(
 		impl<'a, S> Vdom for $name<'a, S> where
@@ -726,7 +1720,15 @@ macro_rules! vdom_impls {
 		}
 	)*};
 }
-vdom_impls!(Element, EventBinding, Node, ReorderableFragment);
+vdom_impls!(
+	Document,
+	Element,
+	EventBinding,
+	Node,
+	Portal,
+	ReorderableFragment,
+	ShadowRoot
+);
 
 impl<S, C> Vdom for CallbackRef<S, C>
 where
@@ -735,3 +1737,19 @@ where
 {
 	type ThreadSafety = S;
 }
+
+impl<T: Vdom> Vdom for Option<T> {
+	type ThreadSafety = T::ThreadSafety;
+}
+
+impl<'r, T: Vdom> Vdom for &'r T {
+	type ThreadSafety = T::ThreadSafety;
+}
+
+impl<T: Vdom, const N: usize> Vdom for [T; N] {
+	type ThreadSafety = T::ThreadSafety;
+}
+
+impl<'r, T: Vdom> Vdom for &'r [T] {
+	type ThreadSafety = T::ThreadSafety;
+}
@@ -0,0 +1,82 @@
+//! Automatic memoization that derives [`Node::Memoized::state_key`] from component inputs.
+//!
+//! Choosing [`state_key`](`Node::Memoized::state_key`)s manually is error-prone, as they must be distinct whenever the content differs.
+//! [`memo`] instead hashes the inputs a subtree is rendered from, together with its call site,
+//! and reuses the subtree from the previous frame if a [`MemoContext`] still has it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{memo::{memo, MemoContext}, Node, ThreadSafe};
+//!
+//! fn greeting<'a, C: MemoContext<'a, ThreadSafe>>(context: &'a C, name: &str) -> Node<'a, ThreadSafe> {
+//!   memo(context, name, |context| {
+//!     let parts = context.alloc_slice_copy(&["Hello, ".into(), context.alloc_str(name).into()]);
+//!     Node::Multi(parts)
+//!   })
+//! }
+//! ```
+
+use crate::{arena::Arena, stable_hash::StableHasher, Node, ThreadSafety};
+use core::{
+	hash::{Hash, Hasher},
+	panic::Location,
+};
+
+/// Storage for memoized subtrees across frames, typically backed by a double-buffered [`Arena`].
+///
+/// # Implementation Contract
+///
+/// > **This is not a soundness contract**. Code using this trait must not rely on it for soundness.
+/// > However, it is free to panic when encountering an incorrect implementation.
+///
+/// [`MemoContext::recall`] **must** only return content that was [recorded](`MemoContext::record`) with the same `state_key`
+/// during the previous or current frame, copied or otherwise kept alive so that it's valid for `'a`.
+pub trait MemoContext<'a, S: ThreadSafety>: Arena {
+	/// Retrieves the content memoized as `state_key`, if still available.
+	fn recall(&'a self, state_key: u64) -> Option<&'a Node<'a, S>>;
+
+	/// Records `content` as memoized under `state_key`, so that it can be recalled during the next frame.
+	fn record(&'a self, state_key: u64, content: &'a Node<'a, S>);
+}
+
+/// Renders `render` as [`Node::Memoized`], with a [`state_key`](`Node::Memoized::state_key`) derived from `inputs` and the call site.
+///
+/// If `context` [recalls](`MemoContext::recall`) content for that key, `render` isn't called and that content is reused instead.
+///
+/// The key is a [`StableHasher`] hash of the caller's [`Location`] followed by `inputs`,
+/// so it's consistent between builds of the same source as long as `inputs`' [`Hash`] implementation is.
+///
+/// `render` **should** depend only on `inputs`, as its output is otherwise reused incorrectly.
+#[track_caller]
+pub fn memo<'a, S, C, I>(
+	context: &'a C,
+	inputs: &I,
+	render: impl FnOnce(&'a C) -> Node<'a, S>,
+) -> Node<'a, S>
+where
+	S: ThreadSafety,
+	C: MemoContext<'a, S>,
+	I: Hash + ?Sized,
+{
+	let state_key = {
+		let location = Location::caller();
+		let mut hasher = StableHasher::new();
+		// Length and bytes, as `str`'s `Hash` implementation isn't stable across Rust versions.
+		hasher.write_u64(location.file().len() as u64);
+		hasher.write(location.file().as_bytes());
+		hasher.write_u32(location.line());
+		hasher.write_u32(location.column());
+		inputs.hash(&mut hasher);
+		hasher.finish()
+	};
+	let memoized = match context.recall(state_key) {
+		Some(recalled) => recalled,
+		None => context.alloc(render(context)),
+	};
+	context.record(state_key, memoized);
+	Node::Memoized {
+		state_key,
+		content: memoized,
+	}
+}
@@ -0,0 +1,234 @@
+//! Structural addressing of [`Node`]s within a VDOM graph, for diagnostics, patches, developer tools and tests.
+//!
+//! There is formally no VDOM identity (see [Limitations](`crate`#limitations)), so a [`NodePath`] instead describes how to reach a [`Node`] from a root.
+//! Steps into [`Node::Keyed`] use [`ReorderableFragment::dom_key`](`crate::ReorderableFragment::dom_key`) rather than a position,
+//! so paths through keyed content stay valid when it's reordered.
+//!
+//! [`NodePath`] borrows its steps, so this module doesn't allocate.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{path::{NodePath, PathStep}, Node, ReorderableFragment, ThreadSafe};
+//!
+//! let keyed = [
+//!   ReorderableFragment { dom_key: 7, content: "a".into() },
+//!   ReorderableFragment { dom_key: 3, content: "b".into() },
+//! ];
+//! let nodes = ["x".into(), Node::Keyed(&keyed)];
+//! let root: Node<ThreadSafe> = Node::Multi(&nodes);
+//!
+//! let path = NodePath::new(&[PathStep::Index(1), PathStep::Key(3)]);
+//! assert_eq!(path.to_string(), "/1/#3");
+//! assert_eq!(root.get(path), Some(&keyed[1].content));
+//! assert_eq!(root.dom_range(path), Some(2..3));
+//!
+//! let mut buffer = [PathStep::Content; 4];
+//! assert_eq!(root.find_path(&keyed[1].content, &mut buffer), Some(path));
+//! ```
+
+use crate::{Node, ThreadSafety};
+use core::{
+	fmt::{self, Display, Formatter},
+	ops::Range,
+	ptr,
+};
+
+/// A single step of a [`NodePath`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathStep {
+	/// Steps into the content of an element [`Node`], [`Node::Memoized`] or [`Node::Portal`].
+	///
+	/// Displayed as `content`.
+	Content,
+	/// Steps to the [`Node`] at this index in a [`Node::Multi`].
+	///
+	/// Displayed as the plain index.
+	Index(usize),
+	/// Steps to the content of the [`ReorderableFragment`](`crate::ReorderableFragment`) with this [`dom_key`](`crate::ReorderableFragment::dom_key`) in a [`Node::Keyed`].
+	///
+	/// Displayed as `#` followed by the key.
+	Key(u32),
+}
+
+impl Display for PathStep {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			PathStep::Content => f.write_str("content"),
+			PathStep::Index(index) => write!(f, "{}", index),
+			PathStep::Key(dom_key) => write!(f, "#{}", dom_key),
+		}
+	}
+}
+
+/// A sequence of [`PathStep`]s that locates a [`Node`] relative to a root [`Node`].
+///
+/// The empty path locates the root itself.
+///
+/// Displayed as the `/`-prefixed steps, for example `/content/2/#17`, or `/` for the empty path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NodePath<'p> {
+	steps: &'p [PathStep],
+}
+
+impl<'p> NodePath<'p> {
+	/// The empty path, which locates the root itself.
+	pub const ROOT: Self = Self { steps: &[] };
+
+	/// Creates a new [`NodePath`] from `steps`.
+	#[must_use]
+	pub const fn new(steps: &'p [PathStep]) -> Self {
+		Self { steps }
+	}
+
+	/// Retrieves the [`PathStep`]s of this path.
+	#[must_use]
+	pub const fn steps(&self) -> &'p [PathStep] {
+		self.steps
+	}
+
+	/// Indicates whether this is the empty path, which locates the root itself.
+	#[must_use]
+	pub const fn is_root(&self) -> bool {
+		self.steps.is_empty()
+	}
+
+	/// The path without its last step, or [`None`] for the root path.
+	#[must_use]
+	pub fn parent(&self) -> Option<Self> {
+		self.steps.split_last().map(|(_, steps)| Self::new(steps))
+	}
+}
+
+impl<'p> Display for NodePath<'p> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		if self.steps.is_empty() {
+			return f.write_str("/");
+		}
+		for step in self.steps {
+			write!(f, "/{}", step)?;
+		}
+		Ok(())
+	}
+}
+
+impl<'a, S: ThreadSafety> Node<'a, S> {
+	/// Resolves `path` relative to this [`Node`].
+	///
+	/// Returns [`None`] iff any step doesn't apply to the [`Node`] it's taken from,
+	/// for example a [`PathStep::Key`] that's not present in a [`Node::Keyed`] or a [`PathStep::Index`] on an element.
+	#[must_use]
+	pub fn get<'n>(&'n self, path: NodePath<'_>) -> Option<&'n Node<'a, S>> {
+		let mut node = self;
+		for &step in path.steps {
+			node = step_into(node, step)?.0;
+		}
+		Some(node)
+	}
+
+	/// Maps `path` to the range of surface-level DOM child offsets its target occupies, as by [`Node::dom_len`].
+	///
+	/// Offsets are relative to the closest element (or [`Node::Portal`]) stepped into with [`PathStep::Content`],
+	/// or to this [`Node`] if there is none.
+	///
+	/// Returns [`None`] iff `path` can't be resolved. See [`Node::get`].
+	#[must_use]
+	pub fn dom_range(&self, path: NodePath<'_>) -> Option<Range<usize>> {
+		let mut node = self;
+		let mut offset = 0;
+		for &step in path.steps {
+			let (next, skipped) = step_into(node, step)?;
+			offset = match skipped {
+				Some(skipped) => offset + skipped,
+				None => 0,
+			};
+			node = next;
+		}
+		Some(offset..offset + node.dom_len())
+	}
+
+	/// Finds the path from this [`Node`] to `target`, writing its steps into `buffer`.
+	///
+	/// `target` is compared by address, so it must be borrowed from within this [`Node`]'s graph rather than from a copy.
+	/// Steps into [`Node::Keyed`] always use [`PathStep::Key`].
+	///
+	/// Returns [`None`] iff `target` isn't reachable through [`PathStep`]s or `buffer` is too short for its path.
+	pub fn find_path<'p>(
+		&self,
+		target: &Node<'a, S>,
+		buffer: &'p mut [PathStep],
+	) -> Option<NodePath<'p>> {
+		let len = find(self, target, buffer, 0)?;
+		Some(NodePath::new(&buffer[..len]))
+	}
+}
+
+/// Takes `step` from `node`, also returning the surface-level DOM offset skipped within `node`,
+/// or [`None`] for that if the step enters a new DOM parent.
+fn step_into<'n, 'a, S: ThreadSafety>(
+	node: &'n Node<'a, S>,
+	step: PathStep,
+) -> Option<(&'n Node<'a, S>, Option<usize>)> {
+	Some(match (node, step) {
+		(
+			Node::HtmlElement { element, .. }
+			| Node::MathMlElement { element, .. }
+			| Node::SvgElement { element, .. },
+			PathStep::Content,
+		) => (&element.content, None),
+		(Node::Memoized { content, .. }, PathStep::Content) => (*content, Some(0)),
+		(Node::Portal(portal), PathStep::Content) => (&portal.content, None),
+		(Node::Multi(nodes), PathStep::Index(index)) => {
+			let node = nodes.get(index)?;
+			(node, Some(nodes[..index].iter().map(Node::dom_len).sum()))
+		}
+		(Node::Keyed(pairs), PathStep::Key(dom_key)) => {
+			let index = pairs.iter().position(|pair| pair.dom_key == dom_key)?;
+			(
+				&pairs[index].content,
+				Some(
+					pairs[..index]
+						.iter()
+						.map(|pair| pair.content.dom_len())
+						.sum(),
+				),
+			)
+		}
+		_ => return None,
+	})
+}
+
+/// Writes the remaining steps into `buffer[depth..]` and returns the total path length.
+fn find<'a, S: ThreadSafety>(
+	node: &Node<'a, S>,
+	target: &Node<'a, S>,
+	buffer: &mut [PathStep],
+	depth: usize,
+) -> Option<usize> {
+	if ptr::eq(node, target) {
+		return Some(depth);
+	}
+	let mut descend = |step: PathStep, child: &Node<'a, S>| {
+		*buffer.get_mut(depth)? = step;
+		find(child, target, buffer, depth + 1)
+	};
+	match node {
+		Node::HtmlElement { element, .. }
+		| Node::MathMlElement { element, .. }
+		| Node::SvgElement { element, .. } => descend(PathStep::Content, &element.content),
+		Node::Memoized { content, .. } => descend(PathStep::Content, content),
+		Node::Portal(portal) => descend(PathStep::Content, &portal.content),
+		Node::Multi(nodes) => nodes
+			.iter()
+			.enumerate()
+			.find_map(|(index, node)| descend(PathStep::Index(index), node)),
+		Node::Keyed(pairs) => pairs
+			.iter()
+			.find_map(|pair| descend(PathStep::Key(pair.dom_key), &pair.content)),
+		Node::Comment { .. }
+		| Node::Text { .. }
+		| Node::RawHtml(_)
+		| Node::HeadContribution { .. }
+		| Node::RemnantSite(_) => None,
+	}
+}
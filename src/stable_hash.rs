@@ -0,0 +1,420 @@
+//! Deterministic structural hashing of [`Node`] graphs, for example to compute [`Node::Memoized::state_key`]s.
+//!
+//! The [`Hash`](`core::hash::Hash`) implementations in this crate depend on the [`Hasher`] they're used with
+//! and always include callback identities, which are erased without the `"callbacks"` feature.
+//! [`Node::stable_hash`] instead uses a fixed algorithm and encoding that don't depend on the platform, Rust version or enabled features,
+//! so for example a server and its clients can agree on [`state_key`](`Node::Memoized::state_key`)s.
+//!
+//! # Algorithm
+//!
+//! The hash is 64-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) over a fixed encoding of the VDOM:
+//! Integers are encoded as little-endian, lengths as [`u64`], floating point numbers by their bit pattern,
+//! and each enum variant and [`Option`] by a leading tag byte.
+//!
+//! Changes to this encoding are breaking changes.
+//!
+//! [`StableHasher`] implements the same algorithm as [`Hasher`], for hashing other values like component inputs.
+//!
+//! Like the [`Hash`](`core::hash::Hash`) implementations, the stable hash is consistent with [`PartialEq`]:
+//! [`Element::classes`] and [`Element::styles`] are hashed regardless of order,
+//! [`AttributeValue`]s by how they're rendered
+//! and [`Node::Memoized`] only by its [`state_key`](`Node::Memoized::state_key`).
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{stable_hash::StableHashOptions, Node, ThreadSafe};
+//!
+//! let nodes = ["Hello, ".into(), "world!".into()];
+//! let content: Node<ThreadSafe> = Node::Multi(&nodes);
+//!
+//! let memoized: Node<ThreadSafe> = Node::Memoized {
+//!   state_key: content.stable_hash(StableHashOptions::new()),
+//!   content: &content,
+//! };
+//! ```
+
+use crate::{
+	callback_registry::CallbackSignature, Attribute, AttributeValue, CallbackRef, Element,
+	EventBinding, Node, PropertyValue, ReorderableFragment, ShadowRootMode, SlotAssignmentMode,
+	StyleDeclaration, ThreadSafety,
+};
+use core::hash::Hasher;
+
+/// Options for [`Node::stable_hash`].
+///
+/// # Options
+///
+/// ## `callbacks`
+///
+/// Whether callback identities, that is [`EventBinding::callback`] and any `dom_binding` [`CallbackRef`]s, factor into the hash.
+///
+/// If this is disabled (the default), callbacks are ignored entirely, including whether a `dom_binding` is present.
+/// Enable it only if the hash is used within a single process, as [`CallbackRef`] identities are assigned at runtime
+/// and erased without the `"callbacks"` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StableHashOptions {
+	callbacks: bool,
+}
+impl Default for StableHashOptions {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+#[allow(clippy::inline_always)] // Trivial getters and setters.
+impl StableHashOptions {
+	/// Creates a new [`StableHashOptions`] that excludes callbacks.
+	#[inline(always)]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { callbacks: false }
+	}
+
+	/// Retrieves whether callback identities factor into the hash.
+	#[inline(always)]
+	#[must_use]
+	pub const fn callbacks(&self) -> bool {
+		self.callbacks
+	}
+	/// Sets whether callback identities factor into the hash.
+	#[inline(always)]
+	pub fn set_callbacks(&mut self, callbacks: bool) {
+		self.callbacks = callbacks
+	}
+	/// Sets whether callback identities factor into the hash.
+	#[inline(always)]
+	#[must_use]
+	pub const fn with_callbacks(self, callbacks: bool) -> Self {
+		Self { callbacks }
+	}
+}
+
+impl<'a, S: ThreadSafety> Node<'a, S> {
+	/// Calculates a deterministic, platform-independent structural hash of this [`Node`].
+	///
+	/// See the [`stable_hash`](`crate::stable_hash`) module for details.
+	#[must_use]
+	#[allow(clippy::missing_panics_doc)] // todo!
+	pub fn stable_hash(&self, options: StableHashOptions) -> u64 {
+		let mut encoder = Encoder::new(options);
+		encoder.node(self);
+		encoder.hasher.finish()
+	}
+}
+
+/// A [`Hasher`] that implements 64-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) with a platform-independent encoding of integers.
+///
+/// All integers are written as little-endian, and [`usize`] and [`isize`] always as 64-bit.
+/// This makes it suitable for hashing [`Hash`](`core::hash::Hash`) values consistently across platforms,
+/// as long as their [`Hash`](`core::hash::Hash`) implementations only write integers and fixed byte sequences.
+///
+/// > Note that the [`Hash`](`core::hash::Hash`) implementations of [`core`] types like [`str`] or slices are not guaranteed to stay the same between Rust versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StableHasher {
+	state: u64,
+}
+impl Default for StableHasher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl StableHasher {
+	/// Creates a new [`StableHasher`] with the FNV-1a offset basis as state.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			state: 0xcbf2_9ce4_8422_2325,
+		}
+	}
+}
+impl Hasher for StableHasher {
+	fn finish(&self) -> u64 {
+		self.state
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		for byte in bytes {
+			self.state = (self.state ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+
+	fn write_u16(&mut self, i: u16) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_u32(&mut self, i: u32) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_u64(&mut self, i: u64) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_u128(&mut self, i: u128) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_usize(&mut self, i: usize) {
+		self.write_u64(i as u64);
+	}
+	fn write_i16(&mut self, i: i16) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_i32(&mut self, i: i32) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_i64(&mut self, i: i64) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_i128(&mut self, i: i128) {
+		self.write(&i.to_le_bytes());
+	}
+	fn write_isize(&mut self, i: isize) {
+		self.write_i64(i as i64);
+	}
+}
+
+/// Writes the fixed encoding of VDOM values into a [`StableHasher`].
+struct Encoder {
+	hasher: StableHasher,
+	options: StableHashOptions,
+}
+impl Encoder {
+	fn new(options: StableHashOptions) -> Self {
+		Self {
+			hasher: StableHasher::new(),
+			options,
+		}
+	}
+
+	fn bytes(&mut self, bytes: &[u8]) {
+		self.hasher.write(bytes);
+	}
+
+	fn u8(&mut self, value: u8) {
+		self.bytes(&[value]);
+	}
+
+	fn bool(&mut self, value: bool) {
+		self.u8(value.into());
+	}
+
+	fn u64(&mut self, value: u64) {
+		self.bytes(&value.to_le_bytes());
+	}
+
+	fn len(&mut self, len: usize) {
+		self.u64(len as u64);
+	}
+
+	fn str(&mut self, value: &str) {
+		self.len(value.len());
+		self.bytes(value.as_bytes());
+	}
+
+	fn option_str(&mut self, value: Option<&str>) {
+		match value {
+			None => self.u8(0),
+			Some(value) => {
+				self.u8(1);
+				self.str(value);
+			}
+		}
+	}
+
+	fn callback<S: ThreadSafety, C: CallbackSignature>(
+		&mut self,
+		callback: Option<&CallbackRef<S, C>>,
+	) {
+		if self.options.callbacks {
+			match callback {
+				None => self.u8(0),
+				Some(callback) => {
+					self.u8(1);
+					self.bytes(&callback.key.get().to_le_bytes());
+				}
+			}
+		}
+	}
+
+	/// Hashes `items` regardless of their order, by summing up their individual hashes.
+	fn unordered<T>(&mut self, items: &[T], mut hash: impl FnMut(&mut Self, &T)) {
+		let sum = items.iter().fold(0_u64, |sum, item| {
+			let mut encoder = Self::new(self.options);
+			hash(&mut encoder, item);
+			sum.wrapping_add(encoder.hasher.finish())
+		});
+		self.len(items.len());
+		self.u64(sum);
+	}
+
+	fn node<S: ThreadSafety>(&mut self, node: &Node<'_, S>) {
+		match node {
+			Node::Comment {
+				comment,
+				dom_binding,
+			} => {
+				self.u8(0);
+				self.str(comment);
+				self.callback(dom_binding.as_ref());
+			}
+			Node::HtmlElement {
+				element,
+				dom_binding,
+			} => {
+				self.u8(1);
+				self.element(element);
+				self.callback(dom_binding.as_ref());
+			}
+			Node::MathMlElement {
+				element,
+				dom_binding,
+			} => {
+				self.u8(2);
+				self.element(element);
+				self.callback(dom_binding.as_ref());
+			}
+			Node::SvgElement {
+				element,
+				dom_binding,
+			} => {
+				self.u8(3);
+				self.element(element);
+				self.callback(dom_binding.as_ref());
+			}
+			Node::Memoized { state_key, .. } => {
+				self.u8(4);
+				self.u64(*state_key);
+			}
+			Node::Multi(nodes) => {
+				self.u8(5);
+				self.len(nodes.len());
+				for node in *nodes {
+					self.node(node);
+				}
+			}
+			Node::Keyed(pairs) => {
+				self.u8(6);
+				self.len(pairs.len());
+				for ReorderableFragment { dom_key, content } in *pairs {
+					self.bytes(&dom_key.to_le_bytes());
+					self.node(content);
+				}
+			}
+			Node::Text { text, dom_binding } => {
+				self.u8(7);
+				self.str(text);
+				self.callback(dom_binding.as_ref());
+			}
+			Node::RawHtml(html) => {
+				self.u8(8);
+				self.str(html.as_str());
+			}
+			Node::Portal(portal) => {
+				self.u8(9);
+				self.str(portal.target);
+				self.node(&portal.content);
+				self.callback(portal.dom_binding.as_ref());
+			}
+			Node::HeadContribution {
+				element,
+				dom_binding,
+			} => {
+				self.u8(10);
+				self.element(element);
+				self.callback(dom_binding.as_ref());
+			}
+			Node::RemnantSite(_) => {
+				todo!("RemnantSite stable hash")
+			}
+		}
+	}
+
+	fn element<S: ThreadSafety>(&mut self, element: &Element<'_, S>) {
+		self.str(element.name);
+		self.option_str(element.creation_options.is());
+		self.len(element.attributes.len());
+		for attribute in element.attributes {
+			self.attribute(attribute);
+		}
+		self.len(element.properties.len());
+		for property in element.properties {
+			self.str(property.name);
+			self.property_value(property.value);
+		}
+		self.unordered(element.classes, |hasher, class| hasher.str(class));
+		self.unordered(element.styles, Self::style);
+		self.len(element.event_bindings.len());
+		for event_binding in element.event_bindings {
+			self.event_binding(event_binding);
+		}
+		self.node(&element.content);
+		match element.shadow_root {
+			None => self.u8(0),
+			Some(shadow_root) => {
+				self.u8(1);
+				let options = shadow_root.options;
+				self.u8(match options.mode() {
+					ShadowRootMode::Open => 0,
+					ShadowRootMode::Closed => 1,
+				});
+				self.bool(options.delegates_focus());
+				self.u8(match options.slot_assignment() {
+					SlotAssignmentMode::Named => 0,
+					SlotAssignmentMode::Manual => 1,
+				});
+				self.node(&shadow_root.content);
+				self.callback(shadow_root.dom_binding.as_ref());
+			}
+		}
+	}
+
+	fn attribute(&mut self, attribute: &Attribute<'_>) {
+		self.str(attribute.name);
+		self.attribute_value(attribute.value);
+	}
+
+	/// By rendered form, like [`AttributeValue`]'s [`Hash`](`core::hash::Hash`) implementation.
+	fn attribute_value(&mut self, value: AttributeValue<'_>) {
+		value.with_rendered(|present, bytes| {
+			self.bool(present);
+			for byte in bytes {
+				self.u8(byte);
+			}
+			// Not valid in UTF-8.
+			self.u8(0xff);
+		});
+	}
+
+	fn property_value(&mut self, value: PropertyValue<'_>) {
+		match value {
+			PropertyValue::Bool(bool) => {
+				self.u8(0);
+				self.bool(bool);
+			}
+			PropertyValue::Integer(integer) => {
+				self.u8(1);
+				self.bytes(&integer.to_le_bytes());
+			}
+			PropertyValue::Number(number) => {
+				self.u8(2);
+				self.u64(number.to_bits());
+			}
+			PropertyValue::String(string) => {
+				self.u8(3);
+				self.str(string);
+			}
+		}
+	}
+
+	fn style(&mut self, style: &StyleDeclaration<'_>) {
+		self.str(style.property);
+		self.str(style.value);
+		self.bool(style.important);
+	}
+
+	fn event_binding<S: ThreadSafety>(&mut self, event_binding: &EventBinding<'_, S>) {
+		self.str(event_binding.name);
+		self.callback(Some(&event_binding.callback));
+		let options = event_binding.options;
+		self.bool(options.capture());
+		self.bool(options.once());
+		self.bool(options.passive());
+	}
+}
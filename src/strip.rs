@@ -0,0 +1,303 @@
+//! Removal of all callbacks from a VDOM graph, for example for server-side rendering, snapshot tests or cross-thread handoff.
+//!
+//! [`Node::strip_callbacks`] copies a [`Node`] of either [`ThreadSafety`] into an [`Arena`] as [`ThreadSafe`] [`Node`],
+//! with all `dom_binding`s set to [`None`] and all [`Element::event_bindings`] emptied.
+//!
+//! Subtrees that don't contain callbacks aren't copied. They are reused from the original graph instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bumpalo::Bump;
+//! use lignin::{arena::Arena, strip::StripOptions, Element, ElementCreationOptions, Node, ThreadBound, ThreadSafe};
+//! # struct Frame(Bump);
+//! # impl Arena for Frame {
+//! #   fn alloc<T: Copy>(&self, value: T) -> &T { self.0.alloc(value) }
+//! #   fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T] { self.0.alloc_slice_copy(slice) }
+//! #   fn alloc_str(&self, string: &str) -> &str { self.0.alloc_str(string) }
+//! #   fn alloc_slice_fill_with<T: Copy>(&self, len: usize, f: impl FnMut(usize) -> T) -> &[T] { self.0.alloc_slice_fill_with(len, f) }
+//! # }
+//!
+//! let element = Element {
+//!   name: "DIV",
+//!   creation_options: ElementCreationOptions::new(),
+//!   attributes: &[],
+//!   properties: &[],
+//!   classes: &[],
+//!   styles: &[],
+//!   content: "Hello!".into(),
+//!   shadow_root: None,
+//!   event_bindings: &[],
+//! };
+//! let node: Node<ThreadBound> = Node::HtmlElement { element: &element, dom_binding: None };
+//!
+//! let arena = Frame(Bump::new());
+//! let stripped: Node<ThreadSafe> = node.strip_callbacks(&arena, StripOptions::new());
+//! assert_eq!(stripped, node);
+//! ```
+
+use crate::{
+	arena::Arena, Attribute, AttributeValue, Element, Node, Portal, ReorderableFragment,
+	ShadowRoot, ThreadSafe, ThreadSafety,
+};
+use core::{cmp::Ordering, ptr::addr_of};
+
+/// Options for [`Node::strip_callbacks`].
+///
+/// # Options
+///
+/// ## `event_names_attribute`
+///
+/// If set, elements with [`Element::event_bindings`] receive an additional [`Attribute`] with this name,
+/// with the bindings' [`name`](`crate::EventBinding::name`)s as [`AttributeValue::Tokens`] in their original order.
+///
+/// This keeps the information which events are handled where, for example for hydration or snapshot tests.
+///
+/// Defaults to [`None`], which drops event bindings without a trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StripOptions<'a> {
+	event_names_attribute: Option<&'a str>,
+}
+impl<'a> Default for StripOptions<'a> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+#[allow(clippy::inline_always)] // Trivial getters and setters.
+impl<'a> StripOptions<'a> {
+	/// Creates a new [`StripOptions`] that drops event bindings without a trace.
+	#[inline(always)]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			event_names_attribute: None,
+		}
+	}
+
+	/// Retrieves the name of the [`Attribute`] that stripped event names are kept in, if any.
+	#[inline(always)]
+	#[must_use]
+	pub const fn event_names_attribute(&self) -> Option<&'a str> {
+		self.event_names_attribute
+	}
+	/// Sets the name of the [`Attribute`] that stripped event names are kept in, if any.
+	#[inline(always)]
+	pub fn set_event_names_attribute(&mut self, event_names_attribute: Option<&'a str>) {
+		self.event_names_attribute = event_names_attribute
+	}
+	/// Sets the name of the [`Attribute`] that stripped event names are kept in, if any.
+	#[inline(always)]
+	#[must_use]
+	pub const fn with_event_names_attribute(self, event_names_attribute: Option<&'a str>) -> Self {
+		#[allow(clippy::needless_update)]
+		Self {
+			event_names_attribute,
+			..self
+		}
+	}
+}
+
+impl<'a, S: ThreadSafety> Node<'a, S> {
+	/// Copies this [`Node`] into `arena` as [`ThreadSafe`] [`Node`] without any callbacks.
+	///
+	/// All `dom_binding`s are set to [`None`] and all [`Element::event_bindings`] are emptied.
+	/// Subtrees without callbacks are reused rather than copied, so this [`Node`] itself is returned (reinterpreted) if it contains no callbacks at all.
+	///
+	/// [`Node::Memoized::state_key`]s are kept, as stripping is deterministic.
+	#[must_use]
+	pub fn strip_callbacks<'b, A: Arena + ?Sized>(
+		&self,
+		arena: &'b A,
+		options: StripOptions<'b>,
+	) -> Node<'b, ThreadSafe>
+	where
+		'a: 'b,
+	{
+		let stripper = Stripper { arena, options };
+		or_reuse(stripper.node(self), *self)
+	}
+}
+
+struct Stripper<'b, A: ?Sized> {
+	arena: &'b A,
+	options: StripOptions<'b>,
+}
+
+/// Takes `stripped` if present, or otherwise reinterprets the unchanged `original`.
+///
+/// `stripped` **must** only be [`None`] if `original` (transitively) contains no callbacks,
+/// and `T` and `U` **must** be the same VDOM type, or a reference to or slice of it, except for [`ThreadSafety`] and a shorter lifetime.
+fn or_reuse<T: Copy, U: Copy>(stripped: Option<U>, original: T) -> U {
+	stripped.unwrap_or_else(|| unsafe {
+		// SAFETY:
+		// All VDOM types share their layout across `ThreadSafety`s (see `tests/layout.rs`),
+		// and without callbacks there's nothing that could be thread-bound.
+		*addr_of!(original).cast()
+	})
+}
+
+/// The methods return [`None`] iff their input doesn't contain callbacks, in which case it can be reused.
+impl<'b, A: Arena + ?Sized> Stripper<'b, A> {
+	fn node<'a: 'b, S: ThreadSafety>(&self, node: &Node<'a, S>) -> Option<Node<'b, ThreadSafe>> {
+		match *node {
+			Node::Comment {
+				comment,
+				dom_binding,
+			} => dom_binding.map(|_| Node::Comment {
+				comment,
+				dom_binding: None,
+			}),
+			Node::HtmlElement {
+				element,
+				dom_binding,
+			} => self
+				.element(element, dom_binding.is_some())
+				.map(|element| Node::HtmlElement {
+					element,
+					dom_binding: None,
+				}),
+			Node::MathMlElement {
+				element,
+				dom_binding,
+			} => self
+				.element(element, dom_binding.is_some())
+				.map(|element| Node::MathMlElement {
+					element,
+					dom_binding: None,
+				}),
+			Node::SvgElement {
+				element,
+				dom_binding,
+			} => self
+				.element(element, dom_binding.is_some())
+				.map(|element| Node::SvgElement {
+					element,
+					dom_binding: None,
+				}),
+			Node::Memoized { state_key, content } => {
+				self.node(content).map(|content| Node::Memoized {
+					state_key,
+					content: self.arena.alloc(content),
+				})
+			}
+			Node::Multi(nodes) => self.slice(nodes, |node| self.node(node)).map(Node::Multi),
+			Node::Keyed(pairs) => self
+				.slice(pairs, |pair| {
+					self.node(&pair.content).map(|content| ReorderableFragment {
+						dom_key: pair.dom_key,
+						content,
+					})
+				})
+				.map(Node::Keyed),
+			Node::Text { text, dom_binding } => dom_binding.map(|_| Node::Text {
+				text,
+				dom_binding: None,
+			}),
+			Node::Portal(portal) => {
+				let content = self.node(&portal.content);
+				if content.is_none() && portal.dom_binding.is_none() {
+					return None;
+				}
+				Some(Node::Portal(self.arena.alloc(Portal {
+					target: portal.target,
+					content: or_reuse(content, portal.content),
+					dom_binding: None,
+				})))
+			}
+			Node::HeadContribution {
+				element,
+				dom_binding,
+			} => {
+				self.element(element, dom_binding.is_some())
+					.map(|element| Node::HeadContribution {
+						element,
+						dom_binding: None,
+					})
+			}
+			Node::RawHtml(_) | Node::RemnantSite(_) => None,
+		}
+	}
+
+	/// Iff `has_dom_binding`, the host [`Node`] must be replaced, so a (possibly reinterpreted) [`Element`] is always returned.
+	fn element<'a: 'b, S: ThreadSafety>(
+		&self,
+		element: &'a Element<'a, S>,
+		has_dom_binding: bool,
+	) -> Option<&'b Element<'b, ThreadSafe>> {
+		let content = self.node(&element.content);
+		let shadow_root = element
+			.shadow_root
+			.map(|shadow_root| (self.shadow_root(shadow_root), shadow_root));
+		let event_bindings = element.event_bindings;
+		if content.is_none()
+			&& !matches!(shadow_root, Some((Some(_), _)))
+			&& event_bindings.is_empty()
+		{
+			return has_dom_binding.then(|| or_reuse(None, element));
+		}
+
+		let attributes = match self.options.event_names_attribute() {
+			Some(name) if !event_bindings.is_empty() => {
+				let attributes = element.attributes;
+				let names = self
+					.arena
+					.alloc_slice_fill_with(event_bindings.len(), |i| event_bindings[i].name);
+				self.arena.alloc_slice_fill_with(attributes.len() + 1, |i| {
+					attributes.get(i).copied().unwrap_or(Attribute {
+						name,
+						value: AttributeValue::Tokens(names),
+					})
+				})
+			}
+			_ => element.attributes,
+		};
+
+		Some(self.arena.alloc(Element {
+			name: element.name,
+			creation_options: element.creation_options,
+			attributes,
+			properties: element.properties,
+			classes: element.classes,
+			styles: element.styles,
+			content: or_reuse(content, element.content),
+			shadow_root: shadow_root.map(|(stripped, original)| or_reuse(stripped, original)),
+			event_bindings: &[],
+		}))
+	}
+
+	fn shadow_root<'a: 'b, S: ThreadSafety>(
+		&self,
+		shadow_root: &'a ShadowRoot<'a, S>,
+	) -> Option<&'b ShadowRoot<'b, ThreadSafe>> {
+		let content = self.node(&shadow_root.content);
+		if content.is_none() && shadow_root.dom_binding.is_none() {
+			return None;
+		}
+		Some(self.arena.alloc(ShadowRoot {
+			options: shadow_root.options,
+			content: or_reuse(content, shadow_root.content),
+			dom_binding: None,
+		}))
+	}
+
+	/// Allocates a new slice only if any item was stripped, reusing the unchanged items around it.
+	fn slice<'a: 'b, T: Copy, U: Copy>(
+		&self,
+		items: &'a [T],
+		strip: impl Fn(&'a T) -> Option<U>,
+	) -> Option<&'b [U]> {
+		let (first, stripped) = items
+			.iter()
+			.enumerate()
+			.find_map(|(i, item)| Some((i, strip(item)?)))?;
+		let mut stripped = Some(stripped);
+		Some(
+			self.arena
+				.alloc_slice_fill_with(items.len(), |i| match i.cmp(&first) {
+					Ordering::Less => or_reuse(None, items[i]),
+					Ordering::Equal => or_reuse(stripped.take(), items[i]),
+					Ordering::Greater => or_reuse(strip(&items[i]), items[i]),
+				}),
+		)
+	}
+}
@@ -4,7 +4,7 @@
 //! Without it, they become [uninhabited](https://doc.rust-lang.org/nomicon/exotic-sizes.html#empty-types) and are erased entirely at compile-time, so any code paths that depend on them can in turn be removed too.
 #![allow(clippy::inline_always)]
 
-use crate::sealed::Sealed;
+use crate::{callback_registry::CallbackParameter, sealed::Sealed};
 
 /// Used as DOM reference callback parameter. (Expand for implementation contract!)
 ///
@@ -90,15 +90,33 @@ web_types! {
 	/// Erasable stand-in for [`web_sys::Comment`](https://docs.rs/web-sys/0.3/web_sys/struct.Comment.html) used as callback parameter.
 	(Comment, "Comment") => web_sys::Comment,
 
+	/// Erasable stand-in for [`web_sys::CustomEvent`](https://docs.rs/web-sys/0.3/web_sys/struct.CustomEvent.html) used as callback parameter.
+	(CustomEvent, "CustomEvent") => web_sys::CustomEvent,
+
 	/// Erasable stand-in for [`web_sys::Element`](https://docs.rs/web-sys/0.3/web_sys/struct.Element.html) used as callback parameter.
 	(Element, "Element") => web_sys::Element,
 
 	/// Erasable stand-in for [`web_sys::Event`](https://docs.rs/web-sys/0.3/web_sys/struct.Event.html) used as callback parameter.
 	(Event, "Event") => web_sys::Event,
 
+	/// Erasable stand-in for [`web_sys::FocusEvent`](https://docs.rs/web-sys/0.3/web_sys/struct.FocusEvent.html) used as callback parameter.
+	(FocusEvent, "FocusEvent") => web_sys::FocusEvent,
+
 	/// Erasable stand-in for [`web_sys::HtmlElement`](https://docs.rs/web-sys/0.3/web_sys/struct.HtmlElement.html) used as callback parameter.
 	(HtmlElement, "HtmlElement") => web_sys::HtmlElement,
 
+	/// Erasable stand-in for [`web_sys::InputEvent`](https://docs.rs/web-sys/0.3/web_sys/struct.InputEvent.html) used as callback parameter.
+	(InputEvent, "InputEvent") => web_sys::InputEvent,
+
+	/// Erasable stand-in for [`web_sys::KeyboardEvent`](https://docs.rs/web-sys/0.3/web_sys/struct.KeyboardEvent.html) used as callback parameter.
+	(KeyboardEvent, "KeyboardEvent") => web_sys::KeyboardEvent,
+
+	/// Erasable stand-in for [`web_sys::MouseEvent`](https://docs.rs/web-sys/0.3/web_sys/struct.MouseEvent.html) used as callback parameter.
+	(MouseEvent, "MouseEvent") => web_sys::MouseEvent,
+
+	/// Erasable stand-in for [`web_sys::ShadowRoot`](https://docs.rs/web-sys/0.3/web_sys/struct.ShadowRoot.html) used as callback parameter.
+	(ShadowRoot, "ShadowRoot") => web_sys::ShadowRoot,
+
 	/// Erasable stand-in for [`web_sys::SvgElement`](https://docs.rs/web-sys/0.3/web_sys/struct.SvgElement.html) used as callback parameter.
 	(SvgElement, "HtmlElement") => web_sys::SvgElement,
 
@@ -174,13 +192,40 @@ impl<T: Materialize<U>, U> Materialize<DomRef<U>> for DomRef<T> {
 
 conversions! {
 	Comment => web_sys::Comment,
+	CustomEvent => web_sys::CustomEvent,
 	Element => web_sys::Element,
 	Event => web_sys::Event,
+	FocusEvent => web_sys::FocusEvent,
 	HtmlElement => web_sys::HtmlElement,
+	InputEvent => web_sys::InputEvent,
+	KeyboardEvent => web_sys::KeyboardEvent,
+	MouseEvent => web_sys::MouseEvent,
+	ShadowRoot => web_sys::ShadowRoot,
 	SvgElement => web_sys::SvgElement,
 	Text => web_sys::Text,
 }
 
+crate::callback_parameter!(
+	CustomEvent,
+	Event,
+	FocusEvent,
+	InputEvent,
+	KeyboardEvent,
+	MouseEvent,
+);
+
+/// Marks [`Event`] and the stand-ins for its subtypes.
+///
+/// This trait is sealed. All implementors are ABI-compatible with [`Event`],
+/// which is what allows [`CallbackRef::upcast`](`crate::CallbackRef::upcast`) to turn typed event callbacks into ones that accept any [`Event`].
+pub trait EventStandIn: CallbackParameter + Sealed {}
+impl EventStandIn for CustomEvent {}
+impl EventStandIn for Event {}
+impl EventStandIn for FocusEvent {}
+impl EventStandIn for InputEvent {}
+impl EventStandIn for KeyboardEvent {}
+impl EventStandIn for MouseEvent {}
+
 /// Empty. Replaces erasable values in this module if the `"callbacks"` feature is not active.
 #[doc(hidden)]
 #[allow(clippy::empty_enum)]
@@ -0,0 +1,586 @@
+//! XML serialisation of [`Node`] graphs, for standalone SVG and [MathML](https://developer.mozilla.org/en-US/docs/Web/MathML) files and XHTML documents.
+//!
+//! Unlike [`html`](`crate::html`), this module writes namespace declarations where the element namespace changes,
+//! closes all empty elements with `/>` and wraps `<script>` and `<style>` content in `CDATA` sections.
+//! HTML element names are written in lowercase, as XHTML requires. Other element names are written as-is.
+//!
+//! Like [`html`](`crate::html`), these functions don't allocate and ignore callbacks.
+//! Content that can't be represented as XML is refused with an [`Error`] instead.
+//! Note that the output may be incomplete in that case.
+//!
+//! [`Node::Portal`] and [`Node::HeadContribution`] write nothing at their logical position.
+//! [`write_document`] collects the latter into `<head>`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use lignin::{xml, Attribute, AttributeValue, Element, ElementCreationOptions, Node, ThreadSafe};
+//!
+//! let circle_attributes = [Attribute::new("r", AttributeValue::Integer(5))];
+//! let circle = Element {
+//!   name: "circle",
+//!   creation_options: ElementCreationOptions::new(),
+//!   attributes: &circle_attributes,
+//!   properties: &[],
+//!   classes: &[],
+//!   styles: &[],
+//!   content: Node::Multi(&[]),
+//!   shadow_root: None,
+//!   event_bindings: &[],
+//! };
+//! let svg_content = [circle.as_svg()];
+//! let svg = Element {
+//!   name: "svg",
+//!   content: Node::Multi(&svg_content),
+//!   attributes: &[],
+//!   ..circle
+//! };
+//!
+//! let node: Node<ThreadSafe> = svg.as_svg();
+//!
+//! let mut file = String::new();
+//! xml::write_standalone(&node, &mut file).unwrap();
+//! assert_eq!(
+//!   file,
+//!   r#"<?xml version="1.0" encoding="UTF-8"?><svg xmlns="http://www.w3.org/2000/svg"><circle r="5"/></svg>"#,
+//! );
+//! ```
+
+use crate::{
+	head,
+	html::{self, ElementKind},
+	Attribute, AttributeValue, Document, Element, Node, PropertyValue, ThreadSafety, TrustedHtml,
+};
+use core::fmt::{self, Display, Formatter, Write};
+
+/// Reasons why a [`Node`] graph can't be serialised as XML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Error<'a> {
+	/// The [`fmt::Write`] target returned an error.
+	Format,
+	/// An element or attribute name isn't a valid XML name.
+	InvalidName(&'a str),
+	/// A [`Node::Comment`] contains `--` or ends with `-`.
+	///
+	/// See <https://www.w3.org/TR/xml/#sec-comments>.
+	InvalidComment(&'a str),
+	/// Text, a comment or an attribute value contains a character that isn't allowed in XML 1.0, like most C0 controls.
+	///
+	/// Contains the text, comment or attribute name.
+	///
+	/// See <https://www.w3.org/TR/xml/#charsets>.
+	InvalidCharacter(&'a str),
+	/// An attribute has a prefix other than `xlink`, `xml` or `xmlns` (see [`Attribute::namespace`]),
+	/// or an `xmlns` attribute doesn't match the element's namespace.
+	UnsupportedNamespace(&'a str),
+	/// The content of a `<script>` or `<style>` element is not plain text.
+	InvalidRawText(&'a str),
+	/// XML has no representation for shadow roots, so [`Element::shadow_root`] must be [`None`].
+	///
+	/// Contains the host's name.
+	UnsupportedShadowRoot(&'a str),
+	/// [`Node::RawHtml`] can't be represented as XML.
+	UnsupportedRawHtml(TrustedHtml<'a>),
+	/// The [`Node`] passed to [`write_standalone`] doesn't consist of exactly one element, optionally surrounded by comments and whitespace.
+	InvalidRoot,
+}
+
+impl<'a> From<fmt::Error> for Error<'a> {
+	fn from(_: fmt::Error) -> Self {
+		Self::Format
+	}
+}
+
+impl<'a> Display for Error<'a> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Format => f.write_str("formatter error"),
+			Error::InvalidName(name) => write!(f, "invalid name {:?}", name),
+			Error::InvalidComment(comment) => write!(f, "invalid comment {:?}", comment),
+			Error::InvalidCharacter(text) => write!(f, "invalid character in {:?}", text),
+			Error::UnsupportedNamespace(name) => {
+				write!(f, "unsupported namespace on attribute {:?}", name)
+			}
+			Error::InvalidRawText(name) => write!(f, "invalid raw text in {:?}", name),
+			Error::UnsupportedShadowRoot(name) => {
+				write!(f, "unsupported shadow root on {:?}", name)
+			}
+			Error::UnsupportedRawHtml(html) => {
+				write!(f, "unsupported raw HTML: {:?}", html.as_str())
+			}
+			Error::InvalidRoot => f.write_str("not exactly one root element"),
+		}
+	}
+}
+
+/// Serialises `node` as XML fragment into `w`.
+///
+/// Each top-level element declares its namespace with an `xmlns` attribute.
+///
+/// # Errors
+///
+/// Iff `node` contains anything that can't be represented as XML, or if `w` returns an error. See [`Error`].
+pub fn write_node<'a, S: ThreadSafety>(
+	node: &Node<'a, S>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	write_node_in(node, None, w)
+}
+
+/// Serialises `element` as `kind` of element into `w`, including its attributes, properties, classes and styles.
+///
+/// The element declares its namespace with an `xmlns` attribute.
+/// Reflected properties, classes and styles are handled as by [`html::Serializer::write_element`].
+///
+/// # Errors
+///
+/// Iff `element` contains anything that can't be represented as XML, or if `w` returns an error. See [`Error`].
+pub fn write_element<'a, S: ThreadSafety>(
+	element: &Element<'a, S>,
+	kind: ElementKind,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	write_element_in(element, kind, None, w)
+}
+
+/// Serialises `node` as standalone XML document, for example an `.svg` file, into `w`.
+///
+/// This writes an XML declaration followed by `node`.
+///
+/// # Errors
+///
+/// Iff `node` isn't exactly one element, optionally surrounded by comments and whitespace,
+/// contains anything that can't be represented as XML, or if `w` returns an error. See [`Error`].
+pub fn write_standalone<'a, S: ThreadSafety>(
+	node: &Node<'a, S>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	if count_roots(node)? != 1 {
+		return Err(Error::InvalidRoot);
+	}
+	w.write_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+	write_node(node, w)
+}
+
+/// Serialises `document` as XHTML document into `w`.
+///
+/// This writes an XML declaration, the doctype and an `<html>` element in the XHTML namespace, with explicit `<head>` and `<body>`.
+/// [`Node::HeadContribution`]s from anywhere in the document are written at the end of `<head>`, as by [`html::Serializer::write_document`].
+///
+/// # Errors
+///
+/// Iff `document` contains anything that can't be represented as XML, or if `w` returns an error. See [`Error`].
+pub fn write_document<'a, S: ThreadSafety>(
+	document: &Document<'a, S>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	if !is_valid_name(document.doctype) {
+		return Err(Error::InvalidName(document.doctype));
+	}
+	write!(
+		w,
+		r#"<?xml version="1.0" encoding="UTF-8"?><!DOCTYPE {}><html xmlns="{}""#,
+		document.doctype,
+		ElementKind::Html.namespace(),
+	)?;
+	write_attributes(document.html_attributes, ElementKind::Html, |_| false, w)?;
+	w.write_str("><head>")?;
+	write_node_in(&document.head, Some(ElementKind::Html), w)?;
+	head::for_each_contribution(&[&document.head, &document.body], |element, _| {
+		write_element_in(element, ElementKind::Html, Some(ElementKind::Html), w)
+	})?;
+	w.write_str("</head><body>")?;
+	write_node_in(&document.body, Some(ElementKind::Html), w)?;
+	w.write_str("</body></html>")?;
+	Ok(())
+}
+
+fn write_node_in<'a, S: ThreadSafety>(
+	node: &Node<'a, S>,
+	parent: Option<ElementKind>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	match *node {
+		Node::Comment { comment, .. } => {
+			if comment.contains("--") || comment.ends_with('-') {
+				return Err(Error::InvalidComment(comment));
+			}
+			if !is_valid_text(comment) {
+				return Err(Error::InvalidCharacter(comment));
+			}
+			write!(w, "<!--{}-->", comment)?;
+		}
+		Node::HtmlElement { element, .. } => {
+			write_element_in(element, ElementKind::Html, parent, w)?
+		}
+		Node::MathMlElement { element, .. } => {
+			write_element_in(element, ElementKind::MathMl, parent, w)?
+		}
+		Node::SvgElement { element, .. } => write_element_in(element, ElementKind::Svg, parent, w)?,
+		Node::Memoized { content, .. } => write_node_in(content, parent, w)?,
+		Node::Multi(nodes) => {
+			for node in nodes {
+				write_node_in(node, parent, w)?;
+			}
+		}
+		Node::Keyed(pairs) => {
+			for pair in pairs {
+				write_node_in(&pair.content, parent, w)?;
+			}
+		}
+		Node::Text { text, .. } => write_text(text, false, text, w)?,
+		Node::RawHtml(html) => return Err(Error::UnsupportedRawHtml(html)),
+		Node::Portal(_) | Node::HeadContribution { .. } => (),
+		Node::RemnantSite(_) => {
+			todo!("RemnantSite XML serialisation")
+		}
+	}
+	Ok(())
+}
+
+fn write_element_in<'a, S: ThreadSafety>(
+	element: &Element<'a, S>,
+	kind: ElementKind,
+	parent: Option<ElementKind>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	let name = element.name;
+	if !is_valid_name(name) {
+		return Err(Error::InvalidName(name));
+	}
+	if element.shadow_root.is_some() {
+		return Err(Error::UnsupportedShadowRoot(name));
+	}
+	let is_html = matches!(kind, ElementKind::Html);
+
+	w.write_char('<')?;
+	write_element_name(name, kind, w)?;
+	if parent != Some(kind) {
+		write!(w, r#" xmlns="{}""#, kind.namespace())?;
+	}
+	if let Some(is) = element.creation_options.is() {
+		write_attribute("is", AttributeValue::String(is), w)?;
+	}
+	write_attributes(
+		element.attributes,
+		kind,
+		|attribute| {
+			(attribute.name == "class" && !element.classes.is_empty())
+				|| (attribute.name == "style" && !element.styles.is_empty())
+				|| element
+					.properties
+					.iter()
+					.any(|property| property.reflected_attribute() == Some(attribute.name))
+		},
+		w,
+	)?;
+	let mut textarea_value = None;
+	for property in element.properties {
+		if is_html && property.name == "value" && name.eq_ignore_ascii_case("textarea") {
+			textarea_value = Some(property.value);
+			continue;
+		}
+		if let Some(attribute_name) = property.reflected_attribute() {
+			write_attribute(attribute_name, html::reflected_value(property.value), w)?;
+		}
+	}
+	if !element.classes.is_empty() {
+		write_attribute("class", AttributeValue::Tokens(element.classes), w)?;
+	}
+	if element.styles.iter().any(html::is_safe_style) {
+		w.write_str(" style=\"")?;
+		for (i, style) in element
+			.styles
+			.iter()
+			.filter(|s| html::is_safe_style(s))
+			.enumerate()
+		{
+			if i > 0 {
+				w.write_str("; ")?;
+			}
+			let mut escaping = EscapingWriter::new(w, true);
+			write!(escaping, "{}", style).map_err(|_| escaping.error(style.value))?;
+		}
+		w.write_char('"')?;
+	}
+
+	if let Some(value) = textarea_value {
+		w.write_char('>')?;
+		let mut escaping = EscapingWriter::new(w, false);
+		match value {
+			PropertyValue::Bool(bool) => write!(escaping, "{}", bool),
+			PropertyValue::Integer(integer) => write!(escaping, "{}", integer),
+			PropertyValue::Number(number) => write!(escaping, "{}", AttributeValue::Float(number)),
+			PropertyValue::String(string) => escaping.write_str(string),
+		}
+		.map_err(|_| escaping.error(name))?;
+	} else if element.content.dom_empty() && !is_raw_html(&element.content) {
+		w.write_str("/>")?;
+		return Ok(());
+	} else if name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style") {
+		w.write_char('>')?;
+		write_cdata(name, &element.content, w)?;
+	} else {
+		w.write_char('>')?;
+		write_node_in(&element.content, Some(kind), w)?;
+	}
+
+	w.write_str("</")?;
+	write_element_name(name, kind, w)?;
+	w.write_char('>')?;
+	Ok(())
+}
+
+/// HTML element names are lowercase in XHTML.
+fn write_element_name(name: &str, kind: ElementKind, w: &mut impl Write) -> fmt::Result {
+	match kind {
+		ElementKind::Html => name
+			.chars()
+			.try_for_each(|c| w.write_char(c.to_ascii_lowercase())),
+		ElementKind::MathMl | ElementKind::Svg => w.write_str(name),
+	}
+}
+
+/// Writes namespace declarations and then all present `attributes` for which `skip` returns `false`.
+fn write_attributes<'a>(
+	attributes: &'a [Attribute<'a>],
+	kind: ElementKind,
+	skip: impl Fn(&Attribute<'a>) -> bool,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	let declared = |attribute: &Attribute<'a>| match attribute.namespace() {
+		// The `xml` prefix is predeclared and `xmlns` attributes are declarations themselves.
+		None | Some(Attribute::XML_NAMESPACE | Attribute::XMLNS_NAMESPACE) => false,
+		Some(_) => attribute.value.is_present() && !skip(attribute),
+	};
+	for (i, attribute) in attributes.iter().enumerate() {
+		if !declared(attribute) {
+			continue;
+		}
+		let prefix = attribute.prefix().expect("unreachable");
+		if !attributes[..i]
+			.iter()
+			.any(|previous| declared(previous) && previous.prefix() == Some(prefix))
+		{
+			write!(w, " xmlns:{}=\"", prefix)?;
+			write_text(
+				attribute.namespace().expect("unreachable"),
+				true,
+				attribute.name,
+				w,
+			)?;
+			w.write_char('"')?;
+		}
+	}
+
+	for attribute in attributes {
+		if !attribute.value.is_present() || skip(attribute) {
+			continue;
+		}
+		if attribute.name == "xmlns" {
+			// Already declared for the element itself.
+			let matches = matches!(attribute.value, AttributeValue::String(namespace) if namespace == kind.namespace());
+			if !matches {
+				return Err(Error::UnsupportedNamespace(attribute.name));
+			}
+			continue;
+		} else if attribute.namespace().is_none() && attribute.name.contains(':') {
+			return Err(Error::UnsupportedNamespace(attribute.name));
+		}
+		write_attribute(attribute.name, attribute.value, w)?;
+	}
+	Ok(())
+}
+
+fn write_attribute<'a>(
+	name: &'a str,
+	value: AttributeValue<'a>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	if !is_valid_name(name) {
+		return Err(Error::InvalidName(name));
+	}
+	match value {
+		AttributeValue::Bool(false) => (),
+		// XML has no minimised attributes.
+		AttributeValue::Bool(true) => write!(w, " {}=\"{}\"", name, name)?,
+		value => {
+			write!(w, " {}=\"", name)?;
+			let mut escaping = EscapingWriter::new(w, true);
+			write!(escaping, "{}", value).map_err(|_| escaping.error(name))?;
+			w.write_char('"')?;
+		}
+	}
+	Ok(())
+}
+
+fn write_text<'a>(
+	text: &str,
+	quot: bool,
+	source: &'a str,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	let mut escaping = EscapingWriter::new(w, quot);
+	escaping.write_str(text).map_err(|_| escaping.error(source))
+}
+
+/// Writes the text in `content` as `CDATA` sections, splitting them around any `]]>`.
+fn write_cdata<'a, S: ThreadSafety>(
+	name: &'a str,
+	content: &Node<'a, S>,
+	w: &mut impl Write,
+) -> Result<(), Error<'a>> {
+	match *content {
+		Node::Text { text, .. } => {
+			if !is_valid_text(text) {
+				return Err(Error::InvalidCharacter(text));
+			}
+			if !text.is_empty() {
+				w.write_str("<![CDATA[")?;
+				let mut rest = text;
+				while let Some(i) = rest.find("]]>") {
+					w.write_str(&rest[..i + 2])?;
+					w.write_str("]]><![CDATA[")?;
+					rest = &rest[i + 2..];
+				}
+				w.write_str(rest)?;
+				w.write_str("]]>")?;
+			}
+		}
+		Node::Memoized { content, .. } => write_cdata(name, content, w)?,
+		Node::Multi(nodes) => {
+			for node in nodes {
+				write_cdata(name, node, w)?;
+			}
+		}
+		Node::Keyed(pairs) => {
+			for pair in pairs {
+				write_cdata(name, &pair.content, w)?;
+			}
+		}
+		_ => return Err(Error::InvalidRawText(name)),
+	}
+	Ok(())
+}
+
+/// Counts the elements in `node` that [`write_node`] writes at top level, refusing non-whitespace text.
+fn count_roots<'a, S: ThreadSafety>(node: &Node<'a, S>) -> Result<usize, Error<'a>> {
+	Ok(match *node {
+		Node::HtmlElement { .. } | Node::MathMlElement { .. } | Node::SvgElement { .. } => 1,
+		Node::Text { text, .. } => {
+			if !text
+				.bytes()
+				.all(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+			{
+				return Err(Error::InvalidRoot);
+			}
+			0
+		}
+		Node::Memoized { content, .. } => count_roots(content)?,
+		Node::Multi(nodes) => {
+			let mut count = 0;
+			for node in nodes {
+				count += count_roots(node)?;
+			}
+			count
+		}
+		Node::Keyed(pairs) => {
+			let mut count = 0;
+			for pair in pairs {
+				count += count_roots(&pair.content)?;
+			}
+			count
+		}
+		Node::RawHtml(html) => return Err(Error::UnsupportedRawHtml(html)),
+		Node::Comment { .. }
+		| Node::Portal(_)
+		| Node::HeadContribution { .. }
+		| Node::RemnantSite(_) => 0,
+	})
+}
+
+/// Whether `node` is (optionally [`Memoized`](`Node::Memoized`)) [`Node::RawHtml`], which [`write_node`] refuses.
+fn is_raw_html<S: ThreadSafety>(node: &Node<'_, S>) -> bool {
+	match node {
+		Node::RawHtml(_) => true,
+		Node::Memoized { content, .. } => is_raw_html(content),
+		_ => false,
+	}
+}
+
+/// Escapes `&`, `<`, `>`, `\r` and optionally `"`, `\t` and `\n`, and refuses characters that aren't allowed in XML.
+struct EscapingWriter<'w, W: Write> {
+	w: &'w mut W,
+	quot: bool,
+	invalid: bool,
+}
+impl<'w, W: Write> EscapingWriter<'w, W> {
+	fn new(w: &'w mut W, quot: bool) -> Self {
+		Self {
+			w,
+			quot,
+			invalid: false,
+		}
+	}
+
+	/// Tells invalid characters apart from errors returned by the inner [`Write`].
+	fn error<'a>(&self, source: &'a str) -> Error<'a> {
+		if self.invalid {
+			Error::InvalidCharacter(source)
+		} else {
+			Error::Format
+		}
+	}
+}
+impl<'w, W: Write> Write for EscapingWriter<'w, W> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if !is_valid_text(s) {
+			self.invalid = true;
+			return Err(fmt::Error);
+		}
+		let mut rest = s;
+		while let Some(i) = rest.find(|c| matches!(c, '&' | '<' | '>' | '\r' | '"' | '\t' | '\n')) {
+			let (verbatim, special) = rest.split_at(i);
+			self.w.write_str(verbatim)?;
+			let c = special.chars().next().expect("unreachable");
+			self.w.write_str(match c {
+				'&' => "&amp;",
+				'<' => "&lt;",
+				'>' => "&gt;",
+				'\r' => "&#13;",
+				// Attribute value normalisation would replace these.
+				'"' if self.quot => "&quot;",
+				'\t' if self.quot => "&#9;",
+				'\n' if self.quot => "&#10;",
+				'"' => "\"",
+				'\t' => "\t",
+				_ => "\n",
+			})?;
+			rest = &special[c.len_utf8()..];
+		}
+		self.w.write_str(rest)
+	}
+}
+
+/// See <https://www.w3.org/TR/xml/#charsets>.
+fn is_valid_text(text: &str) -> bool {
+	text.chars().all(|c| {
+		matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{d7ff}' | '\u{e000}'..='\u{fffd}' | '\u{10000}'..='\u{10ffff}')
+	})
+}
+
+/// Conservative: Names must start with a letter, `_`, `:` or non-ASCII character and otherwise only contain these, digits, `-` and `.`.
+///
+/// See <https://www.w3.org/TR/xml/#NT-Name>.
+fn is_valid_name(name: &str) -> bool {
+	let valid = |c: char| {
+		c.is_ascii_alphanumeric()
+			|| matches!(c, '_' | ':' | '-' | '.')
+			|| (!c.is_ascii() && is_valid_text(c.encode_utf8(&mut [0; 4])) && !c.is_whitespace())
+	};
+	let mut chars = name.chars();
+	match chars.next() {
+		Some(first) if valid(first) && !matches!(first, '0'..='9' | '-' | '.') => chars.all(valid),
+		_ => false,
+	}
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "callbacks")]
+
+use lignin::{
+	callback_registry::{set_spawn_local, LocalFuture},
+	CallbackRegistration,
+};
+use std::{
+	cell::RefCell,
+	future::Future,
+	pin::Pin,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+	task::{Context, Poll, Wake, Waker},
+};
+
+thread_local! {
+	static SPAWNED: RefCell<Vec<LocalFuture>> = RefCell::default();
+	static LOG: RefCell<Vec<u32>> = RefCell::default();
+}
+
+fn spawn_local(future: LocalFuture) {
+	SPAWNED.with(|spawned| spawned.borrow_mut().push(future))
+}
+
+struct Noop;
+impl Wake for Noop {
+	fn wake(self: Arc<Self>) {}
+}
+
+/// Polls all spawned futures once, keeping pending ones.
+fn run() {
+	let waker = Waker::from(Arc::new(Noop));
+	let mut cx = Context::from_waker(&waker);
+	let spawned = SPAWNED.with(|spawned| spawned.take());
+	for mut future in spawned {
+		if Pin::new(&mut future).poll(&mut cx).is_pending() {
+			spawn_local(future)
+		}
+	}
+}
+
+#[derive(Default)]
+struct YieldOnce(bool);
+impl Future for YieldOnce {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+		if self.0 {
+			Poll::Ready(())
+		} else {
+			self.0 = true;
+			Poll::Pending
+		}
+	}
+}
+
+#[test]
+fn spawned_after_handler() {
+	set_spawn_local(spawn_local);
+
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(u32)>::new_async(receiver.as_ref(), |_, value| async move {
+			LOG.with(|log| log.borrow_mut().push(value))
+		});
+	registration.to_ref().call(1);
+	assert_eq!(SPAWNED.with(|spawned| spawned.borrow().len()), 1);
+	assert!(LOG.with(|log| log.borrow().is_empty()));
+
+	run();
+	assert_eq!(LOG.with(|log| log.take()), [1]);
+	assert!(SPAWNED.with(|spawned| spawned.borrow().is_empty()));
+}
+
+#[test]
+fn cancelled_on_drop() {
+	set_spawn_local(spawn_local);
+
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(u32)>::new_async(receiver.as_ref(), |_, value| async move {
+			YieldOnce::default().await;
+			LOG.with(|log| log.borrow_mut().push(value))
+		});
+	registration.to_ref().call(2);
+
+	run();
+	assert_eq!(SPAWNED.with(|spawned| spawned.borrow().len()), 1);
+
+	drop(registration);
+	run();
+	assert!(SPAWNED.with(|spawned| spawned.borrow().is_empty()));
+	assert!(LOG.with(|log| log.borrow().is_empty()));
+}
+
+#[test]
+fn woken_on_drop() {
+	struct Counter(AtomicUsize);
+	impl Wake for Counter {
+		fn wake(self: Arc<Self>) {
+			self.0.fetch_add(1, Ordering::Relaxed);
+		}
+	}
+
+	set_spawn_local(spawn_local);
+
+	let receiver = Box::pin(());
+	let registration = CallbackRegistration::<_, fn(u32)>::new_async(receiver.as_ref(), |_, _| {
+		std::future::pending()
+	});
+	registration.to_ref_thread_bound().call(3);
+	let mut future = SPAWNED.with(|spawned| spawned.borrow_mut().pop()).unwrap();
+
+	let counter = Arc::new(Counter(AtomicUsize::new(0)));
+	let waker = Waker::from(counter.clone());
+	let mut cx = Context::from_waker(&waker);
+	assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+	assert_eq!(counter.0.load(Ordering::Relaxed), 0);
+
+	drop(registration);
+	assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+	assert!(Pin::new(&mut future).poll(&mut cx).is_ready());
+}
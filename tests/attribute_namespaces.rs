@@ -0,0 +1,36 @@
+use lignin::Attribute;
+
+#[test]
+fn names() {
+	let xlink_href = Attribute::new("xlink:href", "#icon".into());
+	assert_eq!(xlink_href.namespace(), Some(Attribute::XLINK_NAMESPACE));
+	assert_eq!(xlink_href.prefix(), Some("xlink"));
+	assert_eq!(xlink_href.local_name(), "href");
+
+	let plain = Attribute::new("data:thing", "".into());
+	assert_eq!(plain.namespace(), None);
+	assert_eq!(plain.prefix(), None);
+	assert_eq!(plain.local_name(), "data:thing");
+}
+
+#[test]
+fn namespaces() {
+	let namespace = |name| Attribute::new(name, "".into()).namespace();
+	assert_eq!(namespace("href"), None);
+	assert_eq!(namespace("xml:lang"), Some(Attribute::XML_NAMESPACE));
+	assert_eq!(namespace("xmlns"), Some(Attribute::XMLNS_NAMESPACE));
+	assert_eq!(namespace("xmlns:svg"), Some(Attribute::XMLNS_NAMESPACE));
+	assert_eq!(namespace("xmlnsx"), None);
+	assert_eq!(namespace("xlinkx:href"), None);
+}
+
+#[test]
+fn literal() {
+	assert_eq!(
+		Attribute {
+			name: "xlink:href",
+			value: "#icon".into(),
+		},
+		Attribute::new("xlink:href", "#icon".into())
+	);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::hash;
+use lignin::AttributeValue;
+
+#[test]
+fn rendering() {
+	assert_eq!(AttributeValue::String("a&b").to_string(), "a&b");
+	assert_eq!(AttributeValue::Bool(true).to_string(), "");
+	assert_eq!(AttributeValue::Integer(-12).to_string(), "-12");
+	assert_eq!(AttributeValue::Float(1.5).to_string(), "1.5");
+	assert_eq!(AttributeValue::Float(1.0).to_string(), "1");
+	assert_eq!(AttributeValue::Float(-0.0).to_string(), "0");
+	assert_eq!(
+		AttributeValue::Float(f64::NEG_INFINITY).to_string(),
+		"-Infinity"
+	);
+	assert_eq!(AttributeValue::Tokens(&["a", "", "b"]).to_string(), "a b");
+}
+
+#[test]
+fn presence() {
+	assert!(AttributeValue::Bool(true).is_present());
+	assert!(!AttributeValue::Bool(false).is_present());
+	assert!(AttributeValue::String("").is_present());
+	assert_ne!(AttributeValue::Bool(false), AttributeValue::String(""));
+	assert!(AttributeValue::Bool(false) < AttributeValue::String(""));
+}
+
+#[test]
+fn semantic_equality() {
+	let equal = [
+		AttributeValue::Float(1.0),
+		AttributeValue::Integer(1),
+		AttributeValue::String("1"),
+		AttributeValue::Tokens(&["1"]),
+	];
+	for a in &equal {
+		for b in &equal {
+			assert_eq!(a, b);
+			assert_eq!(hash(a), hash(b));
+		}
+	}
+
+	assert_eq!(AttributeValue::Bool(true), AttributeValue::String(""));
+	assert_eq!(
+		AttributeValue::Tokens(&["a", "b"]),
+		AttributeValue::String("a b")
+	);
+	assert_eq!(
+		hash(AttributeValue::Tokens(&["a", "b"])),
+		hash(AttributeValue::String("a b"))
+	);
+	assert_ne!(AttributeValue::Integer(10), AttributeValue::Float(1.0));
+	assert!(AttributeValue::Integer(10) < AttributeValue::Integer(2));
+}
+
+#[test]
+fn longest_float() {
+	let tiny = -f64::MIN_POSITIVE * f64::EPSILON;
+	assert_eq!(AttributeValue::Float(tiny).to_string().len(), 327);
+	assert_eq!(
+		AttributeValue::Float(tiny),
+		AttributeValue::String(&tiny.to_string())
+	);
+	assert_eq!(
+		AttributeValue::Float(f64::MAX),
+		AttributeValue::String(&f64::MAX.to_string())
+	);
+}
@@ -0,0 +1,66 @@
+use lignin::{
+	auto_safety::{Align as _, AutoSafe, Deanonymize as _},
+	Node, ThreadBound, ThreadSafe,
+};
+
+fn safe<'a>() -> Node<'a, ThreadSafe> {
+	Node::Multi(&[])
+}
+fn bound<'a>() -> Node<'a, ThreadBound> {
+	Node::Multi(&[])
+}
+
+fn inferred_option_safe<'a>() -> impl AutoSafe<Option<Node<'a, ThreadBound>>> {
+	Some(safe())
+}
+fn inferred_option_bound<'a>() -> impl AutoSafe<Option<Node<'a, ThreadBound>>> {
+	Some(bound())
+}
+
+fn inferred_ref_safe() -> impl AutoSafe<&'static Node<'static, ThreadBound>> {
+	&Node::Multi(&[]) as &Node<ThreadSafe>
+}
+
+fn inferred_array_safe<'a>() -> impl AutoSafe<[Node<'a, ThreadBound>; 2]> {
+	[safe(), safe()]
+}
+fn inferred_array_bound<'a>() -> impl AutoSafe<[Node<'a, ThreadBound>; 2]> {
+	[safe().align(), bound()]
+}
+
+fn inferred_slice_safe() -> impl AutoSafe<&'static [Node<'static, ThreadBound>]> {
+	&[] as &[Node<ThreadSafe>]
+}
+
+fn assert_safe<T: Send + Sync>(_: T) {}
+
+#[test]
+fn deanonymize() {
+	let option: Option<Node<ThreadSafe>> = inferred_option_safe().deanonymize();
+	assert_eq!(option, Some(safe()));
+	let option: Option<Node<ThreadBound>> = inferred_option_bound().deanonymize();
+	assert_eq!(option, Some(bound()));
+
+	let reference: &Node<ThreadSafe> = inferred_ref_safe().deanonymize();
+	assert_safe(reference);
+
+	let array: [Node<ThreadSafe>; 2] = inferred_array_safe().deanonymize();
+	assert_safe(array);
+	let array: [Node<ThreadBound>; 2] = inferred_array_bound().deanonymize();
+	assert_eq!(array, [bound(), bound()]);
+
+	let slice: &[Node<ThreadSafe>] = inferred_slice_safe().deanonymize();
+	assert!(slice.is_empty());
+}
+
+#[test]
+fn align() {
+	let none: Option<Node<ThreadBound>> = None::<Node<ThreadSafe>>.align();
+	assert_eq!(none, None);
+	let some: Option<Node<ThreadBound>> = Some(safe()).align();
+	assert_eq!(some, Some(bound()));
+
+	let nodes = [safe(), safe()];
+	let slice: &[Node<ThreadBound>] = (&nodes[..]).align();
+	assert_eq!(slice, [bound(), bound()]);
+}
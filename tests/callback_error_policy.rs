@@ -0,0 +1,113 @@
+#![cfg(feature = "callbacks")]
+
+use lignin::{
+	callback_registry::{CallbackError, CallbackErrorKind, ErrorPolicy},
+	CallbackRegistration, DomRef,
+};
+use std::{
+	cell::RefCell,
+	panic::{catch_unwind, AssertUnwindSafe},
+};
+
+thread_local! {
+	static ROUTED: RefCell<Vec<CallbackError>> = RefCell::default();
+}
+
+fn route(error: CallbackError) {
+	ROUTED.with(|routed| routed.borrow_mut().push(error))
+}
+
+#[test]
+fn propagate() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(DomRef<&()>)>::new(receiver.as_ref(), |_, _| {
+			panic!("Propagated.")
+		});
+	let callback_ref = registration.to_ref();
+	assert!(catch_unwind(AssertUnwindSafe(|| callback_ref.call(DomRef::Added(&())))).is_err());
+}
+
+#[test]
+fn swallow() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(DomRef<&()>)>::new(receiver.as_ref(), |_, _| {
+			panic!("Swallowed.")
+		})
+		.with_error_policy(ErrorPolicy::LogAndSwallow);
+	registration.to_ref().call(DomRef::Removing(&()));
+}
+
+#[test]
+fn route_panic() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(DomRef<&()>)>::new(receiver.as_ref(), |_, _| {
+			panic!("Routed.")
+		})
+		.with_error_policy(ErrorPolicy::Route(route));
+	let callback_ref = registration.to_ref();
+	callback_ref.call(DomRef::Added(&()));
+
+	let error = ROUTED.with(|routed| routed.borrow_mut().pop()).unwrap();
+	assert!(error.is_from(callback_ref));
+	assert!(error.signature().contains("DomRef"));
+	match error.into_kind() {
+		CallbackErrorKind::Panicked(panic) => {
+			assert_eq!(panic.downcast_ref::<&str>(), Some(&"Routed."))
+		}
+		CallbackErrorKind::Returned(_) => unreachable!(),
+	}
+}
+
+#[test]
+fn route_returned() {
+	let receiver = Box::pin(());
+	let registration = CallbackRegistration::<_, fn(DomRef<&()>)>::new_fallible(
+		receiver.as_ref(),
+		|_, dom_ref| match dom_ref {
+			DomRef::Added(_) => Ok(()),
+			DomRef::Removing(_) => Err("Returned."),
+		},
+	)
+	.with_error_policy(ErrorPolicy::Route(route));
+	let callback_ref = registration.to_ref();
+
+	callback_ref.call(DomRef::Added(&()));
+	assert!(ROUTED.with(|routed| routed.borrow().is_empty()));
+
+	callback_ref.call(DomRef::Removing(&()));
+	let error = ROUTED.with(|routed| routed.borrow_mut().pop()).unwrap();
+	assert!(error.is_from(callback_ref));
+	assert!(
+		matches!(error.kind(), CallbackErrorKind::Returned(returned) if format!("{:?}", returned) == r#""Returned.""#)
+	);
+}
+
+#[test]
+fn propagate_returned() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(DomRef<&()>)>::new_fallible(receiver.as_ref(), |_, _| {
+			Err("Propagated.")
+		});
+	let callback_ref = registration.to_ref();
+	assert!(catch_unwind(AssertUnwindSafe(|| callback_ref.call(DomRef::Added(&())))).is_err());
+}
+
+#[test]
+fn route_missing_spawn_local() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(u32)>::new_async(receiver.as_ref(), |_, _| async {
+			unreachable!()
+		})
+		.with_error_policy(ErrorPolicy::Route(route));
+	let callback_ref = registration.to_ref();
+	callback_ref.call(1);
+
+	let error = ROUTED.with(|routed| routed.borrow_mut().pop()).unwrap();
+	assert!(error.is_from(callback_ref));
+	assert!(matches!(error.kind(), CallbackErrorKind::Returned(_)));
+}
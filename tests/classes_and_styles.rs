@@ -0,0 +1,120 @@
+mod common;
+
+use common::hash;
+use lignin::{html, Element, Node, StyleDeclaration, ThreadSafe};
+
+const RED: StyleDeclaration = StyleDeclaration {
+	property: "color",
+	value: "red",
+	important: false,
+};
+const WIDE: StyleDeclaration = StyleDeclaration {
+	property: "width",
+	value: "100%",
+	important: true,
+};
+
+#[test]
+fn order_independent() {
+	let a = Element {
+		classes: &["a", "b", "c"],
+		styles: &[RED, WIDE],
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+	let b = Element {
+		classes: &["c", "a", "b"],
+		styles: &[WIDE, RED],
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+	assert_eq!(a, b);
+	assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+	assert_eq!(hash(a), hash(b));
+}
+
+#[test]
+fn multiset() {
+	assert_ne!(
+		Element {
+			classes: &["a", "a", "b"],
+			..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+		},
+		Element {
+			classes: &["a", "b", "b"],
+			..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+		}
+	);
+	assert_ne!(
+		Element {
+			classes: &["a"],
+			..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+		},
+		Element {
+			classes: &["a", "a"],
+			..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+		}
+	);
+}
+
+#[test]
+fn ordering_matches_sorted() {
+	let lists: &[&[&str]] = &[
+		&[],
+		&["a"],
+		&["b"],
+		&["a", "a"],
+		&["a", "b"],
+		&["b", "a", "a"],
+		&["a", "c"],
+		&["c", "b", "a"],
+		&["b", "b"],
+	];
+	for a in lists {
+		for b in lists {
+			let (mut sorted_a, mut sorted_b) = (a.to_vec(), b.to_vec());
+			sorted_a.sort_unstable();
+			sorted_b.sort_unstable();
+			assert_eq!(
+				Element {
+					classes: a,
+					..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+				}
+				.cmp(&Element {
+					classes: b,
+					..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+				}),
+				sorted_a.cmp(&sorted_b),
+				"{:?} <=> {:?}",
+				a,
+				b
+			);
+		}
+	}
+}
+
+#[test]
+fn style_display() {
+	assert_eq!(RED.to_string(), "color: red");
+	assert_eq!(WIDE.to_string(), "width: 100% !important");
+}
+
+#[test]
+fn style_escaping() {
+	let styles = [StyleDeclaration {
+		property: "color",
+		value: "red&#59 background: url(evil)",
+		important: false,
+	}];
+	let element = Element {
+		styles: &styles,
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+
+	let mut html = String::new();
+	html::Serializer::new()
+		.write_node(&element.as_html(), &mut html)
+		.unwrap();
+	assert_eq!(
+		html,
+		"<DIV style=\"color: red&amp;#59 background: url(evil)\"></DIV>"
+	);
+}
@@ -0,0 +1,13 @@
+//! Helpers shared between integration tests.
+
+use std::{
+	collections::hash_map::DefaultHasher,
+	hash::{Hash, Hasher},
+};
+
+/// Hashes `value` with a [`DefaultHasher`].
+pub fn hash(value: impl Hash) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	value.hash(&mut hasher);
+	hasher.finish()
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "component")]
+
+use lignin::{
+	component::{Component, Invalidator, Scheduler},
+	frames::Frame,
+	CallbackRegistration, Node, ThreadSafe,
+};
+use std::{cell::Cell, cell::RefCell, pin::Pin, rc::Rc};
+
+struct Counter {
+	count: Cell<u32>,
+	renders: Cell<u32>,
+	/// Invalidates once more during each render while positive.
+	rerender: Cell<u32>,
+	invalidator: Invalidator,
+}
+
+impl Component<ThreadSafe> for Counter {
+	fn render<'a>(self: Pin<&'a Self>, frame: &'a Frame<'a, ThreadSafe>) -> Node<'a, ThreadSafe> {
+		self.renders.set(self.renders.get() + 1);
+		if self.rerender.get() > 0 {
+			self.rerender.set(self.rerender.get() - 1);
+			self.invalidator.invalidate();
+		}
+		frame.bump().alloc_str(&self.count.get().to_string()).into()
+	}
+}
+
+type Log = Rc<RefCell<Vec<(Option<String>, String)>>>;
+
+fn text(node: &Node<ThreadSafe>) -> String {
+	match node {
+		Node::Text { text, .. } => (*text).to_string(),
+		_ => unreachable!(),
+	}
+}
+
+fn scheduler(
+	log: &Log,
+) -> Scheduler<ThreadSafe, Counter, impl FnMut(Option<&Node<ThreadSafe>>, &Node<ThreadSafe>)> {
+	let log = log.clone();
+	Scheduler::new(
+		move |previous: Option<&Node<ThreadSafe>>, next: &Node<ThreadSafe>| {
+			log.borrow_mut().push((previous.map(text), text(next)))
+		},
+		|invalidator| Counter {
+			count: Cell::new(0),
+			renders: Cell::new(0),
+			rerender: Cell::new(0),
+			invalidator,
+		},
+	)
+}
+
+#[test]
+fn render_and_invalidate() {
+	let log = Log::default();
+	let scheduler = scheduler(&log);
+	assert_eq!(*log.borrow(), [(None, "0".to_string())]);
+
+	let counter = scheduler.root();
+	counter.count.set(1);
+	counter.invalidator.invalidate();
+	scheduler.invalidator().invalidate();
+	assert_eq!(
+		*log.borrow(),
+		[
+			(None, "0".to_string()),
+			(Some("0".to_string()), "1".to_string()),
+			(Some("1".to_string()), "1".to_string()),
+		]
+	);
+}
+
+#[test]
+fn invalidate_during_render() {
+	let log = Log::default();
+	let scheduler = scheduler(&log);
+	let counter = scheduler.root();
+
+	counter.rerender.set(2);
+	counter.invalidator.invalidate();
+	assert_eq!(counter.renders.get(), 4);
+	assert_eq!(log.borrow().len(), 4);
+}
+
+#[test]
+#[cfg_attr(not(feature = "callbacks"), ignore = "only with callbacks")]
+fn batched_in_handler() {
+	let log = Log::default();
+	let scheduler = scheduler(&log);
+	let counter = scheduler.root();
+
+	let registration = CallbackRegistration::<_, fn(u32)>::new(counter, |counter, times| {
+		let counter = unsafe { &*counter };
+		for _ in 0..times {
+			counter.count.set(counter.count.get() + 1);
+			counter.invalidator.invalidate();
+		}
+		assert_eq!(counter.renders.get(), 1);
+	});
+	registration.to_ref_thread_bound().call(3);
+
+	assert_eq!(counter.renders.get(), 2);
+	assert_eq!(
+		log.borrow().last(),
+		Some(&(Some("0".to_string()), "3".to_string()))
+	);
+}
+
+#[test]
+fn after_drop() {
+	let log = Log::default();
+	let scheduler = scheduler(&log);
+	let invalidator = scheduler.invalidator();
+	drop(scheduler);
+
+	invalidator.invalidate();
+	assert_eq!(log.borrow().len(), 1);
+}
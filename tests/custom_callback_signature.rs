@@ -0,0 +1,43 @@
+use lignin::{callback_registry::callback_parameter, CallbackRegistration};
+use std::cell::RefCell;
+
+#[derive(Debug, PartialEq)]
+struct Change(String);
+callback_parameter!(Change);
+
+#[test]
+fn custom_parameter() {
+	let receiver = Box::pin(RefCell::new(Vec::<String>::new()));
+	let registration =
+		CallbackRegistration::<_, fn(Change)>::new(receiver.as_ref(), |receiver, change| {
+			unsafe { &*receiver }.borrow_mut().push(change.0)
+		});
+
+	let callback_ref = registration.to_ref_thread_bound();
+	callback_ref.call(Change("first".to_string()));
+	callback_ref.call(Change("second".to_string()));
+
+	drop(registration);
+	callback_ref.call(Change("dropped".to_string()));
+
+	if cfg!(feature = "callbacks") {
+		assert_eq!(*receiver.borrow(), ["first", "second"]);
+	} else {
+		assert!(receiver.borrow().is_empty());
+	}
+}
+
+#[test]
+fn primitive_parameter() {
+	let receiver = Box::pin(RefCell::new(0));
+	let registration =
+		CallbackRegistration::<_, fn(u32)>::new(receiver.as_ref(), |receiver, value| {
+			*unsafe { &*receiver }.borrow_mut() += value
+		});
+	registration.to_ref_thread_bound().call(2);
+	registration.to_ref_thread_bound().call(3);
+	assert_eq!(
+		*receiver.borrow(),
+		if cfg!(feature = "callbacks") { 5 } else { 0 }
+	);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use bumpalo::Bump;
+use common::hash;
+use lignin::{
+	Element, ElementCreationOptions, Node, Portal, ReorderableFragment, ShadowRoot,
+	ShadowRootOptions, ThreadSafe,
+};
+use std::cmp::Ordering;
+
+const DEPTH: usize = 100_000;
+
+/// Nests `leaf` [`DEPTH`] levels deep, cycling through the recursive VDOM types.
+///
+/// [`Node::Memoized`] is left out since its `content` doesn't factor into comparisons.
+fn deep<'a>(bump: &'a Bump, leaf: &'a str) -> Node<'a, ThreadSafe> {
+	let mut node = leaf.into();
+	for i in 0..DEPTH {
+		node = match i % 5 {
+			0 => Node::HtmlElement {
+				element: bump.alloc(Element::new("DIV", node)),
+				dom_binding: None,
+			},
+			1 => Node::Multi(bump.alloc(["sibling".into(), node])),
+			2 => Node::Keyed(bump.alloc([ReorderableFragment {
+				dom_key: 1,
+				content: node,
+			}])),
+			3 => Node::Portal(bump.alloc(Portal {
+				target: "body",
+				content: node,
+				dom_binding: None,
+			})),
+			_ => Node::SvgElement {
+				element: bump.alloc(Element {
+					name: "g",
+					creation_options: ElementCreationOptions::new(),
+					attributes: &[],
+					properties: &[],
+					classes: &[],
+					styles: &[],
+					content: Node::Multi(&[]),
+					shadow_root: Some(bump.alloc(ShadowRoot {
+						options: ShadowRootOptions::new(),
+						content: node,
+						dom_binding: None,
+					})),
+					event_bindings: &[],
+				}),
+				dom_binding: None,
+			},
+		}
+	}
+	node
+}
+
+#[test]
+fn eq_and_ord() {
+	let bump = Bump::new();
+	let a = deep(&bump, "a");
+	let a_again = deep(&bump, "a");
+	let b = deep(&bump, "b");
+
+	assert_eq!(a, a_again);
+	assert_ne!(a, b);
+	assert_eq!(a.cmp(&a_again), Ordering::Equal);
+	assert_eq!(a.cmp(&b), Ordering::Less);
+	assert_eq!(b.partial_cmp(&a), Some(Ordering::Greater));
+}
+
+#[test]
+fn hash_deep() {
+	let bump = Bump::new();
+	assert_eq!(hash(&deep(&bump, "a")), hash(&deep(&bump, "a")));
+	assert_ne!(hash(&deep(&bump, "a")), hash(&deep(&bump, "b")));
+}
+
+#[test]
+fn debug() {
+	let bump = Bump::new();
+	let debug = format!("{:?}", deep(&bump, "a"));
+	assert!(debug.starts_with("Node::SvgElement { element: Element { name: \"g\", "));
+	assert!(debug.contains("Node::Text { text: \"a\", dom_binding: None }"));
+	assert!(debug.ends_with(" }) }, dom_binding: None }"));
+}
+
+#[test]
+fn debug_matches_shallow() {
+	let bump = Bump::new();
+	let text: Node<ThreadSafe> = "a".into();
+	let nodes = [text, Node::Keyed(&[])];
+	let node = Node::Memoized {
+		state_key: 1,
+		content: bump.alloc(Node::Multi(&nodes)),
+	};
+	assert_eq!(
+		format!("{:?}", node),
+		"Node::Memoized { state_key: 1, content: Node::Multi([Node::Text { text: \"a\", dom_binding: None }, Node::Keyed([])]) }"
+	);
+	assert_eq!(
+		format!("{:#?}", node),
+		r#"Node::Memoized {
+    state_key: 1,
+    content: Node::Multi(
+        [
+            Node::Text {
+                text: "a",
+                dom_binding: None,
+            },
+            Node::Keyed(
+                [],
+            ),
+        ],
+    ),
+}"#
+	);
+}
+
+#[test]
+fn dom_len_and_empty() {
+	let bump = Bump::new();
+	let mut multi: Node<ThreadSafe> = Node::Multi(&[]);
+	let mut memoized = multi;
+	for _ in 0..DEPTH {
+		multi = Node::Multi(bump.alloc([multi, "text".into()]));
+		memoized = Node::Memoized {
+			state_key: 0,
+			content: bump.alloc(Node::Multi(bump.alloc([memoized, Node::Keyed(&[])]))),
+		};
+	}
+	assert_eq!(multi.dom_len(), DEPTH);
+	assert!(!multi.dom_empty());
+	assert_eq!(memoized.dom_len(), 0);
+	assert!(memoized.dom_empty());
+
+	let bump = Bump::new();
+	assert_eq!(deep(&bump, "a").dom_len(), 1);
+}
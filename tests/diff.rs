@@ -0,0 +1,299 @@
+#![cfg(feature = "diff")]
+
+use lignin::{
+	diff::{Diff, Patch},
+	CallbackRegistration, DomRef, Element, Node, ReorderableFragment, ThreadBound,
+};
+use std::{cell::Cell, task::Poll};
+
+type Patches<'a> = Vec<(String, Patch<'a, ThreadBound>)>;
+
+fn diff<'a>(previous: &'a Node<'a, ThreadBound>, next: &'a Node<'a, ThreadBound>) -> Patches<'a> {
+	let mut diff = Diff::new(previous, next);
+	let mut budget = usize::MAX;
+	assert_eq!(diff.resume(&mut budget), Poll::Ready(()));
+	let mut patches = vec![];
+	diff.commit(|path, patch| patches.push((path.to_string(), patch)))
+		.unwrap();
+	patches
+}
+
+#[test]
+fn unchanged() {
+	let previous_element = Element {
+		classes: &["x"],
+		..Element::<ThreadBound>::new("DIV", "a".into())
+	};
+	let next_element = Element {
+		classes: &["x"],
+		..Element::<ThreadBound>::new("DIV", "a".into())
+	};
+	let previous = Node::HtmlElement {
+		element: &previous_element,
+		dom_binding: None,
+	};
+	let next = Node::HtmlElement {
+		element: &next_element,
+		dom_binding: None,
+	};
+	assert_eq!(diff(&previous, &next), []);
+}
+
+#[test]
+fn element_and_text() {
+	let previous_element = Element {
+		classes: &["x"],
+		..Element::<ThreadBound>::new("DIV", "a".into())
+	};
+	let next_element = Element {
+		classes: &["y"],
+		..Element::<ThreadBound>::new("DIV", "b".into())
+	};
+	let previous = Node::HtmlElement {
+		element: &previous_element,
+		dom_binding: None,
+	};
+	let next = Node::HtmlElement {
+		element: &next_element,
+		dom_binding: None,
+	};
+	assert_eq!(
+		diff(&previous, &next),
+		[
+			(
+				"/".to_string(),
+				Patch::Update {
+					previous: &previous,
+					next: &next
+				}
+			),
+			(
+				"/content".to_string(),
+				Patch::Update {
+					previous: &previous_element.content,
+					next: &next_element.content
+				}
+			),
+		]
+	);
+
+	let span = Element {
+		name: "SPAN",
+		..next_element
+	};
+	let next = Node::HtmlElement {
+		element: &span,
+		dom_binding: None,
+	};
+	assert_eq!(
+		diff(&previous, &next),
+		[(
+			"/".to_string(),
+			Patch::Replace {
+				previous: &previous,
+				next: &next
+			}
+		)]
+	);
+}
+
+#[test]
+fn multi() {
+	let previous_nodes = ["a".into(), "b".into(), "c".into()];
+	let next_nodes = ["a".into(), "x".into()];
+	let previous = Node::Multi(&previous_nodes);
+	let next = Node::Multi(&next_nodes);
+	assert_eq!(
+		diff(&previous, &next),
+		[
+			(
+				"/2".to_string(),
+				Patch::Remove {
+					previous: &previous_nodes[2]
+				}
+			),
+			(
+				"/1".to_string(),
+				Patch::Update {
+					previous: &previous_nodes[1],
+					next: &next_nodes[1]
+				}
+			),
+		]
+	);
+	assert_eq!(
+		diff(&next, &previous),
+		[
+			(
+				"/1".to_string(),
+				Patch::Update {
+					previous: &next_nodes[1],
+					next: &previous_nodes[1]
+				}
+			),
+			(
+				"/2".to_string(),
+				Patch::Insert {
+					next: &previous_nodes[2]
+				}
+			),
+		]
+	);
+}
+
+#[test]
+fn keyed() {
+	let previous_fragments = [
+		ReorderableFragment {
+			dom_key: 1,
+			content: "a".into(),
+		},
+		ReorderableFragment {
+			dom_key: 2,
+			content: "b".into(),
+		},
+		ReorderableFragment {
+			dom_key: 3,
+			content: "c".into(),
+		},
+	];
+	let next_fragments = [
+		ReorderableFragment {
+			dom_key: 3,
+			content: "C".into(),
+		},
+		ReorderableFragment {
+			dom_key: 4,
+			content: "d".into(),
+		},
+		ReorderableFragment {
+			dom_key: 1,
+			content: "a".into(),
+		},
+	];
+	let previous = Node::Keyed(&previous_fragments);
+	let next = Node::Keyed(&next_fragments);
+	assert_eq!(
+		diff(&previous, &next),
+		[
+			(
+				"/#2".to_string(),
+				Patch::Remove {
+					previous: &previous_fragments[1].content
+				}
+			),
+			(
+				"/#3".to_string(),
+				Patch::Update {
+					previous: &previous_fragments[2].content,
+					next: &next_fragments[0].content
+				}
+			),
+			(
+				"/#4".to_string(),
+				Patch::Insert {
+					next: &next_fragments[1].content
+				}
+			),
+			(
+				"/".to_string(),
+				Patch::Reorder {
+					previous: &previous_fragments,
+					next: &next_fragments
+				}
+			),
+		]
+	);
+}
+
+#[test]
+fn bindings_around_subtree() {
+	let receiver = Box::pin(Cell::new(0));
+	let registration = CallbackRegistration::<_, fn(DomRef<&'_ lignin::web::HtmlElement>)>::new(
+		receiver.as_ref(),
+		|_, _| (),
+	);
+	let other = CallbackRegistration::<_, fn(DomRef<&'_ lignin::web::HtmlElement>)>::new(
+		receiver.as_ref(),
+		|_, _| (),
+	);
+
+	let previous_element = Element::<ThreadBound>::new("DIV", "a".into());
+	let next_element = Element::<ThreadBound>::new("DIV", "b".into());
+	let previous = Node::HtmlElement {
+		element: &previous_element,
+		dom_binding: Some(registration.to_ref_thread_bound()),
+	};
+	let next = Node::HtmlElement {
+		element: &next_element,
+		dom_binding: Some(other.to_ref_thread_bound()),
+	};
+
+	let update = (
+		"/content".to_string(),
+		Patch::Update {
+			previous: &previous_element.content,
+			next: &next_element.content,
+		},
+	);
+	if cfg!(feature = "callbacks") {
+		assert_eq!(
+			diff(&previous, &next),
+			[
+				(
+					"/".to_string(),
+					Patch::Unbind {
+						previous: &previous
+					}
+				),
+				update.clone(),
+				("/".to_string(), Patch::Bind { next: &next }),
+			]
+		);
+	} else {
+		// Without registry, all callback references compare equal.
+		assert_eq!(diff(&previous, &next), [update]);
+	}
+}
+
+#[test]
+fn time_sliced() {
+	let previous_nodes: Vec<Node<ThreadBound>> = (0..10_000)
+		.map(|i| Node::Text {
+			text: if i % 2 == 0 { "even" } else { "odd" },
+			dom_binding: None,
+		})
+		.collect();
+	let next_nodes: Vec<Node<ThreadBound>> = (0..10_000).map(|_| "even".into()).collect();
+	let previous = Node::Multi(&previous_nodes);
+	let next = Node::Multi(&next_nodes);
+
+	let mut diff = Diff::new(&previous, &next);
+	let mut slices = 0;
+	while diff.resume(&mut 1000).is_pending() {
+		slices += 1;
+		assert!(!diff.is_complete());
+		diff = match diff.commit(|_, _| unreachable!()) {
+			Err(diff) => diff,
+			Ok(()) => unreachable!(),
+		};
+	}
+	assert_eq!(slices, 10);
+
+	let mut count = 0;
+	let mut deadline = 3;
+	assert_eq!(
+		Diff::new(&previous, &next).resume(&mut || {
+			deadline -= 1;
+			deadline > 0
+		}),
+		Poll::Pending
+	);
+	diff.commit(|path, patch| {
+		assert_eq!(path.to_string(), format!("/{}", count * 2 + 1));
+		assert!(matches!(patch, Patch::Update { .. }));
+		count += 1;
+	})
+	.unwrap();
+	assert_eq!(count, 5_000);
+}
@@ -0,0 +1,65 @@
+use lignin::{html, Attribute, Document, Element, Node, ThreadSafe};
+
+#[test]
+fn complete_document() {
+	let title = Element::<ThreadSafe>::new("TITLE", "Fish & Chips".into());
+	let main = Element::<ThreadSafe>::new("MAIN", "Menu".into());
+	let head = [title.as_html(), "\n".into()];
+	let document = Document {
+		doctype: "html",
+		html_attributes: &[Attribute::new("lang", lignin::AttributeValue::String("en"))],
+		head: Node::Multi(&head),
+		body: main.as_html(),
+	};
+
+	let mut page = String::new();
+	html::Serializer::new()
+		.write_document(&document, &mut page)
+		.unwrap();
+	assert_eq!(
+		page,
+		"<!DOCTYPE html><html lang=\"en\">\
+			<head><TITLE>Fish &amp; Chips</TITLE>\n</head>\
+			<body><MAIN>Menu</MAIN></body>\
+		</html>"
+	);
+}
+
+#[test]
+fn invalid_head_content() {
+	let div = Element::<ThreadSafe>::new("DIV", Node::Multi(&[]));
+	let document = Document::<ThreadSafe> {
+		doctype: "html",
+		html_attributes: &[],
+		head: div.as_html(),
+		body: Node::Multi(&[]),
+	};
+	let mut page = String::new();
+	assert_eq!(
+		html::Serializer::new().write_document(&document, &mut page),
+		Err(html::Error::InvalidHeadContent("DIV"))
+	);
+
+	let document = Document {
+		head: "text".into(),
+		..document
+	};
+	assert_eq!(
+		html::Serializer::new().write_document(&document, &mut String::new()),
+		Err(html::Error::InvalidHeadContent("text"))
+	);
+}
+
+#[test]
+fn invalid_doctype() {
+	let document = Document::<ThreadSafe> {
+		doctype: "html><script>",
+		html_attributes: &[],
+		head: Node::Multi(&[]),
+		body: Node::Multi(&[]),
+	};
+	assert_eq!(
+		html::Serializer::new().write_document(&document, &mut String::new()),
+		Err(html::Error::InvalidName("html><script>"))
+	);
+}
@@ -0,0 +1,49 @@
+use lignin::{CallbackRegistration, EventBindingOptions, EventOutcome};
+
+#[test]
+fn flags() {
+	let outcome = EventOutcome::new()
+		.with_prevent_default(true)
+		.with_stop_propagation(true);
+	assert!(outcome.prevent_default());
+	assert!(outcome.stop_propagation());
+	assert!(!outcome.stop_immediate_propagation());
+	assert_eq!(
+		outcome
+			.with_prevent_default(false)
+			.with_stop_propagation(false),
+		EventOutcome::default()
+	);
+}
+
+#[test]
+fn checked_against_passive() {
+	let outcome = EventOutcome::new()
+		.with_prevent_default(true)
+		.with_stop_immediate_propagation(true);
+
+	let passive = outcome.checked_against(EventBindingOptions::new());
+	assert!(!passive.prevent_default());
+	assert!(passive.stop_immediate_propagation());
+
+	let active = outcome.checked_against(EventBindingOptions::new().with_passive(false));
+	assert_eq!(active, outcome);
+}
+
+#[test]
+fn call_returns_outcome() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(u8) -> EventOutcome>::new(receiver.as_ref(), |_, value| {
+			EventOutcome::new().with_stop_propagation(value > 1)
+		});
+	let callback_ref = registration.to_ref();
+
+	assert_eq!(
+		callback_ref.call(2).stop_propagation(),
+		cfg!(feature = "callbacks"),
+	);
+
+	drop(registration);
+	assert_eq!(callback_ref.call(2), EventOutcome::default());
+}
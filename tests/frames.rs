@@ -0,0 +1,79 @@
+#![cfg(feature = "bumpalo")]
+
+use lignin::{arena::Arena, frames::Frames, memo::memo, Element, Node, ThreadSafe};
+use std::cell::Cell;
+
+#[test]
+fn previous_and_current() {
+	let mut frames = Frames::<ThreadSafe>::new();
+	assert_eq!(frames.current(), None);
+
+	frames.render(|frame| frame.alloc_str(&1.to_string()).into());
+	assert_eq!(frames.previous(), None);
+	assert_eq!(frames.current(), Some(&"1".into()));
+
+	for count in 2..5 {
+		frames.render(|frame| frame.alloc_str(&count.to_string()).into());
+		assert_eq!(
+			frames.previous(),
+			Some(&Node::from((count - 1).to_string().as_str()))
+		);
+		assert_eq!(
+			frames.current(),
+			Some(&Node::from(count.to_string().as_str()))
+		);
+	}
+}
+
+#[test]
+fn memoized_carry_over() {
+	let renders = Cell::new(0);
+	let mut frames = Frames::<ThreadSafe>::new();
+	let render = |frames: &mut Frames<ThreadSafe>, count: u32| {
+		frames.render(|frame| {
+			memo(frame, &count, |frame| {
+				renders.set(renders.get() + 1);
+				let nested = memo(frame, &(), |_| "nested".into());
+				Node::Multi(
+					frame.alloc_slice_copy(&[frame.alloc_str(&count.to_string()).into(), nested]),
+				)
+			})
+		})
+	};
+
+	render(&mut frames, 1);
+	render(&mut frames, 1);
+	render(&mut frames, 1);
+	assert_eq!(renders.get(), 1);
+	assert_eq!(frames.previous(), frames.current());
+
+	render(&mut frames, 2);
+	assert_eq!(renders.get(), 2);
+	assert_ne!(frames.previous(), frames.current());
+}
+
+#[test]
+fn copy_into() {
+	let bump = bumpalo::Bump::new();
+	let classes = ["a", "b"];
+	let element = Element {
+		classes: &classes,
+		..Element::new("DIV", "text".into())
+	};
+	let node: Node<ThreadSafe> = Node::HtmlElement {
+		element: &element,
+		dom_binding: None,
+	};
+
+	let copy = node.copy_into(&bump);
+	assert_eq!(copy, node);
+	match copy {
+		Node::HtmlElement {
+			element: copied, ..
+		} => {
+			assert!(!std::ptr::eq(copied, &element));
+			assert!(!std::ptr::eq(copied.classes, &classes[..]));
+		}
+		_ => unreachable!(),
+	}
+}
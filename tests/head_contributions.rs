@@ -0,0 +1,168 @@
+use lignin::{
+	head::{self, HeadKey},
+	html, Attribute, AttributeValue, Document, Element, Node, ThreadSafe,
+};
+
+fn contribution<'a>(element: &'a Element<'a, ThreadSafe>) -> Node<'a, ThreadSafe> {
+	Node::HeadContribution {
+		element,
+		dom_binding: None,
+	}
+}
+
+fn collect<'a>(roots: &[&Node<'a, ThreadSafe>]) -> Vec<&'a Element<'a, ThreadSafe>> {
+	let mut elements = vec![];
+	head::for_each_contribution::<_, ()>(roots, |element, _| {
+		elements.push(element);
+		Ok(())
+	})
+	.unwrap();
+	elements
+}
+
+#[test]
+fn keys() {
+	let description = [Attribute::new(
+		"NAME",
+		AttributeValue::String("description"),
+	)];
+	let charset = [Attribute::new("charset", AttributeValue::String("utf-8"))];
+	let og_title = [Attribute::new(
+		"property",
+		AttributeValue::String("og:title"),
+	)];
+	let preload = [Attribute::new("rel", AttributeValue::String("preload"))];
+
+	let empty = Node::Multi(&[]);
+	assert_eq!(
+		HeadKey::of(&Element::<ThreadSafe>::new("TITLE", empty)),
+		Some(HeadKey::Title)
+	);
+	assert_eq!(
+		HeadKey::of(&Element::<ThreadSafe>::new("base", empty)),
+		Some(HeadKey::Base)
+	);
+	assert_eq!(
+		HeadKey::of(&Element {
+			attributes: &charset,
+			..Element::<ThreadSafe>::new("META", empty)
+		}),
+		Some(HeadKey::Charset)
+	);
+	assert_eq!(
+		HeadKey::of(&Element {
+			attributes: &description,
+			..Element::<ThreadSafe>::new("META", empty)
+		}),
+		Some(HeadKey::Meta {
+			attribute: "name",
+			value: "description"
+		})
+	);
+	assert_eq!(
+		HeadKey::of(&Element {
+			attributes: &og_title,
+			..Element::<ThreadSafe>::new("META", empty)
+		}),
+		Some(HeadKey::Meta {
+			attribute: "property",
+			value: "og:title"
+		})
+	);
+	assert_eq!(
+		HeadKey::of(&Element::<ThreadSafe>::new("META", empty)),
+		None
+	);
+	assert_eq!(
+		HeadKey::of(&Element {
+			attributes: &preload,
+			..Element::<ThreadSafe>::new("LINK", empty)
+		}),
+		None
+	);
+}
+
+#[test]
+fn last_wins() {
+	let outer_title = Element::<ThreadSafe>::new("TITLE", "Outer".into());
+	let inner_title = Element::<ThreadSafe>::new("TITLE", "Inner".into());
+	let preload = [Attribute::new("rel", AttributeValue::String("preload"))];
+	let link = Element {
+		attributes: &preload,
+		..Element::<ThreadSafe>::new("LINK", Node::Multi(&[]))
+	};
+
+	let inner = [contribution(&inner_title), contribution(&link)];
+	let section = Element::<ThreadSafe>::new("SECTION", Node::Multi(&inner));
+	let body = [
+		contribution(&outer_title),
+		contribution(&link),
+		section.as_html(),
+	];
+
+	let elements = collect(&[&Node::Multi(&body)]);
+	assert_eq!(elements.len(), 3);
+	assert!(std::ptr::eq(elements[0], &link));
+	assert!(std::ptr::eq(elements[1], &inner_title));
+	assert!(std::ptr::eq(elements[2], &link));
+}
+
+#[test]
+fn dom_len() {
+	let title = Element::<ThreadSafe>::new("TITLE", "Title".into());
+	let nodes = [contribution(&title), "text".into()];
+	assert_eq!(Node::Multi(&nodes).dom_len(), 1);
+	assert!(contribution(&title).dom_empty());
+}
+
+#[test]
+fn document() {
+	let default_title = Element::<ThreadSafe>::new("TITLE", "Default".into());
+	let description = [
+		Attribute::new("name", AttributeValue::String("description")),
+		Attribute::new("content", AttributeValue::String("A page")),
+	];
+	let meta = Element {
+		attributes: &description,
+		..Element::<ThreadSafe>::new("META", Node::Multi(&[]))
+	};
+	let page_title = Element::<ThreadSafe>::new("TITLE", "Page".into());
+
+	let head = [default_title.as_html()];
+	let body_content = [
+		contribution(&page_title),
+		contribution(&meta),
+		"Text".into(),
+	];
+	let main = Element::<ThreadSafe>::new("MAIN", Node::Multi(&body_content));
+	let document = Document {
+		doctype: "html",
+		html_attributes: &[],
+		head: Node::Multi(&head),
+		body: main.as_html(),
+	};
+
+	let mut page = String::new();
+	html::Serializer::new()
+		.write_document(&document, &mut page)
+		.unwrap();
+	assert_eq!(
+		page,
+		"<!DOCTYPE html><html>\
+			<head><TITLE>Default</TITLE><TITLE>Page</TITLE><META name=\"description\" content=\"A page\"></head>\
+			<body><MAIN>Text</MAIN></body>\
+		</html>"
+	);
+}
+
+#[test]
+fn invalid_contribution() {
+	let div = Element::<ThreadSafe>::new("DIV", Node::Multi(&[]));
+	let body = contribution(&div);
+
+	let mut html = String::new();
+	assert_eq!(
+		html::Serializer::new().write_head_contributions(&[&body], &mut html),
+		Err(html::Error::InvalidHeadContent("DIV"))
+	);
+}
@@ -1,4 +1,7 @@
-use lignin::{web::Event, CallbackRef, Element, EventBinding, Node, ThreadBound, ThreadSafe};
+use lignin::{
+	web::Event, CallbackRef, Element, EventBinding, Node, Portal, ReorderableFragment, ShadowRoot,
+	ThreadBound, ThreadSafe,
+};
 use static_assertions::{assert_eq_align, assert_eq_size};
 
 assert_eq_align!(Element<'static, ThreadSafe>, Element<'static, ThreadBound>);
@@ -24,3 +27,42 @@ assert_eq_size!(
 	CallbackRef<ThreadSafe, fn(Event)>,
 	CallbackRef<ThreadBound, fn(Event)>
 );
+
+assert_eq_align!(Portal<'static, ThreadSafe>, Portal<'static, ThreadBound>);
+assert_eq_size!(Portal<'static, ThreadSafe>, Portal<'static, ThreadBound>);
+
+assert_eq_align!(
+	ReorderableFragment<'static, ThreadSafe>,
+	ReorderableFragment<'static, ThreadBound>
+);
+assert_eq_size!(
+	ReorderableFragment<'static, ThreadSafe>,
+	ReorderableFragment<'static, ThreadBound>
+);
+
+assert_eq_align!(
+	ShadowRoot<'static, ThreadSafe>,
+	ShadowRoot<'static, ThreadBound>
+);
+assert_eq_size!(
+	ShadowRoot<'static, ThreadSafe>,
+	ShadowRoot<'static, ThreadBound>
+);
+
+assert_eq_align!(
+	Option<Node<'static, ThreadSafe>>,
+	Option<Node<'static, ThreadBound>>
+);
+assert_eq_size!(
+	Option<Node<'static, ThreadSafe>>,
+	Option<Node<'static, ThreadBound>>
+);
+
+assert_eq_align!(
+	Option<CallbackRef<ThreadSafe, fn(Event)>>,
+	Option<CallbackRef<ThreadBound, fn(Event)>>
+);
+assert_eq_size!(
+	Option<CallbackRef<ThreadSafe, fn(Event)>>,
+	Option<CallbackRef<ThreadBound, fn(Event)>>
+);
@@ -0,0 +1,97 @@
+use bumpalo::Bump;
+use lignin::{
+	arena::Arena,
+	memo::{memo, MemoContext},
+	Node, ThreadSafe,
+};
+use std::cell::{Cell, RefCell};
+
+/// Keeps memoized content for the whole test, like a single long frame.
+#[derive(Default)]
+struct Context<'a> {
+	bump: Bump,
+	memos: RefCell<Vec<(u64, &'a Node<'a, ThreadSafe>)>>,
+}
+impl<'a> Arena for Context<'a> {
+	fn alloc<T: Copy>(&self, value: T) -> &T {
+		self.bump.alloc(value)
+	}
+	fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T] {
+		self.bump.alloc_slice_copy(slice)
+	}
+	fn alloc_str(&self, string: &str) -> &str {
+		self.bump.alloc_str(string)
+	}
+	fn alloc_slice_fill_with<T: Copy>(&self, len: usize, f: impl FnMut(usize) -> T) -> &[T] {
+		self.bump.alloc_slice_fill_with(len, f)
+	}
+}
+impl<'a> MemoContext<'a, ThreadSafe> for Context<'a> {
+	fn recall(&'a self, state_key: u64) -> Option<&'a Node<'a, ThreadSafe>> {
+		self.memos
+			.borrow()
+			.iter()
+			.find(|(key, _)| *key == state_key)
+			.map(|(_, content)| *content)
+	}
+	fn record(&'a self, state_key: u64, content: &'a Node<'a, ThreadSafe>) {
+		self.memos.borrow_mut().push((state_key, content));
+	}
+}
+
+fn counter<'a>(
+	context: &'a Context<'a>,
+	renders: &Cell<usize>,
+	count: u32,
+) -> Node<'a, ThreadSafe> {
+	memo(context, &count, |context| {
+		renders.set(renders.get() + 1);
+		let text = context.alloc_str(&count.to_string());
+		Node::Text {
+			text,
+			dom_binding: None,
+		}
+	})
+}
+
+#[test]
+fn reuse() {
+	let context = Context::default();
+	let renders = Cell::new(0);
+
+	let first = counter(&context, &renders, 1);
+	let second = counter(&context, &renders, 1);
+	let third = counter(&context, &renders, 2);
+	assert_eq!(renders.get(), 2);
+
+	match (first, second, third) {
+		(
+			Node::Memoized {
+				state_key: key_1,
+				content: content_1,
+			},
+			Node::Memoized {
+				state_key: key_2,
+				content: content_2,
+			},
+			Node::Memoized {
+				state_key: key_3,
+				content: content_3,
+			},
+		) => {
+			assert_eq!(key_1, key_2);
+			assert!(std::ptr::eq(content_1, content_2));
+			assert_ne!(key_1, key_3);
+			assert_eq!(content_3, &Node::<ThreadSafe>::from("2"));
+		}
+		_ => panic!("not memoized"),
+	}
+}
+
+#[test]
+fn distinct_call_sites() {
+	let context = Context::default();
+	let a = memo(&context, &(), |_| "a".into());
+	let b = memo(&context, &(), |_| "b".into());
+	assert_ne!(a, b);
+}
@@ -0,0 +1,99 @@
+use lignin::{
+	path::{NodePath, PathStep},
+	Element, Node, ReorderableFragment, ThreadSafe,
+};
+
+#[test]
+fn display() {
+	assert_eq!(NodePath::ROOT.to_string(), "/");
+	assert_eq!(
+		NodePath::new(&[PathStep::Content, PathStep::Index(2), PathStep::Key(17)]).to_string(),
+		"/content/2/#17"
+	);
+}
+
+#[test]
+fn parent() {
+	let steps = [PathStep::Content, PathStep::Index(2)];
+	let path = NodePath::new(&steps);
+	assert_eq!(path.parent(), Some(NodePath::new(&steps[..1])));
+	assert!(path.parent().unwrap().parent().unwrap().is_root());
+	assert_eq!(NodePath::ROOT.parent(), None);
+}
+
+#[test]
+fn element_content() {
+	let inner = ["a".into(), "b".into()];
+	let memoized = Node::Multi(&inner);
+	let content = [
+		"x".into(),
+		Node::Memoized {
+			state_key: 0,
+			content: &memoized,
+		},
+	];
+	let div = Element::<ThreadSafe>::new("DIV", Node::Multi(&content));
+	let nodes = ["before".into(), div.as_html(), Node::Multi(&[])];
+	let root = Node::Multi(&nodes);
+
+	let path = NodePath::new(&[
+		PathStep::Index(1),
+		PathStep::Content,
+		PathStep::Index(1),
+		PathStep::Content,
+		PathStep::Index(1),
+	]);
+	assert_eq!(root.get(path), Some(&inner[1]));
+	// Relative to the `DIV`.
+	assert_eq!(root.dom_range(path), Some(2..3));
+	assert_eq!(root.dom_range(path.parent().unwrap()), Some(1..3));
+
+	assert_eq!(root.get(NodePath::new(&[PathStep::Index(3)])), None);
+	assert_eq!(root.get(NodePath::new(&[PathStep::Key(1)])), None);
+	assert_eq!(
+		root.get(NodePath::new(&[PathStep::Index(0), PathStep::Content])),
+		None
+	);
+
+	let mut buffer = [PathStep::Content; 5];
+	assert_eq!(root.find_path(&inner[1], &mut buffer), Some(path));
+	assert_eq!(root.find_path(&inner[1], &mut [PathStep::Content; 4]), None);
+}
+
+#[test]
+fn stable_across_keyed_reorders() {
+	let before = [
+		ReorderableFragment {
+			dom_key: 1,
+			content: "a".into(),
+		},
+		ReorderableFragment {
+			dom_key: 2,
+			content: "b".into(),
+		},
+	];
+	let after = [
+		ReorderableFragment {
+			dom_key: 2,
+			content: "b".into(),
+		},
+		ReorderableFragment {
+			dom_key: 1,
+			content: "a".into(),
+		},
+	];
+	let before: Node<ThreadSafe> = Node::Keyed(&before);
+	let after: Node<ThreadSafe> = Node::Keyed(&after);
+
+	let mut buffer = [PathStep::Content; 1];
+	let path = before
+		.find_path(
+			before.get(NodePath::new(&[PathStep::Key(2)])).unwrap(),
+			&mut buffer,
+		)
+		.unwrap();
+	assert_eq!(path.steps(), &[PathStep::Key(2)]);
+	assert_eq!(before.get(path), after.get(path));
+	assert_eq!(before.dom_range(path), Some(1..2));
+	assert_eq!(after.dom_range(path), Some(0..1));
+}
@@ -0,0 +1,70 @@
+use lignin::{html, Element, Node, Portal, ThreadSafe};
+
+#[test]
+fn dom_len() {
+	let portal = Portal {
+		target: "overlay",
+		content: Node::Multi(&["a".into(), "b".into()]),
+		dom_binding: None,
+	};
+	let nodes = ["before".into(), Node::Portal(&portal), "after".into()];
+	let node: Node<ThreadSafe> = Node::Multi(&nodes);
+	assert_eq!(node.dom_len(), 2);
+	assert_eq!(Node::Portal(&portal).dom_len(), 0);
+	assert!(Node::Portal(&portal).dom_empty());
+	assert_eq!(portal.content.dom_len(), 2);
+}
+
+#[test]
+fn target_comparison() {
+	let overlay = Portal::<ThreadSafe> {
+		target: "overlay",
+		content: "toast".into(),
+		dom_binding: None,
+	};
+	let body = Portal {
+		target: "body",
+		..overlay
+	};
+	assert_ne!(overlay, body);
+	assert_eq!(overlay, Portal { ..overlay });
+}
+
+#[test]
+fn serialisation() {
+	let inner = Portal {
+		target: "overlay",
+		content: "inner".into(),
+		dom_binding: None,
+	};
+	let other = Portal {
+		target: "elsewhere",
+		content: "other".into(),
+		dom_binding: None,
+	};
+	let outer_content = ["outer ".into(), Node::Portal(&inner)];
+	let outer = Portal {
+		target: "overlay",
+		content: Node::Multi(&outer_content),
+		dom_binding: None,
+	};
+	let content = ["light".into(), Node::Portal(&outer), Node::Portal(&other)];
+	let element = Element::<ThreadSafe>::new("DIV", Node::Multi(&content));
+	let root = element.as_html();
+
+	let mut page = String::new();
+	html::write_node(&root, &mut page).unwrap();
+	assert_eq!(page, "<DIV>light</DIV>");
+
+	let mut overlay = String::new();
+	html::Serializer::new()
+		.write_portals(&root, "overlay", &mut overlay)
+		.unwrap();
+	assert_eq!(overlay, "outer inner");
+
+	let mut elsewhere = String::new();
+	html::Serializer::new()
+		.write_portals(&root, "elsewhere", &mut elsewhere)
+		.unwrap();
+	assert_eq!(elsewhere, "other");
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::hash;
+use lignin::{Element, Node, Property, PropertyValue, ThreadSafe};
+
+#[test]
+fn number_semantics() {
+	assert_eq!(
+		PropertyValue::Number(f64::NAN),
+		PropertyValue::Number(f64::NAN)
+	);
+	assert_ne!(PropertyValue::Number(0.0), PropertyValue::Number(-0.0));
+	assert_eq!(
+		hash(PropertyValue::Number(1.5)),
+		hash(PropertyValue::Number(1.5))
+	);
+
+	assert!(PropertyValue::Number(-1.0) < PropertyValue::Number(-0.0));
+	assert!(PropertyValue::Number(-0.0) < PropertyValue::Number(0.0));
+	assert!(PropertyValue::Number(0.0) < PropertyValue::Number(2.0));
+	assert!(PropertyValue::Number(f64::INFINITY) < PropertyValue::Number(f64::NAN));
+}
+
+#[test]
+fn variants_distinct() {
+	assert_ne!(PropertyValue::Integer(1), PropertyValue::Number(1.0));
+	assert_ne!(PropertyValue::Bool(true), PropertyValue::String("true"));
+	assert!(PropertyValue::Bool(true) < PropertyValue::Integer(0));
+}
+
+#[test]
+fn reflection() {
+	let reflected = |name| {
+		Property {
+			name,
+			value: PropertyValue::Bool(true),
+		}
+		.reflected_attribute()
+	};
+	assert_eq!(reflected("value"), Some("value"));
+	assert_eq!(reflected("checked"), Some("checked"));
+	assert_eq!(reflected("className"), Some("class"));
+	assert_eq!(reflected("indeterminate"), None);
+	assert_eq!(reflected("selectedIndex"), None);
+	assert_eq!(reflected("textContent"), None);
+	assert_eq!(reflected("selected"), Some("selected"));
+	assert_eq!(reflected("readOnly"), Some("readonly"));
+	assert_eq!(reflected("disabled"), Some("disabled"));
+	assert_eq!(reflected("dataset"), None);
+}
+
+#[test]
+fn element_comparison() {
+	let element = |properties| Element::<ThreadSafe> {
+		properties,
+		..Element::new("INPUT", Node::Multi(&[]))
+	};
+
+	let checked = [Property {
+		name: "checked",
+		value: PropertyValue::Bool(true),
+	}];
+	let unchecked = [Property {
+		name: "checked",
+		value: PropertyValue::Bool(false),
+	}];
+
+	assert_eq!(element(&checked), element(&checked));
+	assert_ne!(element(&checked), element(&unchecked));
+	assert!(element(&unchecked) < element(&checked));
+	assert_eq!(hash(element(&checked)), hash(element(&checked)));
+}
@@ -0,0 +1,85 @@
+use core::fmt::{self, Write};
+use lignin::{
+	html::{self, Serializer},
+	Element, Node, ThreadSafe, TrustedHtml,
+};
+
+const MARKDOWN: TrustedHtml<'static> = TrustedHtml::assume_trusted("<p>Hello <em>world</em>!</p>");
+
+fn strip_em(html: TrustedHtml, output: &mut dyn Write) -> fmt::Result {
+	if html.as_str().contains("<script") {
+		return Err(fmt::Error);
+	}
+	output.write_str(&html.as_str().replace("<em>", "").replace("</em>", ""))
+}
+
+#[test]
+fn dom_len() {
+	let raw: Node<ThreadSafe> = Node::RawHtml(MARKDOWN);
+	assert_eq!(raw.dom_len(), 0);
+	assert!(raw.dom_empty());
+	assert_eq!(
+		Element::<ThreadSafe>::new("ARTICLE", Node::RawHtml(MARKDOWN))
+			.as_html()
+			.dom_len(),
+		1
+	);
+}
+
+#[test]
+fn refused_without_sanitizer() {
+	let element = Element::<ThreadSafe>::new("ARTICLE", Node::RawHtml(MARKDOWN));
+	let mut html = String::new();
+	assert_eq!(
+		html::write_node(&element.as_html(), &mut html),
+		Err(html::Error::RawHtmlRefused(MARKDOWN))
+	);
+}
+
+#[test]
+fn sanitized() {
+	let element = Element::<ThreadSafe>::new("ARTICLE", Node::RawHtml(MARKDOWN));
+	let mut html = String::new();
+	Serializer::new()
+		.with_sanitizer(&strip_em)
+		.write_node(&element.as_html(), &mut html)
+		.unwrap();
+	assert_eq!(html, "<ARTICLE><p>Hello world!</p></ARTICLE>");
+}
+
+#[test]
+fn refused_by_sanitizer() {
+	let script = TrustedHtml::assume_trusted("<script>alert(1)</script>");
+	let element = Element::<ThreadSafe>::new("ARTICLE", Node::RawHtml(script));
+	let mut html = String::new();
+	assert_eq!(
+		Serializer::new()
+			.with_sanitizer(&strip_em)
+			.write_node(&element.as_html(), &mut html),
+		Err(html::Error::RawHtmlRefused(script))
+	);
+}
+
+#[test]
+fn void_element() {
+	let element = Element::<ThreadSafe>::new("IMG", Node::RawHtml(MARKDOWN));
+	let mut html = String::new();
+	assert_eq!(
+		Serializer::new()
+			.with_sanitizer(&strip_em)
+			.write_node(&element.as_html(), &mut html),
+		Err(html::Error::VoidElementContent("IMG"))
+	);
+}
+
+#[test]
+fn comparisons() {
+	let raw = |html| -> Node<ThreadSafe> { Node::RawHtml(html) };
+	let text: Node<ThreadSafe> = MARKDOWN.as_str().into();
+	assert_eq!(raw(MARKDOWN), raw(MARKDOWN));
+	assert_ne!(
+		raw(MARKDOWN),
+		raw(TrustedHtml::assume_trusted("<p>Bye!</p>"))
+	);
+	assert_ne!(raw(MARKDOWN), text);
+}
@@ -0,0 +1,151 @@
+use lignin::{
+	html, Element, Node, ShadowRoot, ShadowRootMode, ShadowRootOptions, SlotAssignmentMode,
+	ThreadSafe,
+};
+
+const SHADOW_CONTENT: &[Node<ThreadSafe>] = &[
+	Node::Text {
+		text: "a",
+		dom_binding: None,
+	},
+	Node::Text {
+		text: "b",
+		dom_binding: None,
+	},
+];
+
+#[test]
+fn options() {
+	let options = ShadowRootOptions::new();
+	assert_eq!(options, ShadowRootOptions::default());
+	assert_eq!(options.mode(), ShadowRootMode::Open);
+	assert!(!options.delegates_focus());
+	assert_eq!(options.slot_assignment(), SlotAssignmentMode::Named);
+
+	let mut options = options
+		.with_mode(ShadowRootMode::Closed)
+		.with_delegates_focus(true);
+	options.set_slot_assignment(SlotAssignmentMode::Manual);
+	assert_eq!(options.mode(), ShadowRootMode::Closed);
+	assert!(options.delegates_focus());
+	assert_eq!(options.slot_assignment(), SlotAssignmentMode::Manual);
+}
+
+#[test]
+fn dom_len_ignores_shadow_content() {
+	let shadow_root = ShadowRoot {
+		options: ShadowRootOptions::new(),
+		content: Node::Multi(SHADOW_CONTENT),
+		dom_binding: None,
+	};
+	let element = Element {
+		shadow_root: Some(&shadow_root),
+		..Element::<ThreadSafe>::new("X-HOST", Node::Multi(&[]))
+	};
+	let node = element.as_html();
+	assert_eq!(node.dom_len(), 1);
+	assert!(element.content.dom_empty());
+	assert_eq!(shadow_root.content.dom_len(), 2);
+}
+
+#[test]
+fn comparisons() {
+	let open = ShadowRoot {
+		options: ShadowRootOptions::new(),
+		content: Node::Multi(SHADOW_CONTENT),
+		dom_binding: None,
+	};
+	let closed = ShadowRoot {
+		options: ShadowRootOptions::new().with_mode(ShadowRootMode::Closed),
+		..open
+	};
+	let without = Element::<ThreadSafe>::new("X-HOST", Node::Multi(&[]));
+	let with_open = Element {
+		shadow_root: Some(&open),
+		..without
+	};
+	let with_closed = Element {
+		shadow_root: Some(&closed),
+		..without
+	};
+	let light_only = Element::new("X-HOST", Node::Multi(SHADOW_CONTENT));
+	assert_eq!(
+		with_open,
+		Element {
+			shadow_root: Some(&open),
+			..Element::new("X-HOST", Node::Multi(&[]))
+		}
+	);
+	assert_ne!(with_open, with_closed);
+	assert_ne!(with_open, without);
+	assert_ne!(light_only, with_open);
+}
+
+#[test]
+fn declarative_serialisation() {
+	let shadow_root = ShadowRoot {
+		options: ShadowRootOptions::new()
+			.with_mode(ShadowRootMode::Closed)
+			.with_slot_assignment(SlotAssignmentMode::Manual),
+		content: Node::Multi(SHADOW_CONTENT),
+		dom_binding: None,
+	};
+	let element = Element {
+		shadow_root: Some(&shadow_root),
+		..Element::<ThreadSafe>::new("X-HOST", "light".into())
+	};
+
+	let mut html = String::new();
+	html::write_node(&element.as_html(), &mut html).unwrap();
+	assert_eq!(
+		html,
+		r#"<X-HOST><template shadowrootmode="closed">ab</template>light</X-HOST>"#
+	);
+}
+
+#[test]
+fn void_element_shadow_root() {
+	let shadow_root = ShadowRoot {
+		options: ShadowRootOptions::new(),
+		content: Node::Multi(&[]),
+		dom_binding: None,
+	};
+	let element = Element {
+		shadow_root: Some(&shadow_root),
+		..Element::<ThreadSafe>::new("BR", Node::Multi(&[]))
+	};
+
+	let mut html = String::new();
+	assert_eq!(
+		html::write_node(&element.as_html(), &mut html),
+		Err(html::Error::VoidElementContent("BR"))
+	);
+}
+
+#[test]
+fn raw_text() {
+	/// Returns [`None`] iff the raw text is refused.
+	fn write(name: &str, texts: &[&'static str]) -> Option<String> {
+		let content: Vec<Node<ThreadSafe>> = texts.iter().map(|&text| text.into()).collect();
+		let element = Element::new(name, Node::Multi(&content));
+		let mut html = String::new();
+		match html::write_node(&element.as_html(), &mut html) {
+			Ok(()) => Some(html),
+			Err(html::Error::InvalidRawText(refused)) if refused == name => None,
+			Err(error) => panic!("{}", error),
+		}
+	}
+
+	assert_eq!(
+		write("script", &["if (a <", "/ b) {}"]).as_deref(),
+		Some("<script>if (a </ b) {}</script>")
+	);
+	assert_eq!(write("script", &["x</SCRIPT>"]), None);
+	assert_eq!(
+		write("script", &["x<", "/script><img src=x onerror=alert(1)>"]),
+		None
+	);
+	assert_eq!(write("script", &["x</scr", "ipt>"]), None);
+	assert_eq!(write("script", &["x</", "scr", "ipt"]), None);
+	assert_eq!(write("STYLE", &["a<<", "/sty", "le"]), None);
+}
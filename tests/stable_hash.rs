@@ -0,0 +1,137 @@
+use lignin::{
+	stable_hash::StableHashOptions, web::Event, CallbackRegistration, Element, EventBinding,
+	EventBindingOptions, EventOutcome, Node, StyleDeclaration, ThreadSafe,
+};
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+	bytes.iter().fold(0xcbf2_9ce4_8422_2325, |state, byte| {
+		(state ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+	})
+}
+
+#[test]
+fn fixed_encoding() {
+	let text: Node<ThreadSafe> = "a".into();
+	assert_eq!(
+		text.stable_hash(StableHashOptions::new()),
+		fnv1a(&[7, 1, 0, 0, 0, 0, 0, 0, 0, b'a'])
+	);
+
+	let nodes = [text, text];
+	let multi: Node<ThreadSafe> = Node::Multi(&nodes);
+	assert_eq!(
+		multi.stable_hash(StableHashOptions::new()),
+		fnv1a(&[
+			5, 2, 0, 0, 0, 0, 0, 0, 0, //
+			7, 1, 0, 0, 0, 0, 0, 0, 0, b'a', //
+			7, 1, 0, 0, 0, 0, 0, 0, 0, b'a',
+		])
+	);
+}
+
+#[test]
+fn consistent_with_eq() {
+	let styles_1 = [
+		StyleDeclaration {
+			property: "color",
+			value: "red",
+			important: false,
+		},
+		StyleDeclaration {
+			property: "width",
+			value: "1px",
+			important: true,
+		},
+	];
+	let styles_2 = [styles_1[1], styles_1[0]];
+	let a = Element {
+		classes: &["a", "b"],
+		styles: &styles_1,
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+	let b = Element {
+		classes: &["b", "a"],
+		styles: &styles_2,
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+	assert_eq!(a, b);
+	assert_eq!(
+		a.as_html().stable_hash(StableHashOptions::new()),
+		b.as_html().stable_hash(StableHashOptions::new())
+	);
+
+	let c = Element {
+		classes: &["a", "c"],
+		styles: &styles_1,
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+	assert_ne!(
+		a.as_html().stable_hash(StableHashOptions::new()),
+		c.as_html().stable_hash(StableHashOptions::new())
+	);
+}
+
+#[test]
+fn memoized_by_state_key() {
+	let a: Node<ThreadSafe> = "a".into();
+	let b: Node<ThreadSafe> = "b".into();
+	let memoized = |state_key, content| Node::Memoized { state_key, content };
+	assert_eq!(
+		memoized(1, &a).stable_hash(StableHashOptions::new()),
+		memoized(1, &b).stable_hash(StableHashOptions::new())
+	);
+	assert_ne!(
+		memoized(1, &a).stable_hash(StableHashOptions::new()),
+		memoized(2, &a).stable_hash(StableHashOptions::new())
+	);
+}
+
+#[test]
+fn callbacks() {
+	let receiver = Box::pin(());
+	let registration_1 =
+		CallbackRegistration::<_, fn(Event) -> EventOutcome>::new(receiver.as_ref(), |_, _| {
+			EventOutcome::new()
+		});
+	let registration_2 =
+		CallbackRegistration::<_, fn(Event) -> EventOutcome>::new(receiver.as_ref(), |_, _| {
+			EventOutcome::new()
+		});
+	let bindings_1 = [EventBinding {
+		name: "click",
+		callback: registration_1.to_ref(),
+		options: EventBindingOptions::new(),
+	}];
+	let bindings_2 = [EventBinding {
+		callback: registration_2.to_ref(),
+		..bindings_1[0]
+	}];
+	let a = Element {
+		event_bindings: &bindings_1,
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+	let b = Element {
+		event_bindings: &bindings_2,
+		..Element::<ThreadSafe>::new("DIV", Node::Multi(&[]))
+	};
+
+	let options = StableHashOptions::new();
+	assert!(!options.callbacks());
+	assert_eq!(
+		a.as_html().stable_hash(options),
+		b.as_html().stable_hash(options)
+	);
+
+	let options = options.with_callbacks(true);
+	assert!(options.callbacks());
+	assert_eq!(
+		a.as_html().stable_hash(options),
+		a.as_html().stable_hash(options)
+	);
+	if cfg!(feature = "callbacks") {
+		assert_ne!(
+			a.as_html().stable_hash(options),
+			b.as_html().stable_hash(options)
+		);
+	}
+}
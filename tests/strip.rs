@@ -0,0 +1,120 @@
+use bumpalo::Bump;
+use lignin::{
+	arena::Arena, strip::StripOptions, web::Event, AttributeValue, CallbackRegistration, Element,
+	EventBinding, EventBindingOptions, EventOutcome, Node, ThreadBound, ThreadSafe,
+};
+use std::{cell::Cell, ptr};
+
+struct Frame(Bump);
+impl Arena for Frame {
+	fn alloc<T: Copy>(&self, value: T) -> &T {
+		self.0.alloc(value)
+	}
+	fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T] {
+		self.0.alloc_slice_copy(slice)
+	}
+	fn alloc_str(&self, string: &str) -> &str {
+		self.0.alloc_str(string)
+	}
+	fn alloc_slice_fill_with<T: Copy>(&self, len: usize, f: impl FnMut(usize) -> T) -> &[T] {
+		self.0.alloc_slice_fill_with(len, f)
+	}
+}
+
+#[test]
+fn strip() {
+	// A `!Sync` receiver, so that none of this is `ThreadSafe` to begin with.
+	let receiver = Box::pin(Cell::new(0));
+	let click =
+		CallbackRegistration::<_, fn(Event) -> EventOutcome>::new(receiver.as_ref(), |_, _| {
+			EventOutcome::new()
+		});
+	let text_ref = CallbackRegistration::<_, fn(lignin::DomRef<&'_ lignin::web::Text>)>::new(
+		receiver.as_ref(),
+		|_, _| (),
+	);
+
+	let unchanged = Element::<ThreadBound>::new("DIV", "unchanged".into());
+	let bindings = [EventBinding {
+		name: "click",
+		callback: click.to_ref_thread_bound(),
+		options: EventBindingOptions::new(),
+	}];
+	let nodes = [
+		Node::HtmlElement {
+			element: &unchanged,
+			dom_binding: None,
+		},
+		Node::Text {
+			text: "bound",
+			dom_binding: Some(text_ref.to_ref_thread_bound()),
+		},
+	];
+	let memoized = Node::Memoized {
+		state_key: 7,
+		content: &Node::Multi(&nodes),
+	};
+	let root = Element {
+		event_bindings: &bindings,
+		..Element::new("DIV", memoized)
+	};
+	let node: Node<ThreadBound> = Node::HtmlElement {
+		element: &root,
+		dom_binding: None,
+	};
+
+	let arena = Frame(Bump::new());
+	let stripped: Node<ThreadSafe> = node.strip_callbacks(
+		&arena,
+		StripOptions::new().with_event_names_attribute(Some("data-on")),
+	);
+
+	let root = match stripped {
+		Node::HtmlElement {
+			element,
+			dom_binding: None,
+		} => element,
+		_ => unreachable!(),
+	};
+	assert!(root.event_bindings.is_empty());
+	assert_eq!(root.attributes.len(), 1);
+	assert_eq!(root.attributes[0].name, "data-on");
+	assert_eq!(root.attributes[0].value, AttributeValue::Tokens(&["click"]));
+
+	let nodes = match root.content {
+		Node::Memoized {
+			state_key: 7,
+			content: Node::Multi(nodes),
+		} => nodes,
+		_ => unreachable!(),
+	};
+	match nodes {
+		[Node::HtmlElement {
+			element,
+			dom_binding: None,
+		}, Node::Text {
+			text: "bound",
+			dom_binding: None,
+		}] => assert!(ptr::eq(
+			*element as *const _ as *const (),
+			&unchanged as *const _ as *const ()
+		)),
+		_ => unreachable!(),
+	}
+}
+
+#[test]
+fn reuse_without_callbacks() {
+	let nodes = ["a".into(), "b".into()];
+	let node: Node<ThreadBound> = Node::Multi(&nodes);
+
+	let arena = Frame(Bump::new());
+	match node.strip_callbacks(&arena, StripOptions::new()) {
+		Node::Multi(stripped) => assert!(ptr::eq(
+			stripped.as_ptr() as *const (),
+			nodes.as_ptr() as *const ()
+		)),
+		_ => unreachable!(),
+	}
+	assert_eq!(arena.0.allocated_bytes(), 0);
+}
@@ -0,0 +1,120 @@
+use lignin::{
+	web::Event, CallbackRegistration, Element, EventBinding, EventBindingOptions, EventOutcome,
+	Node, ThreadBound, ThreadSafe,
+};
+use std::cell::Cell;
+
+fn click<S: lignin::ThreadSafety>(
+	callback: lignin::CallbackRef<S, fn(Event) -> EventOutcome>,
+) -> EventBinding<'static, S> {
+	EventBinding {
+		name: "click",
+		callback,
+		options: EventBindingOptions::new(),
+	}
+}
+
+#[test]
+fn without_callbacks_in_graph() {
+	let nodes: [Node<ThreadBound>; 2] = [
+		"a".into(),
+		Node::Comment {
+			comment: "b",
+			dom_binding: None,
+		},
+	];
+	let promoted: Node<ThreadSafe> = Node::Multi(&nodes).try_into_thread_safe().unwrap();
+	assert_eq!(promoted.dom_len(), 2);
+}
+
+#[test]
+fn sync_receiver() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(Event) -> EventOutcome>::new(receiver.as_ref(), |_, _| {
+			EventOutcome::new()
+		});
+	registration.mark_thread_safe();
+
+	let bindings = [click(registration.to_ref_thread_bound())];
+	let element = Element {
+		event_bindings: &bindings,
+		..Element::new("BUTTON", "Click me!".into())
+	};
+	let node = Node::HtmlElement {
+		element: &element,
+		dom_binding: None,
+	};
+
+	let promoted = node.try_into_thread_safe().unwrap();
+	match promoted {
+		Node::HtmlElement { element, .. } => {
+			assert_eq!(element.event_bindings[0].callback, registration.to_ref());
+		}
+		_ => unreachable!(),
+	}
+}
+
+#[test]
+#[cfg_attr(not(feature = "callbacks"), ignore = "only with callbacks")]
+fn thread_bound_receiver() {
+	let receiver = Box::pin(Cell::new(0));
+	let registration =
+		CallbackRegistration::<_, fn(Event) -> EventOutcome>::new(receiver.as_ref(), |_, _| {
+			EventOutcome::new()
+		});
+
+	let bindings = [click(registration.to_ref_thread_bound())];
+	let inner = Element {
+		event_bindings: &bindings,
+		..Element::new("BUTTON", Node::Multi(&[]))
+	};
+	let content = [Node::HtmlElement {
+		element: &inner,
+		dom_binding: None,
+	}];
+	let outer = Element::new("BUTTON", Node::Multi(&content));
+	let node = Node::HtmlElement {
+		element: &outer,
+		dom_binding: None,
+	};
+
+	assert_eq!(node.try_into_thread_safe(), Err(node));
+	assert!(registration
+		.to_ref_thread_bound()
+		.try_into_thread_safe()
+		.is_err());
+}
+
+#[test]
+#[cfg_attr(not(feature = "callbacks"), ignore = "only with callbacks")]
+fn marked_until_dropped() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(Event) -> EventOutcome>::new(receiver.as_ref(), |_, _| {
+			EventOutcome::new()
+		});
+	let callback_ref = registration.to_ref_thread_bound();
+	assert!(callback_ref.try_into_thread_safe().is_err());
+
+	registration.mark_thread_safe();
+	assert!(callback_ref.try_into_thread_safe().is_ok());
+
+	drop(registration);
+	assert!(callback_ref.try_into_thread_safe().is_err());
+}
+
+#[test]
+#[cfg_attr(not(feature = "callbacks"), ignore = "only with callbacks")]
+fn recorded_by_to_ref() {
+	let receiver = Box::pin(());
+	let registration =
+		CallbackRegistration::<_, fn(Event) -> EventOutcome>::new(receiver.as_ref(), |_, _| {
+			EventOutcome::new()
+		});
+	let callback_ref = registration.to_ref_thread_bound();
+	assert!(callback_ref.try_into_thread_safe().is_err());
+
+	let _ = registration.to_ref();
+	assert!(callback_ref.try_into_thread_safe().is_ok());
+}
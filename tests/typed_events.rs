@@ -0,0 +1,34 @@
+use lignin::{
+	events::{self, EventType},
+	web, CallbackRegistration, EventBinding, EventBindingOptions, EventOutcome, ThreadBound,
+};
+
+#[test]
+fn names() {
+	assert_eq!(events::Click::NAME, "click");
+	assert_eq!(events::KeyDown::NAME, "keydown");
+	assert_eq!(events::Input::NAME, "input");
+}
+
+#[test]
+fn typed_binding() {
+	let receiver = Box::pin(());
+	let registration = CallbackRegistration::<_, fn(web::KeyboardEvent) -> EventOutcome>::new(
+		receiver.as_ref(),
+		|_, _| EventOutcome::new().with_prevent_default(true),
+	);
+
+	let options = EventBindingOptions::new().with_passive(false);
+	let binding = EventBinding::<ThreadBound>::typed(
+		events::KeyDown,
+		registration.to_ref_thread_bound(),
+		options,
+	);
+
+	assert_eq!(binding.name, "keydown");
+	assert_eq!(binding.options, options);
+	assert_eq!(
+		binding.callback,
+		registration.to_ref_thread_bound().upcast()
+	);
+}
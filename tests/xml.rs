@@ -0,0 +1,176 @@
+use lignin::{
+	xml, Attribute, AttributeValue, Document, Element, Node, Property, PropertyValue, ShadowRoot,
+	ShadowRootOptions, ThreadSafe, TrustedHtml,
+};
+
+fn write<'a>(node: &Node<'a, ThreadSafe>) -> Result<String, xml::Error<'a>> {
+	let mut xml = String::new();
+	xml::write_node(node, &mut xml).map(|()| xml)
+}
+
+#[test]
+fn namespaces() {
+	let href = [Attribute {
+		name: "xlink:href",
+		value: AttributeValue::String("#a"),
+	}];
+	let mi = Element::<ThreadSafe>::new("mi", "x".into());
+	let math_content = [Node::MathMlElement {
+		element: &mi,
+		dom_binding: None,
+	}];
+	let math = Element::<ThreadSafe>::new("math", Node::Multi(&math_content));
+	let p_content = [Node::MathMlElement {
+		element: &math,
+		dom_binding: None,
+	}];
+	let p = Element::<ThreadSafe>::new("P", Node::Multi(&p_content));
+	let foreign_content = [p.as_html()];
+	let foreign = Element::<ThreadSafe>::new("foreignObject", Node::Multi(&foreign_content));
+	let r#use = Element {
+		attributes: &href,
+		..Element::<ThreadSafe>::new("use", Node::Multi(&[]))
+	};
+	let svg_content = [r#use.as_svg(), foreign.as_svg()];
+	let svg = Element::<ThreadSafe>::new("svg", Node::Multi(&svg_content));
+
+	assert_eq!(
+		write(&svg.as_svg()).unwrap(),
+		"<svg xmlns=\"http://www.w3.org/2000/svg\">\
+			<use xmlns:xlink=\"http://www.w3.org/1999/xlink\" xlink:href=\"#a\"/>\
+			<foreignObject><p xmlns=\"http://www.w3.org/1999/xhtml\">\
+				<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mi>x</mi></math>\
+			</p></foreignObject>\
+		</svg>"
+	);
+}
+
+#[test]
+fn escaping_and_booleans() {
+	let attributes = [
+		Attribute::new("title", AttributeValue::String("\"a\"\tb\n")),
+		Attribute::new("disabled", AttributeValue::Bool(true)),
+		Attribute::new("hidden", AttributeValue::Bool(false)),
+	];
+	let button = Element {
+		attributes: &attributes,
+		..Element::<ThreadSafe>::new("BUTTON", "<&>\r".into())
+	};
+	assert_eq!(
+		write(&button.as_html()).unwrap(),
+		"<button xmlns=\"http://www.w3.org/1999/xhtml\" title=\"&quot;a&quot;&#9;b&#10;\" disabled=\"disabled\">&lt;&amp;&gt;&#13;</button>"
+	);
+
+	let properties = [
+		Property {
+			name: "checked",
+			value: PropertyValue::Bool(false),
+		},
+		Property {
+			name: "required",
+			value: PropertyValue::Bool(true),
+		},
+	];
+	let input = Element {
+		properties: &properties,
+		..Element::<ThreadSafe>::new("INPUT", Node::Multi(&[]))
+	};
+	assert_eq!(
+		write(&input.as_html()).unwrap(),
+		"<input xmlns=\"http://www.w3.org/1999/xhtml\" required=\"required\"/>"
+	);
+}
+
+#[test]
+fn cdata() {
+	let script = Element::<ThreadSafe>::new("script", "if (a < b && c]]>d) {}".into());
+	assert_eq!(
+		write(&script.as_svg()).unwrap(),
+		"<script xmlns=\"http://www.w3.org/2000/svg\"><![CDATA[if (a < b && c]]]]><![CDATA[>d) {}]]></script>"
+	);
+}
+
+#[test]
+fn errors() {
+	let comment: Node<ThreadSafe> = Node::Comment {
+		comment: "a--b",
+		dom_binding: None,
+	};
+	assert_eq!(write(&comment), Err(xml::Error::InvalidComment("a--b")));
+
+	let text: Node<ThreadSafe> = "\u{1}".into();
+	assert_eq!(write(&text), Err(xml::Error::InvalidCharacter("\u{1}")));
+
+	const HTML: TrustedHtml<'static> = TrustedHtml::assume_trusted("<b>");
+	assert_eq!(
+		write(&Node::RawHtml(HTML)),
+		Err(xml::Error::UnsupportedRawHtml(HTML))
+	);
+
+	let shadow_root = ShadowRoot {
+		options: ShadowRootOptions::new(),
+		content: Node::Multi(&[]),
+		dom_binding: None,
+	};
+	let host = Element {
+		shadow_root: Some(&shadow_root),
+		..Element::<ThreadSafe>::new("X-HOST", Node::Multi(&[]))
+	};
+	assert_eq!(
+		write(&host.as_html()),
+		Err(xml::Error::UnsupportedShadowRoot("X-HOST"))
+	);
+
+	let attributes = [Attribute::new("ev:event", AttributeValue::String("click"))];
+	let r#use = Element {
+		attributes: &attributes,
+		..Element::<ThreadSafe>::new("use", Node::Multi(&[]))
+	};
+	assert_eq!(
+		write(&r#use.as_svg()),
+		Err(xml::Error::UnsupportedNamespace("ev:event"))
+	);
+
+	let invalid = Element::<ThreadSafe>::new("1a", Node::Multi(&[]));
+	assert_eq!(write(&invalid.as_svg()), Err(xml::Error::InvalidName("1a")));
+}
+
+#[test]
+fn standalone() {
+	let svg = Element::<ThreadSafe>::new("svg", Node::Multi(&[]));
+	let mut file = String::new();
+	xml::write_standalone(&svg.as_svg(), &mut file).unwrap();
+	assert_eq!(
+		file,
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?><svg xmlns=\"http://www.w3.org/2000/svg\"/>"
+	);
+
+	let two = [svg.as_svg(), svg.as_svg()];
+	assert_eq!(
+		xml::write_standalone(&Node::Multi(&two), &mut String::new()),
+		Err(xml::Error::InvalidRoot)
+	);
+}
+
+#[test]
+fn xhtml_document() {
+	let title = Element::<ThreadSafe>::new("TITLE", "T".into());
+	let br = Element::<ThreadSafe>::new("BR", Node::Multi(&[]));
+	let document = Document {
+		doctype: "html",
+		html_attributes: &[Attribute::new("lang", AttributeValue::String("en"))],
+		head: title.as_html(),
+		body: br.as_html(),
+	};
+
+	let mut page = String::new();
+	xml::write_document(&document, &mut page).unwrap();
+	assert_eq!(
+		page,
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?><!DOCTYPE html>\
+			<html xmlns=\"http://www.w3.org/1999/xhtml\" lang=\"en\">\
+				<head><title>T</title></head>\
+				<body><br/></body>\
+			</html>"
+	);
+}